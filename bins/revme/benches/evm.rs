@@ -3,6 +3,7 @@ use revme::cmd::bench;
 
 fn evm(c: &mut Criterion) {
     bench::analysis::run(c);
+    bench::arithmetic::run(c);
     bench::burntpix::run(c);
     bench::snailtracer::run(c);
     bench::transfer::run(c);
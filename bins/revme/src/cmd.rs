@@ -2,6 +2,7 @@ pub mod bench;
 pub mod blockchaintest;
 pub mod bytecode;
 pub mod evmrunner;
+pub mod optest;
 pub mod statetest;
 
 use clap::Parser;
@@ -24,6 +25,8 @@ pub enum MainCmd {
     Blockchaintest(blockchaintest::Cmd),
     /// Execute Ethereum blockchain tests.
     Btest(blockchaintest::Cmd),
+    /// Execute OP Stack tests.
+    Optest(optest::Cmd),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -33,6 +36,8 @@ pub enum Error {
     #[error(transparent)]
     Blockchaintest(#[from] blockchaintest::Error),
     #[error(transparent)]
+    Optest(#[from] optest::Error),
+    #[error(transparent)]
     EvmRunnerErrors(#[from] evmrunner::Errors),
     #[error("Custom error: {0}")]
     Custom(&'static str),
@@ -50,6 +55,7 @@ impl MainCmd {
                 cmd.run();
             }
             Self::Blockchaintest(cmd) | Self::Btest(cmd) => cmd.run()?,
+            Self::Optest(cmd) => cmd.run()?,
         }
         Ok(())
     }
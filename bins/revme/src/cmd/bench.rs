@@ -1,8 +1,10 @@
 pub mod analysis;
+pub mod arithmetic;
 pub mod burntpix;
 pub mod evm_build;
 pub mod gas_cost_estimator;
 pub mod snailtracer;
+pub mod threaded;
 pub mod transfer;
 pub mod transfer_multi;
 
@@ -11,8 +13,10 @@ use clap::{Parser, ValueEnum};
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum BenchName {
     Analysis,
+    Arithmetic,
     Burntpix,
     Snailtracer,
+    Threaded,
     Transfer,
     EvmBuild,
     TransferMulti,
@@ -22,8 +26,10 @@ pub enum BenchName {
 impl BenchName {
     pub const ALL: &[BenchName] = &[
         BenchName::Analysis,
+        BenchName::Arithmetic,
         BenchName::Burntpix,
         BenchName::Snailtracer,
+        BenchName::Threaded,
         BenchName::Transfer,
         BenchName::TransferMulti,
         BenchName::EvmBuild,
@@ -33,8 +39,10 @@ impl BenchName {
     pub fn as_str(self) -> &'static str {
         match self {
             BenchName::Analysis => "analysis",
+            BenchName::Arithmetic => "arithmetic",
             BenchName::Burntpix => "burntpix",
             BenchName::Snailtracer => "snailtracer",
+            BenchName::Threaded => "threaded",
             BenchName::Transfer => "transfer",
             BenchName::EvmBuild => "evm-build",
             BenchName::TransferMulti => "transfer-multi",
@@ -74,12 +82,18 @@ impl Cmd {
             BenchName::Analysis => {
                 analysis::run(&mut criterion);
             }
+            BenchName::Arithmetic => {
+                arithmetic::run(&mut criterion);
+            }
             BenchName::Burntpix => {
                 burntpix::run(&mut criterion);
             }
             BenchName::Snailtracer => {
                 snailtracer::run(&mut criterion);
             }
+            BenchName::Threaded => {
+                threaded::run(&mut criterion);
+            }
             BenchName::Transfer => {
                 transfer::run(&mut criterion);
             }
@@ -0,0 +1,68 @@
+use criterion::{BenchmarkId, Criterion};
+use revm::{interpreter::instructions::u256, primitives::U256};
+use std::hint::black_box;
+
+/// Operand sets, from small values that take the fast paths to full-width values.
+fn operands() -> [(&'static str, U256, U256, U256); 3] {
+    let big = U256::MAX - U256::from(0x1234_5678_9abc_def0u64);
+    [
+        (
+            "small",
+            U256::from(0x1234_5678u64),
+            U256::from(0x9abc_def0u64),
+            U256::from(1_000_000_007u64),
+        ),
+        (
+            "u128",
+            U256::from(u128::MAX / 3),
+            U256::from(u128::MAX / 7),
+            U256::MAX >> 1,
+        ),
+        ("full", big, big >> 3, big >> 1),
+    ]
+}
+
+pub fn run(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("arithmetic");
+    for (name, a, b, n) in operands() {
+        let exp = U256::from(0x1f);
+
+        group.bench_function(BenchmarkId::new("mul/fast", name), |bench| {
+            bench.iter(|| u256::mul(black_box(a), black_box(b)))
+        });
+        group.bench_function(BenchmarkId::new("mul/generic", name), |bench| {
+            bench.iter(|| black_box(a).wrapping_mul(black_box(b)))
+        });
+        group.bench_function(BenchmarkId::new("div/fast", name), |bench| {
+            bench.iter(|| u256::div(black_box(a), black_box(b)))
+        });
+        group.bench_function(BenchmarkId::new("div/generic", name), |bench| {
+            bench.iter(|| black_box(a).wrapping_div(black_box(b)))
+        });
+        group.bench_function(BenchmarkId::new("mod/fast", name), |bench| {
+            bench.iter(|| u256::rem(black_box(a), black_box(b)))
+        });
+        group.bench_function(BenchmarkId::new("mod/generic", name), |bench| {
+            bench.iter(|| black_box(a).wrapping_rem(black_box(b)))
+        });
+        group.bench_function(BenchmarkId::new("addmod/fast", name), |bench| {
+            bench.iter(|| u256::add_mod(black_box(a), black_box(b), black_box(n)))
+        });
+        group.bench_function(BenchmarkId::new("addmod/generic", name), |bench| {
+            bench.iter(|| black_box(a).add_mod(black_box(b), black_box(n)))
+        });
+        group.bench_function(BenchmarkId::new("mulmod/fast", name), |bench| {
+            bench.iter(|| u256::mul_mod(black_box(a), black_box(b), black_box(n)))
+        });
+        group.bench_function(BenchmarkId::new("mulmod/generic", name), |bench| {
+            bench.iter(|| black_box(a).mul_mod(black_box(b), black_box(n)))
+        });
+        group.bench_function(BenchmarkId::new("exp/fast", name), |bench| {
+            bench.iter(|| u256::pow(black_box(a), black_box(exp)))
+        });
+        group.bench_function(BenchmarkId::new("exp/generic", name), |bench| {
+            bench.iter(|| black_box(a).pow(black_box(exp)))
+        });
+    }
+    group.finish();
+}
@@ -0,0 +1,50 @@
+use criterion::Criterion;
+use std::sync::Arc;
+
+use revm::{
+    bytecode::Bytecode,
+    context::TxEnv,
+    database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET},
+    interpreter::ThreadedCodeCache,
+    primitives::{bytes, hex, Bytes, TxKind},
+    Context, ExecuteEvm, MainBuilder, MainContext,
+};
+
+/// Runs snailtracer with the plain interpreter loop and with the threaded code backend.
+pub fn run(criterion: &mut Criterion) {
+    let bytecode = Bytecode::new_raw(Bytes::from(hex::decode(BYTES).unwrap()));
+
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(bytecode))
+        .modify_cfg_chained(|c| c.disable_nonce_check = true)
+        .build_mainnet();
+
+    let tx = TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(BENCH_TARGET))
+        .data(bytes!("30627b7c"))
+        .gas_limit(1_000_000_000)
+        .build()
+        .unwrap();
+
+    let mut group = criterion.benchmark_group("threaded");
+    group.bench_function("snailtracer-plain", |b| {
+        b.iter_batched(
+            || tx.clone(),
+            |input| evm.transact_one(input).unwrap(),
+            criterion::BatchSize::SmallInput,
+        );
+    });
+
+    evm.instruction.threaded_cache = Some(Arc::new(ThreadedCodeCache::default()));
+    group.bench_function("snailtracer-threaded", |b| {
+        b.iter_batched(
+            || tx.clone(),
+            |input| evm.transact_one(input).unwrap(),
+            criterion::BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+const BYTES: &str = include_str!("snailtracer.hex");
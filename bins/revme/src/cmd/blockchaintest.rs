@@ -5,7 +5,7 @@ use clap::Parser;
 
 use revm::{
     bytecode::Bytecode,
-    context::{cfg::CfgEnv, ContextTr},
+    context::{cfg::CfgEnv, Cfg, ChainHardforks, ContextTr, ForkCondition},
     context_interface::{block::BlobExcessGasAndPrice, result::HaltReason},
     database::{states::bundle_state::BundleRetention, EmptyDB, State},
     handler::EvmTr,
@@ -639,14 +639,11 @@ fn execute_blockchain_test(
     print_env_on_error: bool,
     json_output: bool,
 ) -> Result<(), TestExecutionError> {
-    // Skip all transition forks for now.
+    // Skip transition forks to specs that are not supported.
     if matches!(
         test_case.network,
-        ForkSpec::ByzantiumToConstantinopleAt5
-            | ForkSpec::ParisToShanghaiAtTime15k
-            | ForkSpec::ShanghaiToCancunAtTime15k
-            | ForkSpec::CancunToPragueAtTime15k
-            | ForkSpec::PragueToOsakaAtTime15k
+        ForkSpec::HomesteadToDaoAt5
+            | ForkSpec::ByzantiumToConstantinopleAt5
             | ForkSpec::BPO1ToBPO2AtTime15k
     ) {
         eprintln!("⚠️  Skipping transition fork: {:?}", test_case.network);
@@ -682,10 +679,8 @@ fn execute_blockchain_test(
         .block_hashes
         .insert(0, test_case.genesis_block_header.hash);
 
-    // Setup configuration based on fork
-    let spec_id = fork_to_spec_id(test_case.network);
-    let mut cfg = CfgEnv::default();
-    cfg.spec = spec_id;
+    // Setup configuration based on fork, the spec is updated per block for transition forks.
+    let mut cfg = CfgEnv::default().with_hardforks(fork_to_hardforks(test_case.network));
 
     // Genesis block is not used yet.
     let mut parent_block_hash = Some(test_case.genesis_block_header.hash);
@@ -695,6 +690,7 @@ fn execute_blockchain_test(
         .unwrap_or_default()
         .to::<u64>();
     let mut block_env = test_case.genesis_block_env();
+    cfg.update_spec_for_block(&block_env);
 
     // Process each block in the test
     for (block_idx, block) in test_case.blocks.iter().enumerate() {
@@ -714,15 +710,20 @@ fn execute_blockchain_test(
         if let Some(block_header) = block.block_header.as_ref() {
             block_hash = Some(block_header.hash);
             beacon_root = block_header.parent_beacon_block_root;
-            block_env = block_header.to_block_env(Some(BlobExcessGasAndPrice::new_with_spec(
+            block_env = block_header.to_block_env(None);
+            // The blob base fee depends on the spec of the block.
+            cfg.update_spec_for_block(&block_env);
+            block_env.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice::new_with_spec(
                 parent_excess_blob_gas,
-                spec_id,
-            )));
+                cfg.spec,
+            ));
             this_excess_blob_gas = block_header.excess_blob_gas.map(|i| i.to::<u64>());
         } else {
             this_excess_blob_gas = None;
         }
 
+        let spec_id = cfg.spec;
+
         // Create EVM context for each transaction to ensure fresh state access
         let evm_context = Context::mainnet()
             .with_block(&block_env)
@@ -961,28 +962,55 @@ fn execute_blockchain_test(
     Ok(())
 }
 
-/// Convert ForkSpec to SpecId
+/// Returns the fork schedule of a test network.
+///
+/// Transition forks switch spec at block 5 or at timestamp 15k.
+fn fork_to_hardforks(fork: ForkSpec) -> ChainHardforks {
+    use SpecId::*;
+    let at_5 = ForkCondition::Block(5);
+    let at_time_15k = ForkCondition::Timestamp(15_000);
+    match fork {
+        ForkSpec::FrontierToHomesteadAt5 => ChainHardforks::transition(FRONTIER, HOMESTEAD, at_5),
+        ForkSpec::HomesteadToEIP150At5 => ChainHardforks::transition(HOMESTEAD, TANGERINE, at_5),
+        ForkSpec::EIP158ToByzantiumAt5 => {
+            ChainHardforks::transition(SPURIOUS_DRAGON, BYZANTIUM, at_5)
+        }
+        ForkSpec::ByzantiumToConstantinopleAt5 | ForkSpec::ByzantiumToConstantinopleFixAt5 => {
+            ChainHardforks::transition(BYZANTIUM, PETERSBURG, at_5)
+        }
+        ForkSpec::BerlinToLondonAt5 => ChainHardforks::transition(BERLIN, LONDON, at_5),
+        ForkSpec::ParisToShanghaiAtTime15k => {
+            ChainHardforks::transition(MERGE, SHANGHAI, at_time_15k)
+        }
+        ForkSpec::ShanghaiToCancunAtTime15k => {
+            ChainHardforks::transition(SHANGHAI, CANCUN, at_time_15k)
+        }
+        ForkSpec::CancunToPragueAtTime15k => {
+            ChainHardforks::transition(CANCUN, PRAGUE, at_time_15k)
+        }
+        ForkSpec::PragueToOsakaAtTime15k => ChainHardforks::transition(PRAGUE, OSAKA, at_time_15k),
+        fork => ChainHardforks::new(vec![(fork_to_spec_id(fork), ForkCondition::Block(0))]),
+    }
+}
+
+/// Returns the spec of a test network that is not a transition fork.
 fn fork_to_spec_id(fork: ForkSpec) -> SpecId {
     match fork {
         ForkSpec::Frontier => SpecId::FRONTIER,
-        ForkSpec::Homestead | ForkSpec::FrontierToHomesteadAt5 => SpecId::HOMESTEAD,
-        ForkSpec::EIP150 | ForkSpec::HomesteadToDaoAt5 | ForkSpec::HomesteadToEIP150At5 => {
-            SpecId::TANGERINE
-        }
+        ForkSpec::Homestead => SpecId::HOMESTEAD,
+        ForkSpec::EIP150 => SpecId::TANGERINE,
         ForkSpec::EIP158 => SpecId::SPURIOUS_DRAGON,
-        ForkSpec::Byzantium
-        | ForkSpec::EIP158ToByzantiumAt5
-        | ForkSpec::ByzantiumToConstantinopleFixAt5 => SpecId::BYZANTIUM,
-        ForkSpec::Constantinople | ForkSpec::ByzantiumToConstantinopleAt5 => SpecId::PETERSBURG,
+        ForkSpec::Byzantium => SpecId::BYZANTIUM,
+        ForkSpec::Constantinople => SpecId::PETERSBURG,
         ForkSpec::ConstantinopleFix => SpecId::PETERSBURG,
         ForkSpec::Istanbul => SpecId::ISTANBUL,
         ForkSpec::Berlin => SpecId::BERLIN,
-        ForkSpec::London | ForkSpec::BerlinToLondonAt5 => SpecId::LONDON,
-        ForkSpec::Paris | ForkSpec::ParisToShanghaiAtTime15k => SpecId::MERGE,
+        ForkSpec::London => SpecId::LONDON,
+        ForkSpec::Paris => SpecId::MERGE,
         ForkSpec::Shanghai => SpecId::SHANGHAI,
-        ForkSpec::Cancun | ForkSpec::ShanghaiToCancunAtTime15k => SpecId::CANCUN,
-        ForkSpec::Prague | ForkSpec::CancunToPragueAtTime15k => SpecId::PRAGUE,
-        ForkSpec::Osaka | ForkSpec::PragueToOsakaAtTime15k => SpecId::OSAKA,
+        ForkSpec::Cancun => SpecId::CANCUN,
+        ForkSpec::Prague => SpecId::PRAGUE,
+        ForkSpec::Osaka => SpecId::OSAKA,
         _ => SpecId::OSAKA, // For any unknown forks, use latest available
     }
 }
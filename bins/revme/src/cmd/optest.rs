@@ -0,0 +1,88 @@
+mod runner;
+pub mod types;
+
+pub use runner::{execute_test_suite, TestError as Error, TestErrorKind};
+
+use crate::cmd::blockchaintest::find_all_json_tests;
+use clap::Parser;
+use runner::TestError;
+use std::path::PathBuf;
+
+/// `optest` subcommand
+///
+/// Executes OP Stack fixtures: deposit transactions, L1 and operator fee charging, system
+/// transactions and OP precompile limits. See [`types`] for the fixture format.
+#[derive(Parser, Debug)]
+pub struct Cmd {
+    /// Path to folder or file containing the tests
+    ///
+    /// If multiple paths are specified they will be run in sequence.
+    ///
+    /// Folders will be searched recursively for files with the extension `.json`.
+    #[arg(required = true, num_args = 1..)]
+    paths: Vec<PathBuf>,
+    /// Output results in JSON format
+    ///
+    /// Traces the execution of every fork.
+    #[arg(long)]
+    json: bool,
+    /// Output outcome of every fork in JSON format
+    ///
+    /// If `--json` is true, this is implied.
+    #[arg(short = 'o', long)]
+    json_outcome: bool,
+    /// Keep going after a test failure
+    #[arg(long, alias = "no-fail-fast")]
+    keep_going: bool,
+}
+
+impl Cmd {
+    /// Runs `optest` command.
+    pub fn run(&self) -> Result<(), TestError> {
+        let print_outcome = self.json || self.json_outcome;
+        let mut n_files = 0;
+        let mut n_forks = 0;
+        let mut errors = Vec::new();
+
+        for path in &self.paths {
+            let path_error = |kind| TestError {
+                name: "Path validation".to_string(),
+                path: path.display().to_string(),
+                kind,
+            };
+            if !path.exists() {
+                return Err(path_error(TestErrorKind::InvalidPath));
+            }
+
+            println!("\nRunning OP tests in {}...", path.display());
+            let test_files = find_all_json_tests(path);
+
+            if test_files.is_empty() {
+                return Err(path_error(TestErrorKind::NoJsonFiles));
+            }
+
+            n_files += test_files.len();
+            for test_file in test_files {
+                match execute_test_suite(&test_file, self.json, print_outcome) {
+                    Ok(executed) => n_forks += executed,
+                    Err(error) if self.keep_going => {
+                        println!("{error}");
+                        errors.push(error);
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            println!("All {n_forks} tests passed!");
+            Ok(())
+        } else {
+            println!(
+                "Encountered {} errors out of {n_files} total test files",
+                errors.len()
+            );
+            Err(errors.swap_remove(0))
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use super::types::{PostState, Status, TestSuite, TestUnit, Transaction};
+use op_revm::{
+    fee_estimate::encode_with_dummy_signature,
+    transaction::deposit::{DepositTransactionParts, DEPOSIT_TRANSACTION_TYPE},
+    DefaultOp, DepositReceipt, OpBuilder, OpHaltReason, OpSpecId, OpTransaction,
+    OpTransactionError,
+};
+use revm::{
+    bytecode::Bytecode,
+    context::{
+        block::BlockEnv,
+        cfg::CfgEnv,
+        tx::{TxEnv, TxEnvBuildError},
+        ContextTr,
+    },
+    context_interface::result::{EVMError, ExecutionResult},
+    database::{self, CacheState, EmptyDB},
+    handler::EvmTr,
+    inspector::{inspectors::TracerEip3155, InspectCommitEvm},
+    primitives::{keccak256, Bytes, TxKind, B256, U256},
+    state::AccountInfo,
+    Context, Database, ExecuteCommitEvm,
+};
+use serde_json::json;
+use std::{convert::Infallible, io::stderr, path::Path, str::FromStr};
+use thiserror::Error;
+
+/// Default chain ID of the tests, OP Mainnet.
+const DEFAULT_CHAIN_ID: u64 = 10;
+
+type ExecResult = Result<ExecutionResult<OpHaltReason>, EVMError<Infallible, OpTransactionError>>;
+
+/// Error that occurs during test execution
+#[derive(Debug, Error)]
+#[error("Path: {path}\nName: {name}\nError: {kind}")]
+pub struct TestError {
+    pub name: String,
+    pub path: String,
+    pub kind: TestErrorKind,
+}
+
+/// Specific kind of error that occurred during test execution
+#[derive(Debug, Error)]
+pub enum TestErrorKind {
+    #[error("unknown fork: {0}")]
+    UnknownFork(String),
+    #[error("invalid transaction: {0:?}")]
+    InvalidTransaction(TxEnvBuildError),
+    #[error("unexpected exception: got {got_exception:?}, expected {expected_exception:?}")]
+    UnexpectedException {
+        expected_exception: Option<String>,
+        got_exception: Option<String>,
+    },
+    #[error("unexpected status: got {got:?}, expected {expected:?}")]
+    UnexpectedStatus { got: Status, expected: Status },
+    #[error("unexpected gas used: got {got}, expected {expected}")]
+    UnexpectedGasUsed { got: u64, expected: U256 },
+    #[error("unexpected output: got {got_output:?}, expected {expected_output:?}")]
+    UnexpectedOutput {
+        expected_output: Option<Bytes>,
+        got_output: Option<Bytes>,
+    },
+    #[error("unexpected deposit receipt: got {got:?}, expected {expected:?}")]
+    UnexpectedDepositReceipt {
+        got: Option<DepositReceipt>,
+        expected: DepositReceipt,
+    },
+    #[error("{field} mismatch: got {got}, expected {expected}")]
+    StateMismatch {
+        field: String,
+        got: String,
+        expected: String,
+    },
+    #[error(transparent)]
+    SerdeDeserialize(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("path does not exist")]
+    InvalidPath,
+    #[error("no JSON test files found in path")]
+    NoJsonFiles,
+}
+
+/// Outcome of a test on a fork.
+struct ForkOutcome {
+    exec_result: ExecResult,
+    l1_fee: U256,
+    deposit_receipt: Option<DepositReceipt>,
+}
+
+/// Execute a single test suite file containing multiple tests
+///
+/// # Arguments
+/// * `path` - Path to the JSON test file
+/// * `trace` - Whether to enable EVM tracing
+/// * `print_json_outcome` - Whether to print JSON formatted results
+///
+/// Returns the number of executed forks.
+pub fn execute_test_suite(
+    path: &Path,
+    trace: bool,
+    print_json_outcome: bool,
+) -> Result<usize, TestError> {
+    let path = path.to_string_lossy().into_owned();
+    let error = |name: String, kind: TestErrorKind| TestError {
+        name,
+        path: path.clone(),
+        kind,
+    };
+
+    let s = std::fs::read_to_string(&path).map_err(|e| error("Unknown".to_string(), e.into()))?;
+    let suite: TestSuite =
+        serde_json::from_str(&s).map_err(|e| error("Unknown".to_string(), e.into()))?;
+
+    let mut executed = 0;
+    for (name, unit) in &suite.0 {
+        let cache_state = cache_state(unit);
+
+        for (fork, post) in &unit.post {
+            let test_name = || format!("{name} ({fork})");
+            let spec = OpSpecId::from_str(fork)
+                .map_err(|_| error(test_name(), TestErrorKind::UnknownFork(fork.clone())))?;
+
+            let mut state = database::State::builder()
+                .with_cached_prestate(cache_state.clone())
+                .with_bundle_update()
+                .build();
+            let result = execute_single_test(unit, spec, &mut state, trace).and_then(|outcome| {
+                let check = check_post_state(post, &outcome, &mut state);
+                if print_json_outcome {
+                    let json = build_json_output(name, fork, &outcome, check.as_ref().err());
+                    eprintln!("{json}");
+                }
+                check
+            });
+            result.map_err(|kind| error(test_name(), kind))?;
+            executed += 1;
+        }
+    }
+    Ok(executed)
+}
+
+/// Builds the pre-state of the test.
+fn cache_state(unit: &TestUnit) -> CacheState {
+    let mut cache_state = CacheState::new(true);
+    for (address, info) in &unit.pre {
+        let bytecode = Bytecode::new_raw_checked(info.code.clone())
+            .unwrap_or(Bytecode::new_legacy(info.code.clone()));
+        let acc_info = AccountInfo {
+            balance: info.balance,
+            code_hash: keccak256(&info.code),
+            code: Some(bytecode),
+            nonce: info.nonce,
+        };
+        cache_state.insert_account_with_storage(*address, acc_info, info.storage.clone());
+    }
+    cache_state
+}
+
+/// Builds the OP transaction of the test.
+///
+/// Non-deposit transactions without enveloped bytes are encoded with a dummy signature.
+fn op_transaction(tx: &Transaction, chain_id: u64) -> Result<OpTransaction<TxEnv>, TestErrorKind> {
+    let is_deposit = tx.source_hash.is_some();
+    let mut builder = TxEnv::builder()
+        .caller(tx.sender)
+        .kind(tx.to.map_or(TxKind::Create, TxKind::Call))
+        .value(tx.value)
+        .data(tx.data.clone())
+        .gas_limit(tx.gas_limit.saturating_to())
+        .gas_price(tx.gas_price.saturating_to())
+        .gas_priority_fee(tx.max_priority_fee_per_gas.map(|fee| fee.saturating_to()))
+        .nonce(tx.nonce.saturating_to())
+        .chain_id(Some(chain_id));
+    if is_deposit {
+        builder = builder.tx_type(Some(DEPOSIT_TRANSACTION_TYPE));
+    }
+    let base = builder.build().map_err(TestErrorKind::InvalidTransaction)?;
+
+    let enveloped_tx = if is_deposit {
+        None
+    } else {
+        tx.enveloped_tx
+            .clone()
+            .or_else(|| encode_with_dummy_signature(&base))
+    };
+
+    Ok(OpTransaction {
+        base,
+        enveloped_tx,
+        deposit: DepositTransactionParts::new(
+            tx.source_hash.unwrap_or_default(),
+            tx.mint.map(|mint| mint.saturating_to()),
+            tx.is_system_tx,
+        ),
+    })
+}
+
+fn execute_single_test(
+    unit: &TestUnit,
+    spec: OpSpecId,
+    state: &mut database::State<EmptyDB>,
+    trace: bool,
+) -> Result<ForkOutcome, TestErrorKind> {
+    let mut cfg = CfgEnv::new_with_spec(spec);
+    cfg.chain_id = unit
+        .env
+        .chain_id
+        .map_or(DEFAULT_CHAIN_ID, |chain_id| chain_id.saturating_to());
+
+    let block = BlockEnv {
+        number: unit.env.number,
+        beneficiary: unit.env.coinbase,
+        timestamp: unit.env.timestamp,
+        gas_limit: unit.env.gas_limit.saturating_to(),
+        basefee: unit.env.base_fee.saturating_to(),
+        prevrandao: Some(B256::ZERO),
+        ..BlockEnv::default()
+    };
+
+    // Without L1 block info, it is fetched from the database for the block.
+    let l1_block_info = unit
+        .l1_block_info
+        .as_ref()
+        .map(|l1_block| l1_block.l1_block_info(block.number, spec))
+        .unwrap_or_default();
+
+    let tx = op_transaction(&unit.transaction, cfg.chain_id)?;
+
+    let ctx = Context::op()
+        .with_db(state)
+        .with_block(block)
+        .with_cfg(cfg)
+        .with_chain(l1_block_info);
+
+    let (exec_result, mut l1_block_info) = if trace {
+        let mut evm =
+            ctx.build_op_with_inspector(TracerEip3155::buffered(stderr()).without_summary());
+        let res = evm.inspect_tx_commit(tx.clone());
+        (res, evm.ctx().chain().clone())
+    } else {
+        let mut evm = ctx.build_op();
+        let res = evm.transact_commit(tx.clone());
+        (res, evm.ctx().chain().clone())
+    };
+
+    let l1_fee = tx
+        .enveloped_tx
+        .as_ref()
+        .map(|enveloped_tx| l1_block_info.calculate_tx_l1_cost(enveloped_tx, spec))
+        .unwrap_or_default();
+
+    let deposit_receipt = exec_result
+        .as_ref()
+        .ok()
+        .and_then(|res| res.deposit_receipt);
+
+    Ok(ForkOutcome {
+        exec_result: exec_result.map(|res| res.result),
+        l1_fee,
+        deposit_receipt,
+    })
+}
+
+fn check_post_state(
+    post: &PostState,
+    outcome: &ForkOutcome,
+    state: &mut database::State<EmptyDB>,
+) -> Result<(), TestErrorKind> {
+    let exec_result = &outcome.exec_result;
+    let result = match (&post.expect_exception, exec_result) {
+        // Exception expected and occurred
+        (Some(_), Err(_)) => return Ok(()),
+        (None, Ok(result)) => result,
+        _ => {
+            return Err(TestErrorKind::UnexpectedException {
+                expected_exception: post.expect_exception.clone(),
+                got_exception: exec_result.as_ref().err().map(|e| e.to_string()),
+            })
+        }
+    };
+
+    if let Some(expected) = post.status {
+        let got = match result {
+            ExecutionResult::Success { .. } => Status::Success,
+            ExecutionResult::Revert { .. } => Status::Revert,
+            ExecutionResult::Halt { .. } => Status::Halt,
+        };
+        if got != expected {
+            return Err(TestErrorKind::UnexpectedStatus { got, expected });
+        }
+    }
+
+    if let Some(expected) = post.gas_used {
+        if U256::from(result.gas_used()) != expected {
+            return Err(TestErrorKind::UnexpectedGasUsed {
+                got: result.gas_used(),
+                expected,
+            });
+        }
+    }
+
+    if let Some(expected) = &post.output {
+        if result.output() != Some(expected) {
+            return Err(TestErrorKind::UnexpectedOutput {
+                expected_output: Some(expected.clone()),
+                got_output: result.output().cloned(),
+            });
+        }
+    }
+
+    if let Some(expected) = &post.deposit_receipt {
+        let expected = expected.deposit_receipt();
+        if outcome.deposit_receipt != Some(expected) {
+            return Err(TestErrorKind::UnexpectedDepositReceipt {
+                got: outcome.deposit_receipt,
+                expected,
+            });
+        }
+    }
+
+    for (&address, expected) in &post.state {
+        let info = state.basic(address).unwrap().unwrap_or_default();
+        let mismatch = |field: &str, got: String, expected: String| TestErrorKind::StateMismatch {
+            field: format!("{address} {field}"),
+            got,
+            expected,
+        };
+
+        if let Some(balance) = expected.balance {
+            if info.balance != balance {
+                return Err(mismatch(
+                    "balance",
+                    info.balance.to_string(),
+                    balance.to_string(),
+                ));
+            }
+        }
+        if let Some(nonce) = expected.nonce {
+            if U256::from(info.nonce) != nonce {
+                return Err(mismatch("nonce", info.nonce.to_string(), nonce.to_string()));
+            }
+        }
+        if let Some(code) = &expected.code {
+            let got = info
+                .code
+                .map(|code| code.original_bytes())
+                .unwrap_or_default();
+            if got != *code {
+                return Err(mismatch("code", got.to_string(), code.to_string()));
+            }
+        }
+        for (&slot, &expected) in &expected.storage {
+            let got = state.storage(address, slot).unwrap();
+            if got != expected {
+                return Err(mismatch(
+                    &format!("storage slot {slot}"),
+                    got.to_string(),
+                    expected.to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn build_json_output(
+    test_name: &str,
+    fork: &str,
+    outcome: &ForkOutcome,
+    error: Option<&TestErrorKind>,
+) -> serde_json::Value {
+    let exec_result = &outcome.exec_result;
+    json!({
+        "output": exec_result.as_ref().ok().and_then(|r| r.output().cloned()).unwrap_or_default(),
+        "gasUsed": exec_result.as_ref().ok().map(|r| r.gas_used()).unwrap_or_default(),
+        "l1Fee": outcome.l1_fee,
+        "depositNonce": outcome.deposit_receipt.and_then(|r| r.deposit_nonce),
+        "depositReceiptVersion": outcome.deposit_receipt.and_then(|r| r.deposit_receipt_version),
+        "pass": error.is_none(),
+        "errorMsg": error.map(|e| e.to_string()).unwrap_or_default(),
+        "evmResult": format_evm_result(exec_result),
+        "fork": fork,
+        "test": test_name,
+    })
+}
+
+fn format_evm_result(exec_result: &ExecResult) -> String {
+    match exec_result {
+        Ok(r) => match r {
+            ExecutionResult::Success { reason, .. } => format!("Success: {reason:?}"),
+            ExecutionResult::Revert { .. } => "Revert".to_string(),
+            ExecutionResult::Halt { reason, .. } => format!("Halt: {reason:?}"),
+        },
+        Err(e) => e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::blockchaintest::find_all_json_tests;
+
+    #[test]
+    fn optest_fixtures() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/optest");
+        let test_files = find_all_json_tests(&fixtures);
+        assert!(!test_files.is_empty());
+        for path in test_files {
+            execute_test_suite(&path, false, false).unwrap();
+        }
+    }
+}
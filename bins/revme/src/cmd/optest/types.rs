@@ -0,0 +1,243 @@
+//! JSON format of the OP Stack execution fixtures.
+//!
+//! A fixture file maps test names to a [`TestUnit`] that executes one transaction against a
+//! pre-state on every fork listed in its `post` section.
+use op_revm::{DepositReceipt, L1BlockInfo, OpSpecId};
+use revm::primitives::{Address, Bytes, HashMap, StorageKey, StorageValue, B256, U256};
+use serde::Deserialize;
+use statetest_types::AccountInfo;
+use std::collections::BTreeMap;
+
+/// Test suite, a map of test names to test units.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+pub struct TestSuite(pub BTreeMap<String, TestUnit>);
+
+/// Single transaction executed on one or more OP Stack forks.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TestUnit {
+    /// Block environment.
+    pub env: Env,
+    /// L1 block info used to charge the L1 and operator fees.
+    ///
+    /// If missing, it is read from the storage of the L1Block predeploy
+    /// ([`L1_BLOCK_CONTRACT`](op_revm::constants::L1_BLOCK_CONTRACT)) in the pre-state.
+    #[serde(default)]
+    pub l1_block_info: Option<L1Block>,
+    /// Accounts before execution.
+    pub pre: HashMap<Address, AccountInfo>,
+    /// Transaction to execute.
+    pub transaction: Transaction,
+    /// Expected outcome per fork name, e.g. `Isthmus`.
+    pub post: BTreeMap<String, PostState>,
+}
+
+/// Block environment of the test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Env {
+    /// Block beneficiary, receiving the priority fee.
+    pub coinbase: Address,
+    /// Block number.
+    pub number: U256,
+    /// Block timestamp.
+    pub timestamp: U256,
+    /// Block gas limit.
+    pub gas_limit: U256,
+    /// Block base fee.
+    #[serde(default)]
+    pub base_fee: U256,
+    /// Chain ID, defaults to OP Mainnet.
+    #[serde(default)]
+    pub chain_id: Option<U256>,
+}
+
+/// L1 block info fields, as set by the L1 attributes transaction.
+///
+/// Fields that are not part of a fork are ignored when running on it.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct L1Block {
+    /// L1 base fee.
+    #[serde(default)]
+    pub l1_base_fee: U256,
+    /// L1 fee overhead, used before Ecotone.
+    #[serde(default)]
+    pub l1_fee_overhead: U256,
+    /// L1 base fee scalar.
+    #[serde(default)]
+    pub l1_base_fee_scalar: U256,
+    /// L1 blob base fee, from Ecotone.
+    #[serde(default)]
+    pub l1_blob_base_fee: U256,
+    /// L1 blob base fee scalar, from Ecotone.
+    #[serde(default)]
+    pub l1_blob_base_fee_scalar: U256,
+    /// Operator fee scalar, from Isthmus.
+    #[serde(default)]
+    pub operator_fee_scalar: U256,
+    /// Operator fee constant, from Isthmus.
+    #[serde(default)]
+    pub operator_fee_constant: U256,
+    /// DA footprint gas scalar, from Jovian.
+    #[serde(default)]
+    pub da_footprint_gas_scalar: u16,
+}
+
+impl L1Block {
+    /// Returns the L1 block info of the block with the given number and spec, with the same
+    /// fields as [`L1BlockInfo::try_fetch`] would load from the L1Block predeploy.
+    pub fn l1_block_info(&self, l2_block: U256, spec_id: OpSpecId) -> L1BlockInfo {
+        let mut info = L1BlockInfo {
+            l2_block: Some(l2_block),
+            l1_base_fee: self.l1_base_fee,
+            l1_base_fee_scalar: self.l1_base_fee_scalar,
+            ..Default::default()
+        };
+
+        if !spec_id.is_enabled_in(OpSpecId::ECOTONE) {
+            info.l1_fee_overhead = Some(self.l1_fee_overhead);
+            return info;
+        }
+
+        info.l1_blob_base_fee = Some(self.l1_blob_base_fee);
+        info.l1_blob_base_fee_scalar = Some(self.l1_blob_base_fee_scalar);
+        info.empty_ecotone_scalars =
+            self.l1_base_fee_scalar.is_zero() && self.l1_blob_base_fee_scalar.is_zero();
+        info.l1_fee_overhead = info.empty_ecotone_scalars.then_some(self.l1_fee_overhead);
+
+        if spec_id.is_enabled_in(OpSpecId::ISTHMUS) {
+            info.operator_fee_scalar = Some(self.operator_fee_scalar);
+            info.operator_fee_constant = Some(self.operator_fee_constant);
+        }
+
+        if spec_id.is_enabled_in(OpSpecId::JOVIAN) {
+            info.da_footprint_gas_scalar = Some(self.da_footprint_gas_scalar);
+        }
+
+        info
+    }
+}
+
+/// Transaction of the test.
+///
+/// It is a deposit if `sourceHash` is set, otherwise a legacy transaction or an EIP-1559 one if
+/// `maxPriorityFeePerGas` is set.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Transaction {
+    /// Transaction sender.
+    pub sender: Address,
+    /// Transaction target, `None` for contract creation.
+    #[serde(default)]
+    pub to: Option<Address>,
+    /// Transferred value.
+    #[serde(default)]
+    pub value: U256,
+    /// Transaction input.
+    #[serde(default)]
+    pub data: Bytes,
+    /// Gas limit.
+    pub gas_limit: U256,
+    /// Gas price, or max fee per gas of EIP-1559 transactions.
+    #[serde(default)]
+    pub gas_price: U256,
+    /// Max priority fee per gas of EIP-1559 transactions.
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Sender nonce.
+    #[serde(default)]
+    pub nonce: U256,
+    /// EIP-2718 encoding of the transaction, charged for the L1 fee.
+    ///
+    /// If missing, the transaction is encoded with a dummy signature.
+    #[serde(default)]
+    pub enveloped_tx: Option<Bytes>,
+    /// Source hash of deposit transactions.
+    #[serde(default)]
+    pub source_hash: Option<B256>,
+    /// Value minted on L2 by deposit transactions.
+    #[serde(default)]
+    pub mint: Option<U256>,
+    /// Whether the deposit transaction is a system transaction.
+    #[serde(default)]
+    pub is_system_tx: bool,
+}
+
+/// Expected outcome on a fork.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PostState {
+    /// Expected transaction error. Only its presence is checked.
+    #[serde(default)]
+    pub expect_exception: Option<String>,
+    /// Expected execution status.
+    #[serde(default)]
+    pub status: Option<Status>,
+    /// Expected gas used.
+    #[serde(default)]
+    pub gas_used: Option<U256>,
+    /// Expected output.
+    #[serde(default)]
+    pub output: Option<Bytes>,
+    /// Expected receipt fields of a deposit transaction.
+    #[serde(default)]
+    pub deposit_receipt: Option<DepositReceiptExpectation>,
+    /// Expected accounts after execution. Other accounts are not checked.
+    #[serde(default)]
+    pub state: BTreeMap<Address, AccountExpectation>,
+}
+
+/// Expected receipt fields of a deposit transaction. Missing fields are expected to be unset.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DepositReceiptExpectation {
+    /// Nonce of the depositor before execution, from Regolith.
+    #[serde(default)]
+    pub deposit_nonce: Option<U256>,
+    /// Deposit receipt version, from Canyon.
+    #[serde(default)]
+    pub deposit_receipt_version: Option<U256>,
+}
+
+impl DepositReceiptExpectation {
+    /// Returns the expected deposit receipt.
+    pub fn deposit_receipt(&self) -> DepositReceipt {
+        DepositReceipt {
+            deposit_nonce: self.deposit_nonce.map(|nonce| nonce.saturating_to()),
+            deposit_receipt_version: self
+                .deposit_receipt_version
+                .map(|version| version.saturating_to()),
+        }
+    }
+}
+
+/// Status of an executed transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    /// Execution succeeded.
+    Success,
+    /// Execution reverted.
+    Revert,
+    /// Execution halted.
+    Halt,
+}
+
+/// Expected account fields after execution. Missing fields are not checked.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountExpectation {
+    /// Account balance.
+    #[serde(default)]
+    pub balance: Option<U256>,
+    /// Account nonce.
+    #[serde(default)]
+    pub nonce: Option<U256>,
+    /// Account bytecode.
+    #[serde(default)]
+    pub code: Option<Bytes>,
+    /// Storage slots.
+    #[serde(default)]
+    pub storage: HashMap<StorageKey, StorageValue>,
+}
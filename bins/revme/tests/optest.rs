@@ -0,0 +1,15 @@
+//! Runs the OP Stack fixtures in `tests/optest` with the `optest` runner.
+use revme::{cmd::optest::execute_test_suite, dir_utils::find_all_json_tests};
+use std::path::Path;
+
+#[test]
+fn optest_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/optest");
+    let files = find_all_json_tests(&dir);
+    assert!(!files.is_empty(), "no fixtures in {}", dir.display());
+
+    for file in files {
+        let executed = execute_test_suite(&file, false, false).unwrap_or_else(|e| panic!("{e}"));
+        assert!(executed > 0, "no tests executed in {}", file.display());
+    }
+}
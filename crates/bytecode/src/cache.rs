@@ -0,0 +1,382 @@
+//! Bounded, thread-safe LRU caches shared between EVM instances.
+//!
+//! [`BytecodeCache`] is the process-wide cache of analyzed bytecode keyed by code hash.
+use crate::Bytecode;
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    sync::atomic::{AtomicU64, Ordering},
+};
+use primitives::{map::DefaultHashBuilder, HashMap, OnceLock, B256};
+use std::{
+    boxed::Box,
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Maximum number of independently locked shards.
+const MAX_SHARDS: usize = 16;
+
+/// Minimum number of entries per shard.
+///
+/// Small caches use a single shard so the least recently used entry is evicted exactly.
+const MIN_SHARD_CAPACITY: usize = 256;
+
+/// Bounded, thread-safe LRU cache.
+///
+/// The cache is split into shards that are locked independently, each evicting its least
+/// recently used entry when full. Lookups are counted in [`CacheMetrics`]. A panic while a shard
+/// is locked does not poison the cache, as shards are never left in an inconsistent state.
+///
+/// Caches are compared by identity.
+pub struct LruCache<K, V> {
+    /// Shards selected by the hash of the key.
+    shards: Box<[Mutex<Shard<K, V>>]>,
+    /// Hasher used to select the shard.
+    hasher: DefaultHashBuilder,
+    /// Maximum number of entries.
+    capacity: usize,
+    /// Number of lookups that found the value.
+    hits: AtomicU64,
+    /// Number of lookups that did not find the value.
+    misses: AtomicU64,
+    /// Number of entries removed to make room for new ones.
+    evictions: AtomicU64,
+}
+
+/// Bounded, thread-safe LRU cache of [`Bytecode`] keyed by code hash.
+///
+/// Legacy bytecode returned by a database is analyzed for jump destinations every time it is
+/// created, and every EVM instance keeps its own copy. Sharing a cache between EVMs (e.g. parallel
+/// workers simulating transactions against the same contracts) lets them skip both the database
+/// call and the analysis, while the cached [`Bytecode`] shares its bytes and
+/// [`JumpTable`](crate::JumpTable) between all clones.
+pub type BytecodeCache = LruCache<B256, Bytecode>;
+
+/// Snapshot of the [`BytecodeCache`] counters.
+pub type BytecodeCacheMetrics = CacheMetrics;
+
+/// Snapshot of the [`LruCache`] counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// Number of lookups that found the value.
+    pub hits: u64,
+    /// Number of lookups that did not find the value.
+    pub misses: u64,
+    /// Number of entries removed to make room for new ones.
+    pub evictions: u64,
+    /// Number of cached entries.
+    pub len: usize,
+    /// Maximum number of entries.
+    pub capacity: usize,
+}
+
+impl CacheMetrics {
+    /// Returns the total number of lookups.
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Returns the ratio of lookups that found the code, or zero if there were no lookups.
+    pub fn hit_rate(&self) -> f64 {
+        match self.lookups() {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl<K, V> LruCache<K, V> {
+    /// Capacity of the [`BytecodeCache::global`] cache and of [`Default`] caches.
+    pub const DEFAULT_CAPACITY: usize = 4096;
+}
+
+impl BytecodeCache {
+    /// Returns the process-wide cache with [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY).
+    pub fn global() -> Arc<Self> {
+        static INSTANCE: OnceLock<Arc<BytecodeCache>> = OnceLock::new();
+        INSTANCE
+            .get_or_init(|| Arc::new(Self::new(Self::DEFAULT_CAPACITY)))
+            .clone()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    /// Creates a new cache that holds at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        let shards = (capacity / MIN_SHARD_CAPACITY).clamp(1, MAX_SHARDS);
+        let shards = (0..shards)
+            .map(|i| {
+                // Distribute the remainder so the total is exactly `capacity`.
+                let capacity = capacity / shards + (i < capacity % shards) as usize;
+                Mutex::new(Shard::new(capacity))
+            })
+            .collect();
+        Self {
+            shards,
+            hasher: DefaultHashBuilder::default(),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| lock(shard).entries.len())
+            .sum()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of the key and marks it as recently used.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let value = lock(self.shard(key)).get(key);
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    /// Inserts the value, evicting the least recently used entry if the cache is full.
+    ///
+    /// For [`BytecodeCache`] the code hash is not verified.
+    pub fn insert(&self, key: K, value: V) {
+        let evicted = lock(self.shard(&key)).insert(key, value);
+        if evicted > 0 {
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the cached value or inserts the one returned by `f`.
+    ///
+    /// The lock is not held while `f` runs, so concurrent misses of the same key can call `f`
+    /// more than once.
+    pub fn get_or_try_insert_with<E>(
+        &self,
+        key: K,
+        f: impl FnOnce() -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.get(&key) {
+            return Ok(value);
+        }
+        let value = f()?;
+        self.insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// Removes all entries. Metrics are kept.
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            lock(shard).clear();
+        }
+    }
+
+    /// Returns a snapshot of the metrics.
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            len: self.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    /// Resets the hit, miss and eviction counters.
+    pub fn reset_metrics(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.evictions.store(0, Ordering::Relaxed);
+    }
+
+    fn shard(&self, key: &K) -> &Mutex<Shard<K, V>> {
+        &self.shards[self.hasher.hash_one(key) as usize % self.shards.len()]
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for LruCache<K, V> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruCache")
+            .field("shards", &self.shards.len())
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+impl<K, V> PartialEq for LruCache<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self, other)
+    }
+}
+
+impl<K, V> Eq for LruCache<K, V> {}
+
+/// Locks the shard, ignoring poisoning as the shard is never left in an inconsistent state.
+fn lock<K, V>(shard: &Mutex<Shard<K, V>>) -> MutexGuard<'_, Shard<K, V>> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Single LRU shard of the [`LruCache`].
+struct Shard<K, V> {
+    /// Cached values with the tick of their last use.
+    entries: HashMap<K, (V, u64)>,
+    /// Keys ordered by the tick of their last use.
+    recency: BTreeMap<u64, K>,
+    /// Incremented on every use.
+    tick: u64,
+    /// Maximum number of entries.
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Shard<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::default(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            capacity,
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let (value, used) = self.entries.get_mut(key)?;
+        self.recency.remove(used);
+        self.tick += 1;
+        *used = self.tick;
+        self.recency.insert(self.tick, key.clone());
+        Some(value.clone())
+    }
+
+    /// Inserts the value and returns the number of evicted entries.
+    fn insert(&mut self, key: K, value: V) -> u64 {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, self.tick)) {
+            self.recency.remove(&used);
+        }
+        self.recency.insert(self.tick, key);
+
+        let mut evicted = 0;
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{keccak256, Bytes};
+    use std::{thread, vec::Vec};
+
+    fn code(byte: u8) -> (B256, Bytecode) {
+        let code = Bytecode::new_legacy(Bytes::from(vec![0x60, byte, 0x5b]));
+        (code.hash_slow(), code)
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = BytecodeCache::new(2);
+        let (a, b, c) = (code(1), code(2), code(3));
+        cache.insert(a.0, a.1.clone());
+        cache.insert(b.0, b.1.clone());
+        // `a` becomes the most recently used, so `b` is evicted.
+        assert_eq!(cache.get(&a.0), Some(a.1.clone()));
+        cache.insert(c.0, c.1.clone());
+
+        assert_eq!(cache.get(&b.0), None);
+        assert_eq!(cache.get(&a.0), Some(a.1));
+        assert_eq!(cache.get(&c.0), Some(c.1));
+        assert_eq!(
+            cache.metrics(),
+            CacheMetrics {
+                hits: 3,
+                misses: 1,
+                evictions: 1,
+                len: 2,
+                capacity: 2,
+            }
+        );
+        assert_eq!(cache.metrics().hit_rate(), 0.75);
+
+        cache.clear();
+        cache.reset_metrics();
+        assert!(cache.is_empty());
+        assert_eq!(cache.metrics().lookups(), 0);
+        assert_eq!(cache.metrics().hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn shares_jump_table() {
+        let cache = BytecodeCache::new(1);
+        let (hash, code) = code(1);
+        let loaded = cache
+            .get_or_try_insert_with::<()>(hash, || Ok(code))
+            .unwrap();
+        let cached = cache
+            .get_or_try_insert_with::<()>(hash, || unreachable!())
+            .unwrap();
+        assert!(core::ptr::eq(
+            loaded.legacy_jump_table().unwrap().as_slice(),
+            cached.legacy_jump_table().unwrap().as_slice(),
+        ));
+        assert_eq!(
+            cache.get_or_try_insert_with(keccak256([1]), || Err(1)),
+            Err(1)
+        );
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn concurrent_access() {
+        let cache = Arc::new(BytecodeCache::new(1024));
+        let codes: Vec<_> = (0..=255).map(code).collect();
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for (hash, code) in &codes {
+                        let code = code.clone();
+                        cache
+                            .get_or_try_insert_with::<()>(*hash, || Ok(code))
+                            .unwrap();
+                    }
+                });
+            }
+        });
+        let metrics = cache.metrics();
+        assert_eq!(metrics.len, 256);
+        assert_eq!(metrics.lookups(), 4 * 256);
+        assert!(metrics.misses >= 256);
+        assert_eq!(metrics.evictions, 0);
+    }
+}
@@ -1,9 +1,11 @@
 mod analysis;
 mod analyzed;
+mod blocks;
 mod jump_map;
 mod raw;
 
 pub use analysis::analyze_legacy;
 pub use analyzed::LegacyAnalyzedBytecode;
+pub use blocks::{BasicBlock, BasicBlocks, BlockExit, JumpTarget};
 pub use jump_map::JumpTable;
 pub use raw::LegacyRawBytecode;
@@ -0,0 +1,467 @@
+use super::{JumpTable, LegacyAnalyzedBytecode};
+use crate::opcode::{self, OPCODE_INFO};
+use std::vec::Vec;
+
+/// Basic blocks and control flow graph of legacy bytecode.
+///
+/// A basic block is a sequence of instructions that is only entered at its first instruction and
+/// only left after its last instruction. Blocks start at the beginning of the code, at every
+/// `JUMPDEST` and after every instruction that ends a block; they end after `JUMP`, `JUMPI`,
+/// terminating and unknown instructions. [`BasicBlocks::analyze_with_splits`] additionally ends
+/// blocks after chosen opcodes.
+///
+/// Jump targets are resolved statically when the jump is directly preceded by a `PUSH`.
+///
+/// Created with [`LegacyAnalyzedBytecode::basic_blocks`] or [`BasicBlocks::analyze`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BasicBlocks {
+    /// Blocks ordered by their start offset, together they cover the whole bytecode.
+    blocks: Vec<BasicBlock>,
+}
+
+/// A basic block of legacy bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BasicBlock {
+    /// Offset of the first instruction.
+    pub start: usize,
+    /// Offset after the last instruction and its immediate.
+    pub end: usize,
+    /// Whether the block starts with `JUMPDEST` and can be the target of a jump.
+    pub is_jumpdest: bool,
+    /// Number of instructions.
+    pub instructions: usize,
+    /// Sum of the static gas of all instructions, saturating at `u64::MAX`.
+    pub static_gas: u64,
+    /// Minimum stack height required at the start of the block to not underflow.
+    pub stack_min: usize,
+    /// Maximum stack height growth, relative to the start of the block.
+    pub stack_max_growth: usize,
+    /// Stack height difference between the end and the start of the block.
+    pub stack_delta: isize,
+    /// How the block is exited.
+    pub exit: BlockExit,
+}
+
+/// Last instruction of a [`BasicBlock`] and where execution continues after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlockExit {
+    /// Execution continues in the next block, the next block starts with `JUMPDEST` or the block
+    /// was split after its last instruction.
+    FallThrough,
+    /// Block ends with `JUMP`.
+    Jump(JumpTarget),
+    /// Block ends with `JUMPI`, if the condition is zero execution continues in the next block.
+    JumpI(JumpTarget),
+    /// Block ends with a terminating instruction or an unknown opcode.
+    Halt,
+}
+
+/// Target of a `JUMP` or `JUMPI`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JumpTarget {
+    /// Target is pushed right before the jump and is a valid jump destination.
+    Static(usize),
+    /// Target is pushed right before the jump and is not a valid jump destination.
+    Invalid,
+    /// Target is computed at runtime.
+    Dynamic,
+}
+
+impl BlockExit {
+    /// Returns the jump target if the block ends with a jump.
+    #[inline]
+    pub const fn jump_target(&self) -> Option<JumpTarget> {
+        match self {
+            Self::Jump(target) | Self::JumpI(target) => Some(*target),
+            Self::FallThrough | Self::Halt => None,
+        }
+    }
+
+    /// Returns `true` if execution can continue in the next block.
+    #[inline]
+    pub const fn falls_through(&self) -> bool {
+        matches!(self, Self::FallThrough | Self::JumpI(_))
+    }
+}
+
+impl BasicBlock {
+    /// Returns `true` if the stack does not underflow or overflow `stack_limit` in this block
+    /// when it is entered with `stack_len` items.
+    #[inline]
+    pub const fn fits_stack(&self, stack_len: usize, stack_limit: usize) -> bool {
+        stack_len >= self.stack_min && stack_len + self.stack_max_growth <= stack_limit
+    }
+}
+
+impl BasicBlocks {
+    /// Splits the bytecode into basic blocks.
+    ///
+    /// `static_gas` contains the static gas of every opcode, the bytecode is expected to be padded
+    /// as in [`LegacyAnalyzedBytecode`].
+    pub fn analyze(bytecode: &[u8], jump_table: &JumpTable, static_gas: &[u64; 256]) -> Self {
+        Self::analyze_with_splits(bytecode, jump_table, static_gas, |_| false)
+    }
+
+    /// Splits the bytecode into basic blocks, also ending a block after every opcode for which
+    /// `split_after` returns `true`.
+    ///
+    /// Such blocks exit with [`BlockExit::FallThrough`]. This is used by executors that need to
+    /// observe the machine state after instructions with dynamic gas or side effects.
+    pub fn analyze_with_splits(
+        bytecode: &[u8],
+        jump_table: &JumpTable,
+        static_gas: &[u64; 256],
+        split_after: impl Fn(u8) -> bool,
+    ) -> Self {
+        let mut blocks = Vec::new();
+        let mut block: Option<BlockBuilder> = None;
+        // Target of the last instruction if it was a `PUSH`.
+        let mut pushed = None;
+        let mut pc = 0;
+        while pc < bytecode.len() {
+            let op = bytecode[pc];
+            if op == opcode::JUMPDEST {
+                if let Some(block) = block.take() {
+                    blocks.push(block.finish(pc, BlockExit::FallThrough));
+                }
+            }
+            let builder =
+                block.get_or_insert_with(|| BlockBuilder::new(pc, jump_table.is_valid(pc)));
+            builder.static_gas = builder.static_gas.saturating_add(static_gas[op as usize]);
+            builder.instructions += 1;
+
+            let Some(info) = OPCODE_INFO[op as usize] else {
+                // Unknown opcodes halt the execution.
+                pc += 1;
+                blocks.push(block.take().unwrap().finish(pc, BlockExit::Halt));
+                pushed = None;
+                continue;
+            };
+            builder.push_stack(info.inputs(), info.outputs());
+
+            let immediate = info.immediate_size() as usize;
+            let next = pc + 1 + immediate;
+            let exit = match op {
+                opcode::JUMP => Some(BlockExit::Jump(jump_target(pushed, jump_table))),
+                opcode::JUMPI => Some(BlockExit::JumpI(jump_target(pushed, jump_table))),
+                _ if info.is_terminating() => Some(BlockExit::Halt),
+                _ if split_after(op) => Some(BlockExit::FallThrough),
+                _ => None,
+            };
+            if let Some(exit) = exit {
+                blocks.push(block.take().unwrap().finish(next, exit));
+            }
+            pushed = (opcode::PUSH0..=opcode::PUSH32)
+                .contains(&op)
+                .then(|| push_value(bytecode.get(pc + 1..next).unwrap_or_default()));
+            pc = next;
+        }
+        // Unpadded bytecode can end in the middle of a block.
+        if let Some(block) = block {
+            blocks.push(block.finish(pc, BlockExit::Halt));
+        }
+        Self { blocks }
+    }
+
+    /// Returns all blocks ordered by their start offset.
+    #[inline]
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    /// Returns the number of blocks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns `true` if there are no blocks.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns the index of the block that contains the offset.
+    pub fn block_index_of(&self, pc: usize) -> Option<usize> {
+        let index = self.blocks.partition_point(|block| block.start <= pc);
+        index
+            .checked_sub(1)
+            .filter(|&index| pc < self.blocks[index].end)
+    }
+
+    /// Returns the block that starts at the offset.
+    pub fn block_at(&self, pc: usize) -> Option<&BasicBlock> {
+        self.block_index_of(pc)
+            .map(|index| &self.blocks[index])
+            .filter(|block| block.start == pc)
+    }
+
+    /// Returns indices of the blocks that can be executed after the block at `index`.
+    ///
+    /// Dynamic jump targets are not included.
+    pub fn successors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let block = &self.blocks[index];
+        let next = block
+            .exit
+            .falls_through()
+            .then_some(index + 1)
+            .filter(|&next| next < self.blocks.len());
+        let target = match block.exit.jump_target() {
+            Some(JumpTarget::Static(target)) => self.block_index_of(target),
+            _ => None,
+        };
+        next.into_iter().chain(target)
+    }
+
+    /// Returns for every block whether it can be reached from the first block.
+    ///
+    /// If a reachable block ends with a dynamic jump, every block that starts with `JUMPDEST` is
+    /// considered reachable.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = std::vec![false; self.blocks.len()];
+        let mut stack = Vec::new();
+        let mut visit = |index: usize, stack: &mut Vec<usize>| {
+            if !reachable[index] {
+                reachable[index] = true;
+                stack.push(index);
+            }
+        };
+        if !self.blocks.is_empty() {
+            visit(0, &mut stack);
+        }
+        let mut dynamic_jump = false;
+        while let Some(index) = stack.pop() {
+            let block = &self.blocks[index];
+            if !dynamic_jump && block.exit.jump_target() == Some(JumpTarget::Dynamic) {
+                dynamic_jump = true;
+                for (index, _) in self.iter_jumpdests() {
+                    visit(index, &mut stack);
+                }
+            }
+            for next in self.successors(index) {
+                visit(next, &mut stack);
+            }
+        }
+        reachable
+    }
+
+    /// Returns blocks that start with `JUMPDEST` together with their indices.
+    fn iter_jumpdests(&self) -> impl Iterator<Item = (usize, &BasicBlock)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| block.is_jumpdest)
+    }
+}
+
+impl LegacyAnalyzedBytecode {
+    /// Splits the bytecode into [`BasicBlocks`].
+    ///
+    /// `static_gas` contains the static gas of every opcode.
+    pub fn basic_blocks(&self, static_gas: &[u64; 256]) -> BasicBlocks {
+        BasicBlocks::analyze(self.bytecode(), self.jump_table(), static_gas)
+    }
+
+    /// Splits the bytecode into [`BasicBlocks`], also ending a block after every opcode for which
+    /// `split_after` returns `true`.
+    ///
+    /// See [`BasicBlocks::analyze_with_splits`].
+    pub fn basic_blocks_with_splits(
+        &self,
+        static_gas: &[u64; 256],
+        split_after: impl Fn(u8) -> bool,
+    ) -> BasicBlocks {
+        BasicBlocks::analyze_with_splits(
+            self.bytecode(),
+            self.jump_table(),
+            static_gas,
+            split_after,
+        )
+    }
+}
+
+/// Block under construction.
+struct BlockBuilder {
+    start: usize,
+    is_jumpdest: bool,
+    instructions: usize,
+    static_gas: u64,
+    stack_height: isize,
+    stack_min: isize,
+    stack_max_growth: isize,
+}
+
+impl BlockBuilder {
+    fn new(start: usize, is_jumpdest: bool) -> Self {
+        Self {
+            start,
+            is_jumpdest,
+            instructions: 0,
+            static_gas: 0,
+            stack_height: 0,
+            stack_min: 0,
+            stack_max_growth: 0,
+        }
+    }
+
+    fn push_stack(&mut self, inputs: u8, outputs: u8) {
+        self.stack_min = self.stack_min.max(inputs as isize - self.stack_height);
+        self.stack_height += outputs as isize - inputs as isize;
+        self.stack_max_growth = self.stack_max_growth.max(self.stack_height);
+    }
+
+    fn finish(self, end: usize, exit: BlockExit) -> BasicBlock {
+        BasicBlock {
+            start: self.start,
+            end,
+            is_jumpdest: self.is_jumpdest,
+            instructions: self.instructions,
+            static_gas: self.static_gas,
+            stack_min: self.stack_min as usize,
+            stack_max_growth: self.stack_max_growth as usize,
+            stack_delta: self.stack_height,
+            exit,
+        }
+    }
+}
+
+/// Resolves the target of a jump from the value pushed before it.
+fn jump_target(pushed: Option<Option<usize>>, jump_table: &JumpTable) -> JumpTarget {
+    match pushed {
+        Some(Some(target)) if jump_table.is_valid(target) => JumpTarget::Static(target),
+        Some(_) => JumpTarget::Invalid,
+        None => JumpTarget::Dynamic,
+    }
+}
+
+/// Returns the big-endian value of a `PUSH` immediate if it fits into `usize`.
+fn push_value(immediate: &[u8]) -> Option<usize> {
+    let mut value = 0usize;
+    for &byte in immediate {
+        value = value.checked_mul(256)? | byte as usize;
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcode::*;
+    use primitives::Bytes;
+
+    /// Charges 1 gas for every opcode, except 2 for `ADD`.
+    fn static_gas() -> [u64; 256] {
+        let mut gas = [1; 256];
+        gas[ADD as usize] = 2;
+        gas
+    }
+
+    fn analyze(code: &[u8]) -> BasicBlocks {
+        LegacyAnalyzedBytecode::analyze(Bytes::copy_from_slice(code)).basic_blocks(&static_gas())
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let blocks = analyze(&[
+            PUSH1, 1, PUSH1, 2, ADD, PUSH1, 9, JUMPI, // 0..8
+            STOP,  // 8
+            JUMPDEST, POP, JUMPDEST, // 9, 11
+        ]);
+        let starts: Vec<_> = blocks.blocks().iter().map(|b| b.start).collect();
+        assert_eq!(starts, [0, 8, 9, 11]);
+
+        let first = blocks.blocks()[0];
+        assert_eq!(first.end, 8);
+        assert_eq!(first.instructions, 5);
+        assert_eq!(first.static_gas, 1 + 1 + 2 + 1 + 1);
+        assert_eq!(first.stack_min, 0);
+        assert_eq!(first.stack_max_growth, 2);
+        assert_eq!(first.stack_delta, 0);
+        assert_eq!(first.exit, BlockExit::JumpI(JumpTarget::Static(9)));
+        assert!(!first.is_jumpdest);
+
+        assert_eq!(blocks.blocks()[1].exit, BlockExit::Halt);
+
+        let third = blocks.blocks()[2];
+        assert!(third.is_jumpdest);
+        assert_eq!(third.stack_min, 1);
+        assert_eq!(third.stack_delta, -1);
+        assert_eq!(third.exit, BlockExit::FallThrough);
+
+        // JUMPDEST followed by the STOP padding.
+        assert_eq!(blocks.blocks()[3].end, 13);
+        assert_eq!(blocks.blocks()[3].exit, BlockExit::Halt);
+    }
+
+    #[test]
+    fn jump_targets() {
+        let blocks = analyze(&[
+            PUSH1, 4, JUMP, INVALID, // 0..4
+            JUMPDEST, PUSH1, 3, JUMP, // 4..8, 3 is not a JUMPDEST
+            JUMPDEST, PUSH2, 0xFF, 0xFF, JUMP, // 8..13, out of bounds
+            JUMPDEST, DUP1, JUMP, // 13..16
+            JUMPDEST, PUSH32, // 16..50, PUSH32 immediate doesn't fit usize
+        ]);
+        let exits: Vec<_> = blocks.blocks().iter().map(|b| b.exit).collect();
+        assert_eq!(
+            exits,
+            [
+                BlockExit::Jump(JumpTarget::Static(4)),
+                BlockExit::Halt,
+                BlockExit::Jump(JumpTarget::Invalid),
+                BlockExit::Jump(JumpTarget::Invalid),
+                BlockExit::Jump(JumpTarget::Dynamic),
+                BlockExit::Halt,
+            ]
+        );
+        assert_eq!(blocks.successors(0).collect::<Vec<_>>(), [2]);
+        assert_eq!(blocks.successors(2).count(), 0);
+        assert_eq!(blocks.block_index_of(10), Some(3));
+        assert!(blocks.block_at(10).is_none());
+        assert_eq!(blocks.block_at(13).unwrap().start, 13);
+        assert_eq!(blocks.block_index_of(1000), None);
+    }
+
+    #[test]
+    fn unknown_opcode_ends_block() {
+        let blocks = analyze(&[PUSH1, 1, 0x0C, PUSH1, 2]);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks.blocks()[0].end, 3);
+        assert_eq!(blocks.blocks()[0].exit, BlockExit::Halt);
+        assert_eq!(blocks.blocks()[1].start, 3);
+    }
+
+    #[test]
+    fn splits_after_chosen_opcodes() {
+        let code = LegacyAnalyzedBytecode::analyze(Bytes::from_static(&[
+            PUSH1, 1, GAS, ADD, JUMPDEST, STOP,
+        ]));
+        let blocks = code.basic_blocks_with_splits(&static_gas(), |op| op == GAS);
+        let ranges: Vec<_> = blocks.blocks().iter().map(|b| (b.start, b.end)).collect();
+        assert_eq!(ranges, [(0, 3), (3, 4), (4, 6)]);
+        assert_eq!(blocks.blocks()[0].exit, BlockExit::FallThrough);
+        assert_eq!(blocks.successors(0).collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn reachable() {
+        // The block at 3 is dead code.
+        let code = [
+            PUSH1, 4, JUMP, STOP, // 0..4
+            JUMPDEST, STOP, // 4..6
+            JUMPDEST, STOP, // 6..8
+        ];
+        let blocks = analyze(&code);
+        assert_eq!(blocks.reachable(), [true, false, true, false]);
+
+        // A dynamic jump can reach every JUMPDEST.
+        let mut code = code;
+        code[0] = CALLDATASIZE;
+        code[1] = JUMPDEST;
+        let blocks = analyze(&code);
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(blocks.reachable(), [true, true, false, true, true]);
+
+        assert!(BasicBlocks::default().reachable().is_empty());
+    }
+}
@@ -10,6 +10,8 @@
 extern crate alloc as std;
 
 pub mod bytecode;
+#[cfg(feature = "std")]
+pub mod cache;
 mod decode_errors;
 /// EIP-7702 bytecode.
 pub mod eip7702;
@@ -23,6 +25,8 @@ pub mod utils;
 /// Re-export of bitvec crate, used to store legacy bytecode jump table.
 pub use bitvec;
 pub use bytecode::Bytecode;
+#[cfg(feature = "std")]
+pub use cache::{BytecodeCache, BytecodeCacheMetrics, CacheMetrics, LruCache};
 pub use decode_errors::BytecodeDecodeError;
 pub use iter::BytecodeIterator;
 pub use legacy::{JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
@@ -1,8 +1,10 @@
 //! Block related types and functions.
 //!
 //! [`Block`] trait is used to retrieve block information required for execution.
+pub mod base_fee;
 pub mod blob;
 
+pub use base_fee::{calc_next_block_base_fee, BaseFeeParams};
 pub use blob::{calc_blob_gasprice, BlobExcessGasAndPrice};
 
 use auto_impl::auto_impl;
@@ -0,0 +1,117 @@
+//! Base fee (EIP-1559) related functions and types.
+//!
+//! [`calc_next_block_base_fee`] derives the base fee of a block from its parent, so it can be set
+//! in the block environment before execution.
+//!
+//! See also [the EIP-1559 specification](https://eips.ethereum.org/EIPS/eip-1559#specification).
+use core::cmp::Ordering;
+use primitives::eip1559::{BASE_FEE_MAX_CHANGE_DENOMINATOR, ELASTICITY_MULTIPLIER};
+
+/// Parameters of the base fee update rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaseFeeParams {
+    /// Bounds the amount the base fee can change between blocks.
+    pub max_change_denominator: u64,
+    /// Bounds the maximum gas limit of a block relative to its gas target.
+    pub elasticity_multiplier: u64,
+}
+
+impl BaseFeeParams {
+    /// Parameters of Ethereum mainnet.
+    pub const ETHEREUM: Self = Self::new(BASE_FEE_MAX_CHANGE_DENOMINATOR, ELASTICITY_MULTIPLIER);
+
+    /// Creates new parameters.
+    pub const fn new(max_change_denominator: u64, elasticity_multiplier: u64) -> Self {
+        Self {
+            max_change_denominator,
+            elasticity_multiplier,
+        }
+    }
+
+    /// Calculates the base fee of the next block with [`calc_next_block_base_fee`].
+    pub fn next_block_base_fee(&self, gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
+        calc_next_block_base_fee(gas_used, gas_limit, base_fee, *self)
+    }
+}
+
+impl Default for BaseFeeParams {
+    fn default() -> Self {
+        Self::ETHEREUM
+    }
+}
+
+/// Calculates the base fee of the block following the one with the given gas used, gas limit and
+/// base fee.
+///
+/// The base fee increases when the parent used more gas than its target,
+/// `gas_limit / elasticity_multiplier`, and decreases when it used less, by at most
+/// `1 / max_change_denominator` of the parent base fee. An increase is at least one wei.
+///
+/// # Panics
+///
+/// Panics if the gas target or the max change denominator is zero.
+pub fn calc_next_block_base_fee(
+    gas_used: u64,
+    gas_limit: u64,
+    base_fee: u64,
+    params: BaseFeeParams,
+) -> u64 {
+    let gas_target = gas_limit / params.elasticity_multiplier;
+    let delta = |gas_delta: u64| {
+        base_fee as u128 * gas_delta as u128
+            / gas_target as u128
+            / params.max_change_denominator as u128
+    };
+
+    match gas_used.cmp(&gas_target) {
+        Ordering::Equal => base_fee,
+        Ordering::Greater => {
+            let delta = delta(gas_used - gas_target).max(1);
+            base_fee.saturating_add(delta.try_into().unwrap_or(u64::MAX))
+        }
+        // The delta is less than the base fee.
+        Ordering::Less => base_fee - delta(gas_target - gas_used) as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip1559/eip1559_test.go
+    #[test]
+    fn next_block_base_fee() {
+        for t @ &(gas_used, gas_limit, base_fee, expected) in &[
+            (10_000_000, 10_000_000, 1_000_000_000, 1_125_000_000),
+            (10_000_000, 12_000_000, 1_000_000_000, 1_083_333_333),
+            (10_000_000, 14_000_000, 1_000_000_000, 1_053_571_428),
+            (9_000_000, 10_000_000, 1_072_671_875, 1_179_939_062),
+            (10_001_000, 14_000_000, 1_059_263_476, 1_116_028_649),
+            (0, 2_000_000, 1_049_238_967, 918_084_097),
+            (10_000_000, 18_000_000, 1_049_238_967, 1_063_811_730),
+            (9_000_000, 18_000_000, 1_000_000_000, 1_000_000_000),
+            (10_000_000, 18_000_000, 0, 1),
+            (10_000_000, 18_000_000, 1, 2),
+            (10_000_000, 18_000_000, 2, 3),
+            (30_000_000, 30_000_000, u64::MAX, u64::MAX),
+        ] {
+            let actual = BaseFeeParams::ETHEREUM.next_block_base_fee(gas_used, gas_limit, base_fee);
+            assert_eq!(actual, expected, "test: {t:?}");
+        }
+    }
+
+    #[test]
+    fn custom_params() {
+        // OP mainnet post-Canyon parameters.
+        let params = BaseFeeParams::new(250, 6);
+        assert_eq!(
+            params.next_block_base_fee(30_000_000, 30_000_000, 1_000_000_000),
+            1_020_000_000
+        );
+        assert_eq!(
+            params.next_block_base_fee(0, 30_000_000, 1_000_000_000),
+            996_000_000
+        );
+    }
+}
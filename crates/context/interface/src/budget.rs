@@ -0,0 +1,196 @@
+//! Execution budget [`ExecutionBudget`] that caps the steps and wall-clock time of a transaction.
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Number of steps executed between two wall-clock checks.
+///
+/// Reading the clock is much more expensive than executing an opcode, so the deadline is only
+/// checked once per interval.
+pub const TIME_CHECK_INTERVAL: u64 = 4096;
+
+/// Limit that was exceeded by the execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExecutionLimit {
+    /// Step limit was reached.
+    Steps,
+    /// Time limit was reached.
+    Time,
+}
+
+/// Remaining execution budget of a transaction.
+///
+/// It is created from [`Cfg::step_limit`](crate::Cfg::step_limit) and
+/// [`Cfg::time_limit`](crate::Cfg::time_limit) at the start of the transaction and is spent by
+/// the interpreter loop, one unit per executed instruction.
+///
+/// Time limit is only enforced with the `std` feature and is checked every
+/// [`TIME_CHECK_INTERVAL`] steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecutionBudget {
+    /// Steps that can be spent before the slow path is taken.
+    ticks: u64,
+    /// Steps that are not yet moved to `ticks`.
+    steps: u64,
+    /// Whether the number of steps is limited.
+    step_limited: bool,
+    /// Deadline of the execution.
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
+
+impl Default for ExecutionBudget {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+impl ExecutionBudget {
+    /// Budget without any limits.
+    pub const UNLIMITED: Self = Self {
+        ticks: u64::MAX,
+        steps: 0,
+        step_limited: false,
+        #[cfg(feature = "std")]
+        deadline: None,
+    };
+
+    /// Creates a new budget that starts counting time now.
+    pub fn new(step_limit: Option<u64>, time_limit: Option<Duration>) -> Self {
+        #[cfg(not(feature = "std"))]
+        let _ = time_limit;
+        let mut budget = Self {
+            ticks: 0,
+            steps: step_limit.unwrap_or(u64::MAX),
+            step_limited: step_limit.is_some(),
+            #[cfg(feature = "std")]
+            deadline: time_limit.and_then(|limit| Instant::now().checked_add(limit)),
+        };
+        budget.refill();
+        budget
+    }
+
+    /// Returns `true` if neither steps nor time are limited.
+    pub fn is_unlimited(&self) -> bool {
+        !self.step_limited && !self.has_deadline()
+    }
+
+    /// Returns the number of steps left, or `None` if steps are not limited.
+    pub fn remaining_steps(&self) -> Option<u64> {
+        self.step_limited.then(|| self.ticks + self.steps)
+    }
+
+    /// Spends `steps` from the budget.
+    ///
+    /// On error the step budget is left untouched.
+    #[inline]
+    pub fn spend(&mut self, steps: u64) -> Result<(), ExecutionLimit> {
+        if steps <= self.ticks {
+            self.ticks -= steps;
+            return Ok(());
+        }
+        self.spend_slow(steps)
+    }
+
+    /// Gives back `steps` that were spent but not executed.
+    #[inline]
+    pub fn refund(&mut self, steps: u64) {
+        self.ticks = self.ticks.saturating_add(steps);
+    }
+
+    #[cold]
+    fn spend_slow(&mut self, steps: u64) -> Result<(), ExecutionLimit> {
+        if self.step_limited {
+            let available = self.ticks + self.steps;
+            if steps > available {
+                return Err(ExecutionLimit::Steps);
+            }
+            self.steps = available - steps;
+        } else {
+            self.steps = u64::MAX;
+        }
+        self.ticks = 0;
+
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(ExecutionLimit::Time);
+            }
+        }
+
+        self.refill();
+        Ok(())
+    }
+
+    /// Moves steps to `ticks`, at most one check interval if there is a deadline.
+    fn refill(&mut self) {
+        let interval = if self.has_deadline() {
+            TIME_CHECK_INTERVAL
+        } else {
+            u64::MAX
+        };
+        let ticks = self.steps.min(interval);
+        self.steps -= ticks;
+        self.ticks = ticks;
+    }
+
+    fn has_deadline(&self) -> bool {
+        #[cfg(feature = "std")]
+        return self.deadline.is_some();
+        #[cfg(not(feature = "std"))]
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited() {
+        let mut budget = ExecutionBudget::UNLIMITED;
+        assert!(budget.is_unlimited());
+        assert_eq!(budget.remaining_steps(), None);
+        assert_eq!(budget.spend(u64::MAX), Ok(()));
+        assert_eq!(budget.spend(1), Ok(()));
+        assert_eq!(ExecutionBudget::new(None, None), ExecutionBudget::UNLIMITED);
+    }
+
+    #[test]
+    fn step_limit() {
+        let mut budget = ExecutionBudget::new(Some(10), None);
+        assert_eq!(budget.remaining_steps(), Some(10));
+        assert_eq!(budget.spend(4), Ok(()));
+        assert_eq!(budget.spend(7), Err(ExecutionLimit::Steps));
+        assert_eq!(budget.remaining_steps(), Some(6));
+        budget.refund(2);
+        assert_eq!(budget.spend(8), Ok(()));
+        assert_eq!(budget.remaining_steps(), Some(0));
+        assert_eq!(budget.spend(1), Err(ExecutionLimit::Steps));
+        assert_eq!(budget.spend(0), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn step_limit_with_deadline() {
+        let mut budget = ExecutionBudget::new(
+            Some(3 * TIME_CHECK_INTERVAL),
+            Some(Duration::from_secs(3600)),
+        );
+        for _ in 0..3 * TIME_CHECK_INTERVAL {
+            assert_eq!(budget.spend(1), Ok(()));
+        }
+        assert_eq!(budget.remaining_steps(), Some(0));
+        assert_eq!(budget.spend(1), Err(ExecutionLimit::Steps));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn time_limit() {
+        let mut budget = ExecutionBudget::new(None, Some(Duration::ZERO));
+        assert!(!budget.is_unlimited());
+        let result = (0..=TIME_CHECK_INTERVAL).try_for_each(|_| budget.spend(1));
+        assert_eq!(result, Err(ExecutionLimit::Time));
+    }
+}
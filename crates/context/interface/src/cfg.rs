@@ -1,6 +1,11 @@
 //! Configuration for the EVM. Containing [`SpecId`].
+pub mod gas_params;
+
+pub use gas_params::GasParams;
+
+use crate::Block;
 use auto_impl::auto_impl;
-use core::{fmt::Debug, hash::Hash};
+use core::{fmt::Debug, hash::Hash, time::Duration};
 use primitives::{hardfork::SpecId, Address, TxKind, U256};
 
 /// Configuration for the EVM.
@@ -66,6 +71,44 @@ pub trait Cfg {
 
     /// Returns the limit in bytes for the memory buffer.
     fn memory_limit(&self) -> u64;
+
+    /// Returns the maximum number of instructions a transaction can execute across all frames.
+    ///
+    /// Execution halts with [`HaltReason::StepLimitReached`](crate::result::HaltReason::StepLimitReached)
+    /// when it is exceeded. `None` means unlimited.
+    fn step_limit(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the maximum wall-clock time a transaction can spend in the interpreter.
+    ///
+    /// Execution halts with [`HaltReason::TimeLimitReached`](crate::result::HaltReason::TimeLimitReached)
+    /// when it is exceeded. Only enforced with the `std` feature. `None` means unlimited.
+    fn time_limit(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the gas schedule used by instructions and the initial transaction gas.
+    ///
+    /// Defaults to the mainnet schedule of [`Cfg::spec`].
+    fn gas_params(&self) -> &GasParams {
+        GasParams::mainnet(self.spec().into())
+    }
+
+    /// Returns the recipient of the base fee, which is burned if `None`.
+    ///
+    /// Only used from London, when the base fee was introduced.
+    fn base_fee_recipient(&self) -> Option<Address> {
+        None
+    }
+
+    /// Sets the spec to the one active in the given block, e.g. from a fork activation schedule.
+    ///
+    /// Called by the context whenever its block is set. Does nothing by default.
+    #[auto_impl(keep_default_for(&, Arc))]
+    fn update_spec_for_block(&mut self, block: &impl Block) {
+        let _ = block;
+    }
 }
 
 /// What bytecode analysis to perform
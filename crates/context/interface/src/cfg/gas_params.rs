@@ -0,0 +1,564 @@
+//! Gas schedule used by the interpreter and by the initial transaction gas calculation.
+//!
+//! [`GasParams`] contains every gas cost that changed between mainnet hardforks. Instructions
+//! read it through [`Host::gas_params`](crate::Host::gas_params) and handler reads it through
+//! [`Cfg::gas_params`](crate::Cfg::gas_params), so a chain can reprice individual operations
+//! without forking the instruction set.
+//!
+//! Costs that never changed on mainnet (e.g. arithmetic opcodes) are part of the static gas of
+//! the instruction table and are not included.
+use crate::context::{SStoreResult, SelfDestructResult, StateLoad};
+use primitives::{hardfork::SpecId, U256};
+
+/// Spec dependent gas costs.
+///
+/// Presets for mainnet hardforks are created with [`GasParams::new_spec`], individual costs can
+/// be overridden with struct update syntax:
+///
+/// ```
+/// # use revm_context_interface::cfg::GasParams;
+/// # use primitives::hardfork::SpecId;
+/// let params = GasParams {
+///     cold_sload: 3000,
+///     ..GasParams::new_spec(SpecId::PRAGUE)
+/// };
+/// assert_eq!(params.cold_sload_additional(), 2900);
+/// ```
+///
+/// EIP-2929 cold costs are zero before Berlin, as there was no warm and cold access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasParams {
+    /* Storage */
+    /// `SLOAD` cost, warm access cost since Berlin.
+    pub sload: u64,
+    /// Total cost of a cold `SLOAD`, charged as additional cost by `SSTORE` on a cold slot.
+    pub cold_sload: u64,
+    /// `SSTORE` cost when setting a zero slot to non-zero.
+    pub sstore_set: u64,
+    /// `SSTORE` cost when modifying a non-zero slot, without the cold access cost since Berlin.
+    pub sstore_reset: u64,
+    /// Refund for clearing a storage slot.
+    pub sstore_clears_refund: u64,
+
+    /* Account access */
+    /// Warm account access cost, used for the EIP-7702 delegated account.
+    pub warm_account_access: u64,
+    /// Total cost of a cold account access.
+    pub cold_account_access: u64,
+    /// `BALANCE` cost, warm access cost since Berlin.
+    pub balance: u64,
+    /// `EXTCODESIZE` cost, warm access cost since Berlin.
+    pub extcodesize: u64,
+    /// `EXTCODEHASH` cost, warm access cost since Berlin.
+    pub extcodehash: u64,
+    /// `EXTCODECOPY` base cost, warm access cost since Berlin.
+    pub extcodecopy: u64,
+
+    /* Calls */
+    /// `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL` base cost, warm access cost since
+    /// Berlin.
+    pub call: u64,
+    /// Additional cost of a call that transfers value.
+    pub call_value: u64,
+    /// Gas stipend given to the callee of a call that transfers value.
+    ///
+    /// `SSTORE` fails if less or equal gas is left (EIP-1706).
+    pub call_stipend: u64,
+    /// Cost of a call that creates a new account.
+    pub new_account: u64,
+    /// `SELFDESTRUCT` base cost.
+    pub selfdestruct: u64,
+    /// Cost of a `SELFDESTRUCT` that creates the beneficiary account.
+    pub selfdestruct_new_account: u64,
+    /// Refund for a `SELFDESTRUCT`.
+    pub selfdestruct_refund: u64,
+
+    /* Arithmetic */
+    /// `EXP` base cost.
+    pub exp: u64,
+    /// `EXP` cost per byte of the exponent.
+    pub exp_byte: u64,
+
+    /* Transaction */
+    /// Base cost of every transaction.
+    pub tx_base: u64,
+    /// Additional cost of a contract creation transaction.
+    pub tx_create: u64,
+    /// Cost of a calldata token.
+    pub tx_token_cost: u64,
+    /// Number of tokens a non-zero calldata byte counts as, a zero byte is one token.
+    pub tx_token_non_zero_byte_multiplier: u64,
+    /// Cost of an access list address.
+    pub tx_access_list_address: u64,
+    /// Cost of an access list storage key.
+    pub tx_access_list_storage_key: u64,
+    /// Cost per word of initcode of a contract creation transaction.
+    pub tx_initcode_word: u64,
+    /// Cost of an EIP-7702 authorization.
+    pub tx_authorization: u64,
+    /// EIP-7623 floor cost per calldata token, zero if there is no floor.
+    pub tx_floor_cost_per_token: u64,
+}
+
+impl Default for GasParams {
+    fn default() -> Self {
+        Self::new_spec(SpecId::default())
+    }
+}
+
+impl GasParams {
+    /// Returns the mainnet gas schedule of the given spec.
+    pub const fn new_spec(spec: SpecId) -> Self {
+        let is_tangerine = spec.is_enabled_in(SpecId::TANGERINE);
+        let is_istanbul = spec.is_enabled_in(SpecId::ISTANBUL);
+        let is_berlin = spec.is_enabled_in(SpecId::BERLIN);
+        let is_london = spec.is_enabled_in(SpecId::LONDON);
+
+        // EIP-2929: Gas cost increases for state access opcodes
+        let warm_access = 100;
+        let (sload, cold_sload, cold_account_access) = if is_berlin {
+            (warm_access, 2100, 2600)
+        } else if is_istanbul {
+            // EIP-1884: Repricing for trie-size-dependent opcodes
+            (800, 0, 0)
+        } else if is_tangerine {
+            // EIP-150: Gas cost changes for IO-heavy operations
+            (200, 0, 0)
+        } else {
+            (50, 0, 0)
+        };
+        let sstore_reset = if is_berlin { 5000 - cold_sload } else { 5000 };
+        // EIP-3529: Reduction in refunds
+        let sstore_clears_refund = if is_london {
+            sstore_reset + 1900
+        } else {
+            15000
+        };
+
+        let (balance, extcodehash) = if is_berlin {
+            (warm_access, warm_access)
+        } else if is_istanbul {
+            (700, 700)
+        } else if is_tangerine {
+            (400, 400)
+        } else {
+            (20, 400)
+        };
+        let (extcode, call) = if is_berlin {
+            (warm_access, warm_access)
+        } else if is_tangerine {
+            (700, 700)
+        } else {
+            (20, 40)
+        };
+
+        Self {
+            sload,
+            cold_sload,
+            sstore_set: 20000,
+            sstore_reset,
+            sstore_clears_refund,
+            warm_account_access: if is_berlin { warm_access } else { 0 },
+            cold_account_access,
+            balance,
+            extcodesize: extcode,
+            extcodehash,
+            extcodecopy: extcode,
+            call,
+            call_value: 9000,
+            call_stipend: 2300,
+            new_account: 25000,
+            selfdestruct: if is_tangerine { 5000 } else { 0 },
+            selfdestruct_new_account: if is_tangerine { 25000 } else { 0 },
+            selfdestruct_refund: if is_london { 0 } else { 24000 },
+            exp: 10,
+            // EIP-160: EXP cost increase
+            exp_byte: if spec.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
+                50
+            } else {
+                10
+            },
+            tx_base: 21000,
+            // EIP-2: Homestead Hard-fork Changes
+            tx_create: if spec.is_enabled_in(SpecId::HOMESTEAD) {
+                32000
+            } else {
+                0
+            },
+            tx_token_cost: 4,
+            // EIP-2028: Transaction data gas cost reduction
+            tx_token_non_zero_byte_multiplier: if is_istanbul { 4 } else { 17 },
+            tx_access_list_address: 2400,
+            tx_access_list_storage_key: 1900,
+            // EIP-3860: Limit and meter initcode
+            tx_initcode_word: if spec.is_enabled_in(SpecId::SHANGHAI) {
+                2
+            } else {
+                0
+            },
+            // EIP-7702: Set EOA account code
+            tx_authorization: if spec.is_enabled_in(SpecId::PRAGUE) {
+                25000
+            } else {
+                0
+            },
+            // EIP-7623: Increase calldata cost
+            tx_floor_cost_per_token: if spec.is_enabled_in(SpecId::PRAGUE) {
+                10
+            } else {
+                0
+            },
+        }
+    }
+
+    /// Returns a static reference to the mainnet gas schedule of the given spec.
+    ///
+    /// Same as [`GasParams::new_spec`] but without building the schedule on every call.
+    pub const fn mainnet(spec: SpecId) -> &'static Self {
+        static FRONTIER: GasParams = GasParams::new_spec(SpecId::FRONTIER);
+        static HOMESTEAD: GasParams = GasParams::new_spec(SpecId::HOMESTEAD);
+        static TANGERINE: GasParams = GasParams::new_spec(SpecId::TANGERINE);
+        static SPURIOUS_DRAGON: GasParams = GasParams::new_spec(SpecId::SPURIOUS_DRAGON);
+        static ISTANBUL: GasParams = GasParams::new_spec(SpecId::ISTANBUL);
+        static BERLIN: GasParams = GasParams::new_spec(SpecId::BERLIN);
+        static LONDON: GasParams = GasParams::new_spec(SpecId::LONDON);
+        static SHANGHAI: GasParams = GasParams::new_spec(SpecId::SHANGHAI);
+        static PRAGUE: GasParams = GasParams::new_spec(SpecId::PRAGUE);
+
+        if spec.is_enabled_in(SpecId::PRAGUE) {
+            &PRAGUE
+        } else if spec.is_enabled_in(SpecId::SHANGHAI) {
+            &SHANGHAI
+        } else if spec.is_enabled_in(SpecId::LONDON) {
+            &LONDON
+        } else if spec.is_enabled_in(SpecId::BERLIN) {
+            &BERLIN
+        } else if spec.is_enabled_in(SpecId::ISTANBUL) {
+            &ISTANBUL
+        } else if spec.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
+            &SPURIOUS_DRAGON
+        } else if spec.is_enabled_in(SpecId::TANGERINE) {
+            &TANGERINE
+        } else if spec.is_enabled_in(SpecId::HOMESTEAD) {
+            &HOMESTEAD
+        } else {
+            &FRONTIER
+        }
+    }
+
+    /// Additional cost of a cold `SLOAD` on top of [`GasParams::sload`].
+    #[inline]
+    pub const fn cold_sload_additional(&self) -> u64 {
+        self.cold_sload.saturating_sub(self.sload)
+    }
+
+    /// Additional cost of a cold account access on top of the warm access cost.
+    #[inline]
+    pub const fn cold_account_access_additional(&self) -> u64 {
+        self.cold_account_access
+            .saturating_sub(self.warm_account_access)
+    }
+
+    /// `SLOAD` cost.
+    #[inline]
+    pub const fn sload_cost(&self, is_cold: bool) -> u64 {
+        if is_cold {
+            self.sload + self.cold_sload_additional()
+        } else {
+            self.sload
+        }
+    }
+
+    /// Static part of the `SSTORE` cost, charged before the slot is loaded.
+    ///
+    /// `is_istanbul` enables EIP-2200 net gas metering.
+    #[inline]
+    pub const fn sstore_static_cost(&self, is_istanbul: bool) -> u64 {
+        if is_istanbul {
+            self.sload
+        } else {
+            self.sstore_reset
+        }
+    }
+
+    /// Dynamic part of the `SSTORE` cost, charged after the slot is loaded.
+    ///
+    /// Zero if overridden costs make the static part larger than the total cost.
+    #[inline]
+    pub const fn sstore_dynamic_cost(
+        &self,
+        is_istanbul: bool,
+        vals: &SStoreResult,
+        is_cold: bool,
+    ) -> u64 {
+        self.sstore_cost(is_istanbul, vals, is_cold)
+            .saturating_sub(self.sstore_static_cost(is_istanbul))
+    }
+
+    /// Total `SSTORE` cost.
+    #[inline]
+    pub const fn sstore_cost(&self, is_istanbul: bool, vals: &SStoreResult, is_cold: bool) -> u64 {
+        if !is_istanbul {
+            // Frontier sstore cost just had two cases set and reset values.
+            return if vals.is_present_zero() && !vals.is_new_zero() {
+                self.sstore_set
+            } else {
+                self.sstore_reset
+            };
+        }
+        // EIP-2200: Structured Definitions for Net Gas Metering
+        let gas = if vals.is_new_eq_present() {
+            self.sload
+        } else if vals.is_original_eq_present() && vals.is_original_zero() {
+            self.sstore_set
+        } else if vals.is_original_eq_present() {
+            self.sstore_reset
+        } else {
+            self.sload
+        };
+        if is_cold {
+            gas.saturating_add(self.cold_sload)
+        } else {
+            gas
+        }
+    }
+
+    /// `SSTORE` refund, can be negative if a previous refund is taken back.
+    #[inline]
+    pub const fn sstore_refund(&self, is_istanbul: bool, vals: &SStoreResult) -> i64 {
+        let clears = self.sstore_clears_refund as i64;
+        if !is_istanbul {
+            return if !vals.is_present_zero() && vals.is_new_zero() {
+                clears
+            } else {
+                0
+            };
+        }
+        if vals.is_new_eq_present() {
+            return 0;
+        }
+        if vals.is_original_eq_present() && vals.is_new_zero() {
+            return clears;
+        }
+
+        let mut refund = 0;
+        if !vals.is_original_zero() {
+            if vals.is_present_zero() {
+                refund -= clears;
+            } else if vals.is_new_zero() {
+                refund += clears;
+            }
+        }
+        if vals.is_original_eq_new() {
+            if vals.is_original_zero() {
+                refund += self.sstore_set as i64 - self.sload as i64;
+            } else {
+                refund += self.sstore_reset as i64 - self.sload as i64;
+            }
+        }
+        refund
+    }
+
+    /// `EXP` cost for the given exponent.
+    #[inline]
+    pub const fn exp_cost(&self, power: U256) -> Option<u64> {
+        let Some(gas) = self.exp_byte.checked_mul(power.byte_len() as u64) else {
+            return None;
+        };
+        self.exp.checked_add(gas)
+    }
+
+    /// Static part of the call cost.
+    ///
+    /// Account access cost and [`GasParams::call_value`] if value is transferred.
+    #[inline]
+    pub const fn call_static_cost(&self, transfers_value: bool) -> u64 {
+        if transfers_value {
+            self.call + self.call_value
+        } else {
+            self.call
+        }
+    }
+
+    /// Cost of a call to an empty account.
+    ///
+    /// `is_spurious_dragon` enables EIP-161, after which the account is only created if value is
+    /// transferred.
+    #[inline]
+    pub const fn new_account_cost(&self, is_spurious_dragon: bool, transfers_value: bool) -> u64 {
+        if !is_spurious_dragon || transfers_value {
+            self.new_account
+        } else {
+            0
+        }
+    }
+
+    /// Dynamic part of the `SELFDESTRUCT` cost.
+    ///
+    /// `is_spurious_dragon` enables EIP-161, after which the beneficiary is only created if value
+    /// is transferred.
+    #[inline]
+    pub const fn selfdestruct_dynamic_cost(
+        &self,
+        is_spurious_dragon: bool,
+        res: &StateLoad<SelfDestructResult>,
+    ) -> u64 {
+        let should_charge_topup = if is_spurious_dragon {
+            res.data.had_value && !res.data.target_exists
+        } else {
+            !res.data.target_exists
+        };
+
+        let mut gas = 0;
+        if should_charge_topup {
+            gas += self.selfdestruct_new_account;
+        }
+        if res.is_cold {
+            gas += self.cold_account_access;
+        }
+        gas
+    }
+
+    /// Number of tokens in calldata.
+    #[inline]
+    pub fn tokens_in_calldata(&self, input: &[u8]) -> u64 {
+        let zero_data_len = input.iter().filter(|v| **v == 0).count() as u64;
+        let non_zero_data_len = input.len() as u64 - zero_data_len;
+        zero_data_len + non_zero_data_len * self.tx_token_non_zero_byte_multiplier
+    }
+
+    /// EIP-7623 floor cost of a transaction, zero if there is no floor.
+    #[inline]
+    pub const fn tx_floor_cost(&self, tokens_in_calldata: u64) -> u64 {
+        if self.tx_floor_cost_per_token == 0 {
+            return 0;
+        }
+        tokens_in_calldata * self.tx_floor_cost_per_token + self.tx_base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::StorageValue;
+
+    #[test]
+    fn mainnet_presets() {
+        let frontier = GasParams::mainnet(SpecId::FRONTIER);
+        assert_eq!(frontier.sload, 50);
+        assert_eq!(frontier.call, 40);
+        assert_eq!(frontier.tx_create, 0);
+        assert_eq!(frontier.tx_token_non_zero_byte_multiplier, 17);
+        assert_eq!(frontier.cold_sload_additional(), 0);
+
+        let istanbul = GasParams::mainnet(SpecId::MUIR_GLACIER);
+        assert_eq!(istanbul.sload, 800);
+        assert_eq!(istanbul.balance, 700);
+        assert_eq!(istanbul.sstore_clears_refund, 15000);
+
+        let berlin = GasParams::mainnet(SpecId::BERLIN);
+        assert_eq!(berlin.cold_sload_additional(), 2000);
+        assert_eq!(berlin.cold_account_access_additional(), 2500);
+        assert_eq!(berlin.sstore_reset, 2900);
+
+        let london = GasParams::mainnet(SpecId::LONDON);
+        assert_eq!(london.sstore_clears_refund, 4800);
+        assert_eq!(london.selfdestruct_refund, 0);
+
+        let prague = GasParams::mainnet(SpecId::OSAKA);
+        assert_eq!(prague.tx_authorization, 25000);
+        assert_eq!(prague.tx_floor_cost(10), 21100);
+    }
+
+    #[test]
+    fn sstore_with_overridden_costs() {
+        let one = StorageValue::from(1);
+        let two = StorageValue::from(2);
+
+        // SLOAD costs more than setting or resetting a slot.
+        let params = GasParams {
+            sload: 30_000,
+            ..GasParams::new_spec(SpecId::BERLIN)
+        };
+        for original in [StorageValue::ZERO, one] {
+            // Restores the original value, which refunds the difference to SLOAD.
+            let vals = SStoreResult {
+                original_value: original,
+                present_value: two,
+                new_value: original,
+            };
+            assert_eq!(params.sstore_cost(true, &vals, false), 30_000);
+            assert_eq!(params.sstore_dynamic_cost(true, &vals, false), 0);
+            let expected = if original.is_zero() {
+                params.sstore_set as i64 - 30_000
+            } else {
+                params.sstore_reset as i64 - 30_000
+            };
+            assert_eq!(params.sstore_refund(true, &vals), expected);
+        }
+
+        // Setting a slot costs less than resetting it before Istanbul.
+        let params = GasParams {
+            sstore_set: 1_000,
+            ..GasParams::new_spec(SpecId::FRONTIER)
+        };
+        let vals = SStoreResult {
+            original_value: StorageValue::ZERO,
+            present_value: StorageValue::ZERO,
+            new_value: one,
+        };
+        assert_eq!(params.sstore_static_cost(false), params.sstore_reset);
+        assert_eq!(params.sstore_dynamic_cost(false, &vals, false), 0);
+    }
+
+    #[test]
+    fn mainnet_matches_new_spec() {
+        for spec in 0..=u8::MAX {
+            let Some(spec) = SpecId::try_from_u8(spec) else {
+                continue;
+            };
+            assert_eq!(GasParams::mainnet(spec), &GasParams::new_spec(spec));
+        }
+    }
+
+    #[test]
+    fn exp_cost() {
+        let params = GasParams::new_spec(SpecId::PRAGUE);
+        assert_eq!(params.exp_cost(U256::ZERO), Some(10));
+        assert_eq!(params.exp_cost(U256::from(255)), Some(60));
+        assert_eq!(params.exp_cost(U256::from(256)), Some(110));
+        assert_eq!(params.exp_cost(U256::MAX), Some(10 + 50 * 32));
+
+        let params = GasParams {
+            exp_byte: u64::MAX,
+            ..params
+        };
+        assert_eq!(params.exp_cost(U256::from(256)), None);
+    }
+
+    #[test]
+    fn sstore_cost_and_refund() {
+        let params = GasParams::new_spec(SpecId::PRAGUE);
+        let vals = SStoreResult {
+            original_value: U256::ZERO,
+            present_value: U256::ZERO,
+            new_value: U256::from(1),
+        };
+        assert_eq!(params.sstore_cost(true, &vals, true), 22100);
+        assert_eq!(params.sstore_dynamic_cost(true, &vals, false), 19900);
+        assert_eq!(params.sstore_refund(true, &vals), 0);
+
+        let vals = SStoreResult {
+            original_value: U256::from(1),
+            present_value: U256::from(1),
+            new_value: U256::ZERO,
+        };
+        assert_eq!(params.sstore_cost(true, &vals, false), 2900);
+        assert_eq!(params.sstore_refund(true, &vals), 4800);
+
+        let frontier = GasParams::new_spec(SpecId::FRONTIER);
+        assert_eq!(frontier.sstore_cost(false, &vals, false), 5000);
+        assert_eq!(frontier.sstore_refund(false, &vals), 15000);
+    }
+}
@@ -1,11 +1,12 @@
 //! Host interface for external blockchain state access.
 
 use crate::{
+    cfg::GasParams,
     context::{SStoreResult, SelfDestructResult, StateLoad},
     journaled_state::{AccountInfoLoad, AccountLoad},
 };
 use auto_impl::auto_impl;
-use primitives::{Address, Bytes, Log, StorageKey, StorageValue, B256, U256};
+use primitives::{hardfork::SpecId, Address, Bytes, Log, StorageKey, StorageValue, B256, U256};
 use state::Bytecode;
 
 /// Error that can happen when loading account info.
@@ -60,6 +61,13 @@ pub trait Host {
     /// Max initcode size, calls `ContextTr::cfg().max_code_size().saturating_mul(2)`
     fn max_initcode_size(&self) -> usize;
 
+    /// Gas schedule, calls `ContextTr::cfg().gas_params()`
+    ///
+    /// Defaults to the mainnet schedule of [`SpecId::default`].
+    fn gas_params(&self) -> &GasParams {
+        GasParams::mainnet(SpecId::default())
+    }
+
     /* Database */
 
     /// Block hash, calls `ContextTr::journal_mut().db().block_hash(number)`
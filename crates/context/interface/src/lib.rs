@@ -6,6 +6,7 @@
 extern crate alloc as std;
 
 pub mod block;
+pub mod budget;
 pub mod cfg;
 pub mod context;
 pub mod host;
@@ -15,7 +16,8 @@ pub mod result;
 pub mod transaction;
 
 pub use block::Block;
-pub use cfg::{Cfg, CreateScheme, TransactTo};
+pub use budget::{ExecutionBudget, ExecutionLimit};
+pub use cfg::{Cfg, CreateScheme, GasParams, TransactTo};
 pub use context::{ContextError, ContextSetters, ContextTr};
 pub use database_interface::{DBErrorMarker, Database};
 pub use either;
@@ -1,4 +1,5 @@
 //! Local context trait [`LocalContextTr`] and related types.
+use crate::ExecutionBudget;
 use core::{
     cell::{Ref, RefCell},
     ops::Range,
@@ -64,6 +65,22 @@ impl<T> FrameStack<T> {
         self.index = Some(0);
     }
 
+    /// Returns the initialized frames up to and including the current one.
+    #[inline]
+    pub fn frames(&self) -> &[T] {
+        match self.index {
+            Some(index) => &self.stack[..=index],
+            None => &[],
+        }
+    }
+
+    /// Replaces the stack with the given frames, the last frame becomes the current one.
+    #[inline]
+    pub fn set_frames(&mut self, frames: Vec<T>) {
+        self.index = frames.len().checked_sub(1);
+        self.stack = frames;
+    }
+
     /// Returns the current index of the stack.
     #[inline]
     pub fn index(&self) -> Option<usize> {
@@ -238,6 +255,14 @@ pub trait LocalContextTr {
     ///
     /// Returns `Some(String)` if a precompile error message was recorded.
     fn take_precompile_error_context(&mut self) -> Option<String>;
+
+    /// Remaining execution budget of the transaction, shared by all frames.
+    fn execution_budget(&self) -> ExecutionBudget {
+        ExecutionBudget::UNLIMITED
+    }
+
+    /// Sets the remaining execution budget of the transaction.
+    fn set_execution_budget(&mut self, _budget: ExecutionBudget) {}
 }
 
 #[cfg(test)]
@@ -627,6 +627,12 @@ pub enum HaltReason {
     OutOfFunds,
     /// Call is too deep.
     CallTooDeep,
+
+    /* Execution budget halts, only found if the limits are set in the configuration */
+    /// Step limit of the transaction was reached.
+    StepLimitReached,
+    /// Time limit of the transaction was reached.
+    TimeLimitReached,
 }
 
 /// Out of gas errors.
@@ -1,7 +1,10 @@
 //! This module contains [`CfgEnv`] and implements [`Cfg`] trait for it.
-pub use context_interface::Cfg;
+pub use context_interface::{cfg::GasParams, Cfg};
 
-use primitives::{eip170, eip3860, eip7825, hardfork::SpecId};
+use crate::ChainHardforks;
+use context_interface::Block;
+use core::time::Duration;
+use primitives::{eip170, eip3860, eip7825, hardfork::SpecId, Address};
 /// EVM configuration
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -55,6 +58,12 @@ pub struct CfgEnv<SPEC = SpecId> {
     /// Introduced in Osaka in [EIP-7825: Transaction Gas Limit Cap](https://eips.ethereum.org/EIPS/eip-7825)
     /// with initials cap of 30M.
     pub tx_gas_limit_cap: Option<u64>,
+    /// Gas schedule override.
+    ///
+    /// If None, the mainnet gas schedule of the spec is used, see [`GasParams::mainnet`].
+    /// If Some, this schedule is used regardless of the spec.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_params: Option<GasParams>,
     /// A hard memory limit in bytes beyond which
     /// [OutOfGasError::Memory][context_interface::result::OutOfGasError::Memory] cannot be resized.
     ///
@@ -64,6 +73,40 @@ pub struct CfgEnv<SPEC = SpecId> {
     /// Defaults to `2^32 - 1` bytes per EIP-1985.
     #[cfg(feature = "memory_limit")]
     pub memory_limit: u64,
+    /// Maximum number of instructions a transaction can execute, summed over all frames.
+    ///
+    /// When reached, execution halts with
+    /// [HaltReason::StepLimitReached][context_interface::result::HaltReason::StepLimitReached].
+    ///
+    /// By default, it is set to `None` (unlimited).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub step_limit: Option<u64>,
+    /// Maximum wall-clock time a transaction can spend executing instructions.
+    ///
+    /// When reached, execution halts with
+    /// [HaltReason::TimeLimitReached][context_interface::result::HaltReason::TimeLimitReached].
+    /// The clock is sampled periodically, so the limit can be overshot by a few thousand
+    /// instructions or by a single slow database access. Only enforced with the `std` feature.
+    ///
+    /// By default, it is set to `None` (unlimited).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_limit: Option<Duration>,
+    /// Recipient of the base fee.
+    ///
+    /// Chains that do not burn the base fee can set this to credit it to an account, e.g. a fee
+    /// vault. It is ignored on OP Stack chains, which credit their base fee vaults in the handler.
+    ///
+    /// By default, it is set to `None` (burned).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub base_fee_recipient: Option<Address>,
+    /// Fork activation schedule.
+    ///
+    /// If set, [`spec`](Self::spec) is updated to the active spec whenever the block of the
+    /// context is set, see [`Cfg::update_spec_for_block`].
+    ///
+    /// By default, it is set to `None` (the spec is fixed).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hardforks: Option<ChainHardforks<SPEC>>,
     /// Skip balance checks if `true`
     ///
     /// Adds transaction cost to balance to ensure execution doesn't fail.
@@ -159,8 +202,13 @@ impl<SPEC> CfgEnv<SPEC> {
             max_blobs_per_tx: None,
             tx_gas_limit_cap: None,
             blob_base_fee_update_fraction: None,
+            gas_params: None,
             #[cfg(feature = "memory_limit")]
             memory_limit: (1 << 32) - 1,
+            step_limit: None,
+            time_limit: None,
+            base_fee_recipient: None,
+            hardforks: None,
             #[cfg(feature = "optional_balance_check")]
             disable_balance_check: false,
             #[cfg(feature = "optional_block_gas_limit")]
@@ -199,6 +247,8 @@ impl<SPEC> CfgEnv<SPEC> {
     }
 
     /// Consumes `self` and returns a new `CfgEnv` with the specified spec.
+    ///
+    /// The fork activation schedule is of the previous spec type and is not kept.
     pub fn with_spec<OSPEC: Into<SpecId>>(self, spec: OSPEC) -> CfgEnv<OSPEC> {
         CfgEnv {
             chain_id: self.chain_id,
@@ -210,8 +260,13 @@ impl<SPEC> CfgEnv<SPEC> {
             tx_gas_limit_cap: self.tx_gas_limit_cap,
             max_blobs_per_tx: self.max_blobs_per_tx,
             blob_base_fee_update_fraction: self.blob_base_fee_update_fraction,
+            gas_params: self.gas_params,
             #[cfg(feature = "memory_limit")]
             memory_limit: self.memory_limit,
+            step_limit: self.step_limit,
+            time_limit: self.time_limit,
+            base_fee_recipient: self.base_fee_recipient,
+            hardforks: None,
             #[cfg(feature = "optional_balance_check")]
             disable_balance_check: self.disable_balance_check,
             #[cfg(feature = "optional_block_gas_limit")]
@@ -231,6 +286,36 @@ impl<SPEC> CfgEnv<SPEC> {
         }
     }
 
+    /// Consumes `self` and returns a new `CfgEnv` with the gas schedule override.
+    pub fn with_gas_params(mut self, gas_params: GasParams) -> Self {
+        self.gas_params = Some(gas_params);
+        self
+    }
+
+    /// Consumes `self` and returns a new `CfgEnv` with the specified step limit.
+    pub fn with_step_limit(mut self, step_limit: u64) -> Self {
+        self.step_limit = Some(step_limit);
+        self
+    }
+
+    /// Consumes `self` and returns a new `CfgEnv` with the specified time limit.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Consumes `self` and returns a new `CfgEnv` with the specified fork activation schedule.
+    pub fn with_hardforks(mut self, hardforks: ChainHardforks<SPEC>) -> Self {
+        self.hardforks = Some(hardforks);
+        self
+    }
+
+    /// Consumes `self` and returns a new `CfgEnv` with the specified base fee recipient.
+    pub fn with_base_fee_recipient(mut self, base_fee_recipient: Address) -> Self {
+        self.base_fee_recipient = Some(base_fee_recipient);
+        self
+    }
+
     /// Sets the blob target
     pub fn with_max_blobs_per_tx(mut self, max_blobs_per_tx: u64) -> Self {
         self.set_max_blobs_per_tx(max_blobs_per_tx);
@@ -410,6 +495,35 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
             }
         }
     }
+
+    fn step_limit(&self) -> Option<u64> {
+        self.step_limit
+    }
+
+    fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    fn base_fee_recipient(&self) -> Option<Address> {
+        self.base_fee_recipient
+    }
+
+    fn update_spec_for_block(&mut self, block: &impl Block) {
+        if let Some(spec) = self
+            .hardforks
+            .as_ref()
+            .and_then(|hardforks| hardforks.spec_for_block(block))
+        {
+            self.spec = spec;
+        }
+    }
+
+    #[inline]
+    fn gas_params(&self) -> &GasParams {
+        self.gas_params
+            .as_ref()
+            .unwrap_or_else(|| GasParams::mainnet(self.spec.into()))
+    }
 }
 
 impl<SPEC: Default> Default for CfgEnv<SPEC> {
@@ -427,4 +541,19 @@ mod test {
         let cfg: CfgEnv = Default::default();
         assert_eq!(cfg.max_blobs_per_tx(), None);
     }
+
+    #[test]
+    fn gas_params_follow_spec_unless_overridden() {
+        let mut cfg = CfgEnv::new_with_spec(SpecId::BERLIN);
+        assert_eq!(cfg.gas_params().sstore_clears_refund, 15000);
+        cfg.spec = SpecId::LONDON;
+        assert_eq!(cfg.gas_params().sstore_clears_refund, 4800);
+
+        let params = GasParams {
+            call_stipend: 5000,
+            ..GasParams::new_spec(SpecId::LONDON)
+        };
+        let cfg = cfg.with_gas_params(params).with_spec(SpecId::PRAGUE);
+        assert_eq!(cfg.gas_params(), &params);
+    }
 }
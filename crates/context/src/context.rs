@@ -4,7 +4,8 @@ use context_interface::{
     context::{ContextError, ContextSetters, SStoreResult, SelfDestructResult, StateLoad},
     host::LoadError,
     journaled_state::AccountInfoLoad,
-    Block, Cfg, ContextTr, Host, JournalTr, LocalContextTr, Transaction, TransactionType,
+    Block, Cfg, ContextTr, GasParams, Host, JournalTr, LocalContextTr, Transaction,
+    TransactionType,
 };
 use database_interface::{Database, DatabaseRef, EmptyDB, WrapDatabaseRef};
 use derive_where::derive_where;
@@ -121,6 +122,7 @@ impl<
 
     fn set_block(&mut self, block: Self::Block) {
         self.block = block;
+        self.update_spec_for_block();
     }
 }
 
@@ -227,7 +229,7 @@ where
         self,
         block: OB,
     ) -> Context<OB, TX, CFG, DB, JOURNAL, CHAIN, LOCAL> {
-        Context {
+        let mut context = Context {
             tx: self.tx,
             block,
             cfg: self.cfg,
@@ -235,7 +237,9 @@ where
             local: self.local,
             chain: self.chain,
             error: Ok(()),
-        }
+        };
+        context.update_spec_for_block();
+        context
     }
     /// Creates a new context with a new transaction type.
     pub fn with_tx<OTX: Transaction>(
@@ -366,6 +370,14 @@ where
         F: FnOnce(&mut BLOCK),
     {
         f(&mut self.block);
+        self.update_spec_for_block();
+    }
+
+    /// Updates the spec of the configuration and the journal for the current block, see
+    /// [`Cfg::update_spec_for_block`].
+    fn update_spec_for_block(&mut self) {
+        self.cfg.update_spec_for_block(&self.block);
+        self.journaled_state.set_spec_id(self.cfg.spec().into());
     }
 
     /// Modifies the context transaction.
@@ -493,6 +505,10 @@ impl<
         self.cfg().max_initcode_size()
     }
 
+    fn gas_params(&self) -> &GasParams {
+        self.cfg().gas_params()
+    }
+
     /* Database */
 
     fn block_hash(&mut self, requested_number: u64) -> Option<B256> {
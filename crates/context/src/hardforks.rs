@@ -0,0 +1,259 @@
+//! This module contains [`ChainHardforks`], the fork activation schedule of a chain.
+use context_interface::Block;
+use primitives::{hardfork::SpecId, U256};
+use std::{vec, vec::Vec};
+
+/// Condition under which a hardfork is activated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForkCondition {
+    /// Activated at and after the block with this number.
+    Block(u64),
+    /// Activated at and after the first block with this timestamp or later.
+    Timestamp(u64),
+}
+
+impl ForkCondition {
+    /// Returns `true` if the condition is met for a block with the given number and timestamp.
+    #[inline]
+    pub const fn is_active_at(&self, number: u64, timestamp: u64) -> bool {
+        match *self {
+            Self::Block(block) => number >= block,
+            Self::Timestamp(time) => timestamp >= time,
+        }
+    }
+}
+
+/// Fork activation schedule of a chain.
+///
+/// Contains specs with their activation conditions, ordered by activation. The active spec of a
+/// block is the last one whose condition is met.
+///
+/// Ethereum schedules are available with [`ChainHardforks::mainnet`], [`ChainHardforks::sepolia`],
+/// [`ChainHardforks::holesky`] and [`ChainHardforks::hoodi`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainHardforks<SPEC = SpecId> {
+    forks: Vec<(SPEC, ForkCondition)>,
+}
+
+impl<SPEC: Copy> ChainHardforks<SPEC> {
+    /// Creates a new schedule from specs ordered by activation.
+    pub fn new(forks: Vec<(SPEC, ForkCondition)>) -> Self {
+        Self { forks }
+    }
+
+    /// Creates a schedule that starts at `from` and transitions to `to` once `condition` is met.
+    ///
+    /// Used by transition fixtures, e.g. `PragueToOsakaAtTime15k` is
+    /// `transition(PRAGUE, OSAKA, ForkCondition::Timestamp(15_000))`.
+    pub fn transition(from: SPEC, to: SPEC, condition: ForkCondition) -> Self {
+        Self::new(vec![(from, ForkCondition::Block(0)), (to, condition)])
+    }
+
+    /// Returns the specs with their activation conditions.
+    pub fn forks(&self) -> &[(SPEC, ForkCondition)] {
+        &self.forks
+    }
+
+    /// Returns the active spec for a block with the given number and timestamp.
+    ///
+    /// Returns `None` if no spec is active yet.
+    pub fn spec_at(&self, number: u64, timestamp: u64) -> Option<SPEC> {
+        self.forks
+            .iter()
+            .rev()
+            .find(|(_, condition)| condition.is_active_at(number, timestamp))
+            .map(|(spec, _)| *spec)
+    }
+
+    /// Returns the active spec for the given block.
+    pub fn spec_for_block(&self, block: &impl Block) -> Option<SPEC> {
+        self.spec_at(
+            saturating_u64(block.number()),
+            saturating_u64(block.timestamp()),
+        )
+    }
+}
+
+impl ChainHardforks {
+    /// Ethereum mainnet chain ID.
+    pub const MAINNET_CHAIN_ID: u64 = 1;
+    /// Sepolia chain ID.
+    pub const SEPOLIA_CHAIN_ID: u64 = 11_155_111;
+    /// Holesky chain ID.
+    pub const HOLESKY_CHAIN_ID: u64 = 17_000;
+    /// Hoodi chain ID.
+    pub const HOODI_CHAIN_ID: u64 = 560_048;
+
+    /// Returns the schedule of a known Ethereum chain.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            Self::MAINNET_CHAIN_ID => Some(Self::mainnet()),
+            Self::SEPOLIA_CHAIN_ID => Some(Self::sepolia()),
+            Self::HOLESKY_CHAIN_ID => Some(Self::holesky()),
+            Self::HOODI_CHAIN_ID => Some(Self::hoodi()),
+            _ => None,
+        }
+    }
+
+    /// Ethereum mainnet schedule.
+    ///
+    /// Constantinople is replaced by Petersburg as both were activated at the same block.
+    pub fn mainnet() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        use SpecId::*;
+        Self::new(vec![
+            (FRONTIER, Block(0)),
+            (FRONTIER_THAWING, Block(200_000)),
+            (HOMESTEAD, Block(1_150_000)),
+            (DAO_FORK, Block(1_920_000)),
+            (TANGERINE, Block(2_463_000)),
+            (SPURIOUS_DRAGON, Block(2_675_000)),
+            (BYZANTIUM, Block(4_370_000)),
+            (PETERSBURG, Block(7_280_000)),
+            (ISTANBUL, Block(9_069_000)),
+            (MUIR_GLACIER, Block(9_200_000)),
+            (BERLIN, Block(12_244_000)),
+            (LONDON, Block(12_965_000)),
+            (ARROW_GLACIER, Block(13_773_000)),
+            (GRAY_GLACIER, Block(15_050_000)),
+            (MERGE, Block(15_537_394)),
+            (SHANGHAI, Timestamp(1_681_338_455)),
+            (CANCUN, Timestamp(1_710_338_135)),
+            (PRAGUE, Timestamp(1_746_612_311)),
+            (OSAKA, Timestamp(1_764_798_551)),
+        ])
+    }
+
+    /// Sepolia testnet schedule.
+    pub fn sepolia() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        use SpecId::*;
+        Self::new(vec![
+            (LONDON, Block(0)),
+            (MERGE, Block(1_735_371)),
+            (SHANGHAI, Timestamp(1_677_557_088)),
+            (CANCUN, Timestamp(1_706_655_072)),
+            (PRAGUE, Timestamp(1_741_159_776)),
+            (OSAKA, Timestamp(1_760_427_360)),
+        ])
+    }
+
+    /// Holesky testnet schedule.
+    pub fn holesky() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        use SpecId::*;
+        Self::new(vec![
+            (MERGE, Block(0)),
+            (SHANGHAI, Timestamp(1_696_000_704)),
+            (CANCUN, Timestamp(1_707_305_664)),
+            (PRAGUE, Timestamp(1_740_434_112)),
+            (OSAKA, Timestamp(1_759_308_480)),
+        ])
+    }
+
+    /// Hoodi testnet schedule.
+    pub fn hoodi() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        use SpecId::*;
+        Self::new(vec![
+            (CANCUN, Block(0)),
+            (PRAGUE, Timestamp(1_742_999_832)),
+            (OSAKA, Timestamp(1_761_677_592)),
+        ])
+    }
+}
+
+/// Converts a block field to `u64`, saturating at [`u64::MAX`].
+fn saturating_u64(value: U256) -> u64 {
+    value.try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockEnv;
+
+    fn block(number: u64, timestamp: u64) -> BlockEnv {
+        BlockEnv {
+            number: U256::from(number),
+            timestamp: U256::from(timestamp),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mainnet_schedule() {
+        let mainnet = ChainHardforks::mainnet();
+        assert_eq!(mainnet.spec_at(0, 0), Some(SpecId::FRONTIER));
+        assert_eq!(mainnet.spec_at(7_279_999, 0), Some(SpecId::BYZANTIUM));
+        assert_eq!(mainnet.spec_at(7_280_000, 0), Some(SpecId::PETERSBURG));
+        assert_eq!(
+            mainnet.spec_at(15_537_394, 1_663_224_179),
+            Some(SpecId::MERGE)
+        );
+        assert_eq!(
+            mainnet.spec_at(17_034_870, 1_681_338_455),
+            Some(SpecId::SHANGHAI)
+        );
+        assert_eq!(
+            mainnet.spec_at(22_431_084, 1_746_612_311),
+            Some(SpecId::PRAGUE)
+        );
+        assert_eq!(mainnet.spec_at(u64::MAX, u64::MAX), Some(SpecId::OSAKA));
+    }
+
+    #[test]
+    fn specs_are_ordered() {
+        for chain in [
+            ChainHardforks::mainnet(),
+            ChainHardforks::sepolia(),
+            ChainHardforks::holesky(),
+            ChainHardforks::hoodi(),
+        ] {
+            assert!(chain.forks().windows(2).all(|w| w[0].0 < w[1].0));
+        }
+    }
+
+    #[test]
+    fn transition_updates_context_spec() {
+        use crate::{CfgEnv, Context, ContextSetters, Journal, TxEnv};
+        use database_interface::EmptyDB;
+
+        let hardforks = ChainHardforks::transition(
+            SpecId::PRAGUE,
+            SpecId::OSAKA,
+            ForkCondition::Timestamp(15_000),
+        );
+        let mut context = Context::<BlockEnv, TxEnv, CfgEnv, EmptyDB, Journal<EmptyDB>, ()>::new(
+            EmptyDB::new(),
+            SpecId::CANCUN,
+        )
+        .modify_cfg_chained(|cfg| cfg.hardforks = Some(hardforks))
+        .with_block(block(1, 14_999));
+        assert_eq!(context.cfg.spec, SpecId::PRAGUE);
+
+        context.set_block(block(2, 15_000));
+        assert_eq!(context.cfg.spec, SpecId::OSAKA);
+        assert_eq!(context.journaled_state.inner.spec, SpecId::OSAKA);
+
+        context.modify_block(|block| block.timestamp = U256::from(14_000));
+        assert_eq!(context.cfg.spec, SpecId::PRAGUE);
+    }
+
+    #[test]
+    fn spec_before_first_fork() {
+        let hardforks = ChainHardforks::new(vec![(SpecId::LONDON, ForkCondition::Block(10))]);
+        assert_eq!(hardforks.spec_for_block(&block(9, 0)), None);
+        assert_eq!(
+            hardforks.spec_for_block(&block(10, 0)),
+            Some(SpecId::LONDON)
+        );
+        assert_eq!(
+            ChainHardforks::from_chain_id(ChainHardforks::HOODI_CHAIN_ID),
+            Some(ChainHardforks::hoodi())
+        );
+        assert_eq!(ChainHardforks::from_chain_id(1337), None);
+    }
+}
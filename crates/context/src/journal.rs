@@ -9,6 +9,8 @@ pub use context_interface::journaled_state::entry::{JournalEntry, JournalEntryTr
 pub use inner::JournalInner;
 
 use bytecode::Bytecode;
+#[cfg(feature = "std")]
+use bytecode::BytecodeCache;
 use context_interface::{
     context::{SStoreResult, SelfDestructResult, StateLoad},
     journaled_state::{
@@ -22,6 +24,8 @@ use primitives::{
     hardfork::SpecId, Address, HashMap, HashSet, Log, StorageKey, StorageValue, B256, U256,
 };
 use state::{Account, EvmState};
+#[cfg(feature = "std")]
+use std::sync::Arc;
 use std::vec::Vec;
 
 /// A journal of state changes internal to the EVM
@@ -69,6 +73,13 @@ impl<DB, ENTRY: JournalEntryTr> Journal<DB, ENTRY> {
         Self { database, inner }
     }
 
+    /// Sets the shared cache of analyzed bytecode, see [`JournalInner::code_cache`].
+    #[cfg(feature = "std")]
+    pub fn with_code_cache(mut self, code_cache: Arc<BytecodeCache>) -> Self {
+        self.inner.code_cache = Some(code_cache);
+        self
+    }
+
     /// Consumes the [`Journal`] and returns [`JournalInner`].
     ///
     /// If you need to preserve the original journal, use [`Self::to_inner`] instead which clones the state.
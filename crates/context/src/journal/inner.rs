@@ -1,6 +1,8 @@
 //! Module containing the [`JournalInner`] that is part of [`crate::Journal`].
 use super::warm_addresses::WarmAddresses;
 use bytecode::Bytecode;
+#[cfg(feature = "std")]
+use bytecode::BytecodeCache;
 use context_interface::{
     context::{SStoreResult, SelfDestructResult, StateLoad},
     journaled_state::{
@@ -17,6 +19,8 @@ use primitives::{
     Address, HashMap, Log, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use state::{Account, EvmState, EvmStorageSlot, TransientStorage};
+#[cfg(feature = "std")]
+use std::sync::Arc;
 use std::vec::Vec;
 /// Inner journal state that contains journal and state changes.
 ///
@@ -57,6 +61,14 @@ pub struct JournalInner<ENTRY> {
     pub spec: SpecId,
     /// Warm addresses containing both coinbase and current precompiles.
     pub warm_addresses: WarmAddresses,
+    /// Shared cache of analyzed bytecode that is consulted whenever code is loaded from the
+    /// database, both before [`Database::code_by_hash`] and for code returned by
+    /// [`Database::basic`].
+    ///
+    /// It outlives transactions and is not serialized.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub code_cache: Option<Arc<BytecodeCache>>,
 }
 
 impl<ENTRY: JournalEntryTr> Default for JournalInner<ENTRY> {
@@ -80,6 +92,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             depth: 0,
             spec: SpecId::default(),
             warm_addresses: WarmAddresses::new(),
+            #[cfg(feature = "std")]
+            code_cache: None,
         }
     }
 
@@ -108,7 +122,12 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             transaction_id,
             spec,
             warm_addresses,
+            #[cfg(feature = "std")]
+            code_cache,
         } = self;
+        // Code cache is shared and outlives the transaction.
+        #[cfg(feature = "std")]
+        let _ = code_cache;
         // Spec precompiles and state are not changed. It is always set again execution.
         let _ = spec;
         let _ = state;
@@ -137,7 +156,12 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             transaction_id,
             spec,
             warm_addresses,
+            #[cfg(feature = "std")]
+            code_cache,
         } = self;
+        // Code cache is shared and outlives the transaction.
+        #[cfg(feature = "std")]
+        let _ = code_cache;
         let is_spurious_dragon_enabled = spec.is_enabled_in(SPURIOUS_DRAGON);
         // iterate over all journals entries and revert our global state
         journal.drain(..).rev().for_each(|entry| {
@@ -169,7 +193,12 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             transaction_id,
             spec,
             warm_addresses,
+            #[cfg(feature = "std")]
+            code_cache,
         } = self;
+        // Code cache is shared and outlives the transaction.
+        #[cfg(feature = "std")]
+        let _ = code_cache;
         // Spec is not changed. And it is always set again in execution.
         let _ = spec;
         // Clear coinbase address warming for next tx
@@ -663,6 +692,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         load_code: bool,
         skip_cold_load: bool,
     ) -> Result<StateLoad<JournaledAccount<'_, ENTRY>>, JournalLoadError<DB::Error>> {
+        let mut is_loaded = false;
         let load = match self.state.entry(address) {
             Entry::Occupied(entry) => {
                 let account = entry.into_mut();
@@ -705,6 +735,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
                     return Err(JournalLoadError::ColdLoadSkipped);
                 }
                 let account = if let Some(account) = db.basic(address)? {
+                    is_loaded = true;
                     account.into()
                 } else {
                     Account::new_not_existing(self.transaction_id)
@@ -722,12 +753,28 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             self.journal.push(ENTRY::account_warmed(address));
         }
 
-        if load_code && load.data.info.code.is_none() {
-            let info = &mut load.data.info;
+        // Code that is missing or was just returned by the database goes through the code cache.
+        let info = &mut load.data.info;
+        if (load_code && info.code.is_none()) || (is_loaded && info.code.is_some()) {
             let code = if info.code_hash == KECCAK_EMPTY {
                 Bytecode::default()
             } else {
-                db.code_by_hash(info.code_hash)?
+                let code_hash = info.code_hash;
+                let code = info.code.take();
+                let load_code = || match code {
+                    Some(code) => Ok(code),
+                    None => db.code_by_hash(code_hash),
+                };
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "std")] {
+                        match &self.code_cache {
+                            Some(cache) => cache.get_or_try_insert_with(code_hash, load_code)?,
+                            None => load_code()?,
+                        }
+                    } else {
+                        load_code()?
+                    }
+                }
             };
             info.code = Some(code);
         }
@@ -933,4 +980,61 @@ mod tests {
         assert!(!state_load.is_cold); // Should be warm
         assert_eq!(state_load.data, U256::ZERO); // Empty slot
     }
+
+    #[test]
+    fn test_load_code_from_shared_cache() {
+        use database::{CacheDB, MetricsDB};
+        use primitives::Bytes;
+
+        let address = address!("1000000000000000000000000000000000000000");
+        let code = Bytecode::new_legacy(Bytes::from_static(&[0x60, 0x01, 0x5b]));
+        let mut db = CacheDB::<EmptyDB>::default();
+        // Code is not returned with the account, so it is loaded by hash.
+        let code_hash = code.hash_slow();
+        db.insert_account_info(address, AccountInfo::default().with_code_hash(code_hash));
+        db.cache.contracts.insert(code_hash, code.clone());
+
+        let cache = Arc::new(BytecodeCache::new(16));
+        for i in 0..2 {
+            let mut journal = JournalInner::<JournalEntry>::new();
+            journal.code_cache = Some(cache.clone());
+            let mut db = MetricsDB::new(db.clone());
+            let account = journal
+                .load_account_mut_optional_code(&mut db, address, true, false)
+                .unwrap();
+            assert_eq!(account.data.info.code, Some(code.clone()));
+            assert_eq!(db.metrics().basic.calls, 1);
+            // Only the first journal loads the code from the database.
+            assert_eq!(db.metrics().code_by_hash.calls, (i == 0) as u64);
+        }
+        assert_eq!(cache.metrics().hits, 1);
+        assert_eq!(cache.metrics().misses, 1);
+    }
+
+    #[test]
+    fn test_load_code_returned_with_account_from_shared_cache() {
+        use database::CacheDB;
+        use primitives::Bytes;
+
+        let address = address!("1000000000000000000000000000000000000000");
+        let code = Bytecode::new_legacy(Bytes::from_static(&[0x60, 0x01, 0x5b]));
+        let mut db = CacheDB::<EmptyDB>::default();
+        // Code is returned with the account, analyzed separately by every database.
+        db.insert_account_info(address, AccountInfo::default().with_code(code.clone()));
+
+        let cache = Arc::new(BytecodeCache::new(16));
+        let mut loaded = Vec::new();
+        for _ in 0..2 {
+            let mut journal = JournalInner::<JournalEntry>::new();
+            journal.code_cache = Some(cache.clone());
+            let mut db = db.clone();
+            let account = journal
+                .load_account_mut_optional_code(&mut db, address, false, false)
+                .unwrap();
+            loaded.push(account.data.info.code.clone().unwrap());
+        }
+        assert_eq!(loaded, [code.clone(), code]);
+        assert_eq!(cache.metrics().hits, 1);
+        assert_eq!(cache.metrics().misses, 1);
+    }
 }
@@ -11,6 +11,7 @@ pub mod block;
 pub mod cfg;
 pub mod context;
 pub mod evm;
+pub mod hardforks;
 pub mod journal;
 pub mod local;
 pub mod tx;
@@ -19,6 +20,7 @@ pub use block::BlockEnv;
 pub use cfg::{Cfg, CfgEnv};
 pub use context::*;
 pub use evm::Evm;
+pub use hardforks::{ChainHardforks, ForkCondition};
 pub use journal::*;
 pub use local::LocalContext;
 pub use tx::TxEnv;
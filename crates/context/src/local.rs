@@ -1,5 +1,5 @@
 //! Local context that is filled by execution.
-use context_interface::LocalContextTr;
+use context_interface::{ExecutionBudget, LocalContextTr};
 use core::cell::RefCell;
 use std::{rc::Rc, string::String, vec::Vec};
 
@@ -10,6 +10,8 @@ pub struct LocalContext {
     pub shared_memory_buffer: Rc<RefCell<Vec<u8>>>,
     /// Optional precompile error message to bubble up.
     pub precompile_error_message: Option<String>,
+    /// Remaining execution budget of the transaction.
+    pub execution_budget: ExecutionBudget,
 }
 
 impl Default for LocalContext {
@@ -17,6 +19,7 @@ impl Default for LocalContext {
         Self {
             shared_memory_buffer: Rc::new(RefCell::new(Vec::with_capacity(1024 * 4))),
             precompile_error_message: None,
+            execution_budget: ExecutionBudget::UNLIMITED,
         }
     }
 }
@@ -26,6 +29,7 @@ impl LocalContextTr for LocalContext {
         // Sets len to 0 but it will not shrink to drop the capacity.
         unsafe { self.shared_memory_buffer.borrow_mut().set_len(0) };
         self.precompile_error_message = None;
+        self.execution_budget = ExecutionBudget::UNLIMITED;
     }
 
     fn shared_memory_buffer(&self) -> &Rc<RefCell<Vec<u8>>> {
@@ -39,6 +43,14 @@ impl LocalContextTr for LocalContext {
     fn take_precompile_error_context(&mut self) -> Option<String> {
         self.precompile_error_message.take()
     }
+
+    fn execution_budget(&self) -> ExecutionBudget {
+        self.execution_budget
+    }
+
+    fn set_execution_budget(&mut self, budget: ExecutionBudget) {
+        self.execution_budget = budget;
+    }
 }
 
 impl LocalContext {
@@ -0,0 +1,107 @@
+#![allow(missing_docs)]
+//! Block execution latency with synchronous and background transition merging.
+//!
+//! Every block commits changes to `ACCOUNTS` accounts with `SLOTS` storage slots each and merges
+//! the transitions with reverts, as done after every block by a node.
+use criterion::{criterion_group, criterion_main, Criterion};
+use database_interface::{Database, DatabaseCommit};
+use primitives::{Address, HashMap, StorageKey, StorageValue, U256};
+use revm_database::{states::bundle_state::BundleRetention, InMemoryDB, State};
+use state::{Account, EvmStorageSlot};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const BLOCKS: u64 = 10;
+const ACCOUNTS: u64 = 1_000;
+const SLOTS: u64 = 16;
+
+fn new_state(background: bool) -> State<InMemoryDB> {
+    let builder = State::builder()
+        .with_database(InMemoryDB::default())
+        .with_bundle_update();
+    if background {
+        builder.with_background_transition_merge().build()
+    } else {
+        builder.build()
+    }
+}
+
+/// Executes the blocks on the calling thread and returns the time spent in
+/// [`State::merge_transitions`].
+fn execute_blocks(state: &mut State<InMemoryDB>) -> Duration {
+    let mut merge = Duration::ZERO;
+    for block in 1..=BLOCKS {
+        let mut changes = HashMap::default();
+        for i in 0..ACCOUNTS {
+            let address = Address::from_word(U256::from(i).into());
+            let mut info = state.basic(address).unwrap().unwrap_or_default();
+            info.balance += U256::from(block);
+            let storage = (0..SLOTS).map(|slot| {
+                (
+                    StorageKey::from(slot),
+                    EvmStorageSlot::new_changed(
+                        StorageValue::from(block - 1),
+                        StorageValue::from(block),
+                        0,
+                    ),
+                )
+            });
+            let account = Account::from(info)
+                .with_storage(storage)
+                .with_touched_mark();
+            changes.insert(address, account);
+        }
+        state.commit(changes);
+        let start = Instant::now();
+        state.merge_transitions(BundleRetention::Reverts);
+        merge += start.elapsed();
+    }
+    merge
+}
+
+fn bench_merge_transitions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merge_transitions");
+    for (name, background) in [("sync", false), ("background", true)] {
+        // Time spent on the executing thread before the bundle is needed.
+        group.bench_function(format!("{name}/blocks"), |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let mut state = new_state(background);
+                    let start = Instant::now();
+                    black_box(execute_blocks(&mut state));
+                    total += start.elapsed();
+                    black_box(state.take_bundle());
+                }
+                total
+            })
+        });
+        // Time spent merging on the executing thread.
+        group.bench_function(format!("{name}/merge_only"), |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        let mut state = new_state(background);
+                        let merge = execute_blocks(&mut state);
+                        black_box(state.take_bundle());
+                        merge
+                    })
+                    .sum()
+            })
+        });
+        // Same as the first one including the wait for the merged bundle.
+        group.bench_function(format!("{name}/blocks_and_take_bundle"), |b| {
+            b.iter(|| {
+                let mut state = new_state(background);
+                black_box(execute_blocks(&mut state));
+                black_box(state.take_bundle())
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_merge_transitions);
+criterion_main!(benches);
@@ -1,6 +1,9 @@
 //! Database implementations for `either::Either` type.
 
-use crate::{Database, DatabaseCommit, DatabaseRef};
+use crate::{
+    Database, DatabaseCommit, DatabaseRef, DatabaseStorageIter, DatabaseStorageIterRef,
+    StorageOrder, StorageRange,
+};
 use either::Either;
 use primitives::{Address, HashMap, StorageKey, StorageValue, B256};
 use state::{Account, AccountInfo, Bytecode};
@@ -97,3 +100,41 @@ where
         }
     }
 }
+
+impl<L, R> DatabaseStorageIter for Either<L, R>
+where
+    L: DatabaseStorageIter,
+    R: DatabaseStorageIter<Error = L::Error>,
+{
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        match self {
+            Self::Left(db) => db.storage_range(address, order, start, limit),
+            Self::Right(db) => db.storage_range(address, order, start, limit),
+        }
+    }
+}
+
+impl<L, R> DatabaseStorageIterRef for Either<L, R>
+where
+    L: DatabaseStorageIterRef,
+    R: DatabaseStorageIterRef<Error = L::Error>,
+{
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        match self {
+            Self::Left(db) => db.storage_range_ref(address, order, start, limit),
+            Self::Right(db) => db.storage_range_ref(address, order, start, limit),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! Empty database implementation.
-use crate::{DBErrorMarker, Database, DatabaseRef};
+use crate::{
+    DBErrorMarker, Database, DatabaseRef, DatabaseStorageIter, DatabaseStorageIterRef,
+    StorageOrder, StorageRange,
+};
 use core::{convert::Infallible, error::Error, fmt, marker::PhantomData};
 use primitives::{keccak256, Address, StorageKey, StorageValue, B256};
 use state::{AccountInfo, Bytecode};
@@ -110,6 +113,32 @@ impl<E: DBErrorMarker + Error> DatabaseRef for EmptyDBTyped<E> {
     }
 }
 
+impl<E: DBErrorMarker + Error> DatabaseStorageIter for EmptyDBTyped<E> {
+    #[inline]
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        <Self as DatabaseStorageIterRef>::storage_range_ref(self, address, order, start, limit)
+    }
+}
+
+impl<E: DBErrorMarker + Error> DatabaseStorageIterRef for EmptyDBTyped<E> {
+    #[inline]
+    fn storage_range_ref(
+        &self,
+        _address: Address,
+        _order: StorageOrder,
+        _start: B256,
+        _limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        Ok(StorageRange::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,11 +32,13 @@ pub const BENCH_CALLER_BALANCE: U256 = TEST_BALANCE;
 pub mod async_db;
 pub mod either;
 pub mod empty_db;
+pub mod storage_iter;
 pub mod try_commit;
 
 #[cfg(feature = "asyncdb")]
 pub use async_db::{DatabaseAsync, WrapDatabaseAsync};
 pub use empty_db::{EmptyDB, EmptyDBTyped};
+pub use storage_iter::{DatabaseStorageIter, DatabaseStorageIterRef, StorageOrder, StorageRange};
 pub use try_commit::{ArcUpgradeError, TryDatabaseCommit};
 
 /// Database error marker is needed to implement From conversion for Error type.
@@ -145,6 +147,19 @@ impl<T: DatabaseRef + DatabaseCommit> DatabaseCommit for WrapDatabaseRef<T> {
     }
 }
 
+impl<T: DatabaseStorageIterRef> DatabaseStorageIter for WrapDatabaseRef<T> {
+    #[inline]
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        self.0.storage_range_ref(address, order, start, limit)
+    }
+}
+
 impl<T: DatabaseRef> DatabaseRef for WrapDatabaseRef<T> {
     type Error = T::Error;
 
@@ -172,3 +187,16 @@ impl<T: DatabaseRef> DatabaseRef for WrapDatabaseRef<T> {
         self.0.block_hash_ref(number)
     }
 }
+
+impl<T: DatabaseStorageIterRef> DatabaseStorageIterRef for WrapDatabaseRef<T> {
+    #[inline]
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        self.0.storage_range_ref(address, order, start, limit)
+    }
+}
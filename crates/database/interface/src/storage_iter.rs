@@ -0,0 +1,400 @@
+//! Storage enumeration extension for [`Database`] and [`DatabaseRef`].
+//!
+//! Storage is enumerated page by page. Every slot has a cursor that depends on the requested
+//! [`StorageOrder`]; a page contains the non-zero slots whose cursor is greater than or equal to
+//! the requested start, sorted by cursor, and [`StorageRange::next`] is the cursor of the first slot
+//! that did not fit in the page.
+use crate::{Database, DatabaseRef};
+use core::cmp::Ordering;
+use primitives::{keccak256, Address, StorageKey, StorageValue, B256};
+use std::{boxed::Box, sync::Arc, vec::Vec};
+
+/// Default page size used by [`DatabaseStorageIter::account_storage`].
+pub const STORAGE_PAGE_SIZE: usize = 256;
+
+/// Order in which storage slots are enumerated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageOrder {
+    /// Slots are ordered by `keccak256` of the big-endian key, the order of the storage trie.
+    ///
+    /// This is the order used by `debug_storageRangeAt`.
+    #[default]
+    KeyHash,
+    /// Slots are ordered by the raw big-endian key.
+    RawKey,
+}
+
+impl StorageOrder {
+    /// Returns the cursor of the storage key in this order.
+    #[inline]
+    pub fn cursor(&self, key: StorageKey) -> B256 {
+        let key = B256::from(key);
+        match self {
+            Self::KeyHash => keccak256(key),
+            Self::RawKey => key,
+        }
+    }
+}
+
+/// A page of storage slots of a single account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageRange {
+    /// Non-zero storage slots sorted by their cursor.
+    pub slots: Vec<(StorageKey, StorageValue)>,
+    /// Cursor of the first slot after this page, `None` if there are no more slots.
+    pub next: Option<B256>,
+}
+
+impl StorageRange {
+    /// Builds a page from an unordered set of slots.
+    ///
+    /// Zero values are skipped as they are indistinguishable from missing slots.
+    pub fn from_slots(
+        slots: impl IntoIterator<Item = (StorageKey, StorageValue)>,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Self {
+        let mut slots: Vec<_> = slots
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(key, value)| (order.cursor(key), key, value))
+            .filter(|(cursor, ..)| *cursor >= start)
+            .collect();
+        slots.sort_unstable_by_key(|(cursor, ..)| *cursor);
+
+        let next = slots.get(limit).map(|(cursor, ..)| *cursor);
+        slots.truncate(limit);
+        Self {
+            slots: slots
+                .into_iter()
+                .map(|(_, key, value)| (key, value))
+                .collect(),
+            next,
+        }
+    }
+
+    /// Builds a page from slots held in memory on top of another storage layer.
+    ///
+    /// `overlay` values take precedence over the ones from the base layer, and a zero value in
+    /// `overlay` hides the slot. If `wiped` is true the base layer is ignored, as happens
+    /// after the account got selfdestructed or newly created.
+    ///
+    /// `base` is called with the number of slots it needs to return starting from `start`.
+    pub fn layered<E>(
+        overlay: impl IntoIterator<Item = (StorageKey, StorageValue)>,
+        wiped: bool,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+        base: impl FnOnce(usize) -> Result<StorageRange, E>,
+    ) -> Result<Self, E> {
+        let mut overlay: Vec<_> = overlay
+            .into_iter()
+            .map(|(key, value)| (order.cursor(key), key, value))
+            .filter(|(cursor, ..)| *cursor >= start)
+            .collect();
+        if wiped {
+            return Ok(Self::from_slots(
+                overlay.into_iter().map(|(_, key, value)| (key, value)),
+                order,
+                start,
+                limit,
+            ));
+        }
+        overlay.sort_unstable_by_key(|(cursor, ..)| *cursor);
+
+        // Every overlay slot can hide at most one base slot, so requesting `limit + overlay.len()`
+        // base slots guarantees a full page if the base layer has enough slots.
+        let base = base(limit.saturating_add(overlay.len()))?;
+        // Overlay slots after the end of the base page can't be ordered against base slots
+        // that were not fetched, they are returned in later pages.
+        if let Some(end) = base.next {
+            overlay.retain(|(cursor, ..)| *cursor < end);
+        }
+
+        let mut merged = Vec::with_capacity(base.slots.len() + overlay.len());
+        let mut base_iter = base
+            .slots
+            .into_iter()
+            .map(|(key, value)| (order.cursor(key), key, value))
+            .peekable();
+        let mut overlay_iter = overlay.into_iter().peekable();
+        loop {
+            let next = match (base_iter.peek(), overlay_iter.peek()) {
+                (Some(b), Some(o)) => match b.0.cmp(&o.0) {
+                    Ordering::Less => base_iter.next(),
+                    Ordering::Equal => {
+                        base_iter.next();
+                        overlay_iter.next()
+                    }
+                    Ordering::Greater => overlay_iter.next(),
+                },
+                (Some(_), None) => base_iter.next(),
+                (None, Some(_)) => overlay_iter.next(),
+                (None, None) => break,
+            };
+            if let Some(slot) = next.filter(|(.., value)| !value.is_zero()) {
+                merged.push(slot);
+            }
+        }
+
+        let next = match merged.get(limit) {
+            Some((cursor, ..)) => Some(*cursor),
+            None => base.next,
+        };
+        merged.truncate(limit);
+        Ok(Self {
+            slots: merged
+                .into_iter()
+                .map(|(_, key, value)| (key, value))
+                .collect(),
+            next,
+        })
+    }
+}
+
+/// Extension of [`Database`] that allows enumerating the storage of an account.
+pub trait DatabaseStorageIter: Database {
+    /// Returns up to `limit` non-zero storage slots of `address` whose cursor in `order` is
+    /// greater than or equal to `start`.
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error>;
+
+    /// Returns all non-zero storage slots of `address` sorted by the raw key.
+    fn account_storage(
+        &mut self,
+        address: Address,
+    ) -> Result<Vec<(StorageKey, StorageValue)>, Self::Error> {
+        let mut slots = Vec::new();
+        let mut start = B256::ZERO;
+        loop {
+            let range =
+                self.storage_range(address, StorageOrder::RawKey, start, STORAGE_PAGE_SIZE)?;
+            slots.extend(range.slots);
+            match range.next {
+                Some(next) => start = next,
+                None => return Ok(slots),
+            }
+        }
+    }
+}
+
+/// Extension of [`DatabaseRef`] that allows enumerating the storage of an account.
+///
+/// Contains the same methods as [`DatabaseStorageIter`], but with `&self` receivers instead of `&mut self`.
+pub trait DatabaseStorageIterRef: DatabaseRef {
+    /// Returns up to `limit` non-zero storage slots of `address` whose cursor in `order` is
+    /// greater than or equal to `start`.
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error>;
+
+    /// Returns all non-zero storage slots of `address` sorted by the raw key.
+    fn account_storage_ref(
+        &self,
+        address: Address,
+    ) -> Result<Vec<(StorageKey, StorageValue)>, Self::Error> {
+        let mut slots = Vec::new();
+        let mut start = B256::ZERO;
+        loop {
+            let range =
+                self.storage_range_ref(address, StorageOrder::RawKey, start, STORAGE_PAGE_SIZE)?;
+            slots.extend(range.slots);
+            match range.next {
+                Some(next) => start = next,
+                None => return Ok(slots),
+            }
+        }
+    }
+}
+
+// `auto_impl` can't forward the supertrait error type, so pointer impls are written by hand.
+impl<T: DatabaseStorageIter + ?Sized> DatabaseStorageIter for &mut T {
+    #[inline]
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        (**self).storage_range(address, order, start, limit)
+    }
+}
+
+impl<T: DatabaseStorageIter + ?Sized> DatabaseStorageIter for Box<T> {
+    #[inline]
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        (**self).storage_range(address, order, start, limit)
+    }
+}
+
+impl<T: DatabaseStorageIterRef + ?Sized> DatabaseStorageIterRef for &T {
+    #[inline]
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        (**self).storage_range_ref(address, order, start, limit)
+    }
+}
+
+impl<T: DatabaseStorageIterRef + ?Sized> DatabaseStorageIterRef for Arc<T> {
+    #[inline]
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        (**self).storage_range_ref(address, order, start, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use primitives::U256;
+
+    fn slots(range: impl IntoIterator<Item = u64>) -> Vec<(StorageKey, StorageValue)> {
+        range
+            .into_iter()
+            .map(|i| (StorageKey::from(i), StorageValue::from(i + 1)))
+            .collect()
+    }
+
+    /// Collects all pages of a layered range.
+    fn collect_layered(
+        base: &[(StorageKey, StorageValue)],
+        overlay: &[(StorageKey, StorageValue)],
+        order: StorageOrder,
+        limit: usize,
+    ) -> Vec<(StorageKey, StorageValue)> {
+        let mut all = Vec::new();
+        let mut start = B256::ZERO;
+        loop {
+            let range = StorageRange::layered(
+                overlay.iter().copied(),
+                false,
+                order,
+                start,
+                limit,
+                |base_limit| {
+                    Ok::<_, Infallible>(StorageRange::from_slots(
+                        base.iter().copied(),
+                        order,
+                        start,
+                        base_limit,
+                    ))
+                },
+            )
+            .unwrap();
+            assert!(range.slots.len() <= limit);
+            all.extend(range.slots);
+            match range.next {
+                Some(next) => start = next,
+                None => return all,
+            }
+        }
+    }
+
+    #[test]
+    fn from_slots_paginates_in_raw_order() {
+        let page =
+            StorageRange::from_slots(slots([5, 1, 3, 2, 4]), StorageOrder::RawKey, B256::ZERO, 2);
+        assert_eq!(page.slots, slots([1, 2]));
+        assert_eq!(page.next, Some(B256::from(U256::from(3))));
+
+        let page = StorageRange::from_slots(
+            slots([5, 1, 3, 2, 4]),
+            StorageOrder::RawKey,
+            page.next.unwrap(),
+            10,
+        );
+        assert_eq!(page.slots, slots([3, 4, 5]));
+        assert_eq!(page.next, None);
+    }
+
+    #[test]
+    fn from_slots_orders_by_key_hash_and_skips_zero() {
+        let mut input = slots(0..10);
+        input.push((StorageKey::from(100), StorageValue::ZERO));
+        let page = StorageRange::from_slots(input, StorageOrder::KeyHash, B256::ZERO, 100);
+        assert_eq!(page.slots.len(), 10);
+        assert!(page.slots.windows(2).all(|w| {
+            StorageOrder::KeyHash.cursor(w[0].0) < StorageOrder::KeyHash.cursor(w[1].0)
+        }));
+    }
+
+    #[test]
+    fn layered_overrides_and_hides_base_slots() {
+        let base = slots(0..20);
+        // Override 3, hide 5 and 7, add 30.
+        let overlay = vec![
+            (StorageKey::from(3), StorageValue::from(100)),
+            (StorageKey::from(5), StorageValue::ZERO),
+            (StorageKey::from(7), StorageValue::ZERO),
+            (StorageKey::from(30), StorageValue::from(31)),
+        ];
+        let mut expected: Vec<_> = slots(0..20)
+            .into_iter()
+            .filter(|(key, _)| *key != StorageKey::from(5) && *key != StorageKey::from(7))
+            .map(|(key, value)| {
+                if key == StorageKey::from(3) {
+                    (key, StorageValue::from(100))
+                } else {
+                    (key, value)
+                }
+            })
+            .collect();
+        expected.push((StorageKey::from(30), StorageValue::from(31)));
+
+        for limit in 1..25 {
+            assert_eq!(
+                collect_layered(&base, &overlay, StorageOrder::RawKey, limit),
+                expected
+            );
+            let mut hashed = collect_layered(&base, &overlay, StorageOrder::KeyHash, limit);
+            hashed.sort();
+            assert_eq!(hashed, expected);
+        }
+    }
+
+    #[test]
+    fn layered_wiped_ignores_base() {
+        let range = StorageRange::layered(
+            slots([1, 2]),
+            true,
+            StorageOrder::RawKey,
+            B256::ZERO,
+            10,
+            |_| -> Result<StorageRange, Infallible> { unreachable!("base must not be read") },
+        )
+        .unwrap();
+        assert_eq!(range.slots, slots([1, 2]));
+        assert_eq!(range.next, None);
+    }
+}
@@ -1,7 +1,8 @@
 use core::convert::Infallible;
 use database_interface::{
-    Database, DatabaseCommit, DatabaseRef, EmptyDB, BENCH_CALLER, BENCH_CALLER_BALANCE,
-    BENCH_TARGET, BENCH_TARGET_BALANCE,
+    Database, DatabaseCommit, DatabaseRef, DatabaseStorageIter, DatabaseStorageIterRef, EmptyDB,
+    StorageOrder, StorageRange, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET,
+    BENCH_TARGET_BALANCE,
 };
 use primitives::{
     hash_map::Entry, Address, HashMap, Log, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
@@ -361,6 +362,47 @@ impl<ExtDB: DatabaseRef> DatabaseRef for CacheDB<ExtDB> {
     }
 }
 
+impl<ExtDB: DatabaseStorageIterRef> DatabaseStorageIter for CacheDB<ExtDB> {
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        self.storage_range_ref(address, order, start, limit)
+    }
+}
+
+impl<ExtDB: DatabaseStorageIterRef> DatabaseStorageIterRef for CacheDB<ExtDB> {
+    /// Enumerates cached storage slots layered on top of the storage of the underlying database.
+    ///
+    /// Storage of the underlying database is skipped if the account storage was cleared.
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        let Some(account) = self.cache.accounts.get(&address) else {
+            return self.db.storage_range_ref(address, order, start, limit);
+        };
+        let wiped = matches!(
+            account.account_state,
+            AccountState::StorageCleared | AccountState::NotExisting
+        );
+        StorageRange::layered(
+            account.storage.iter().map(|(k, v)| (*k, *v)),
+            wiped,
+            order,
+            start,
+            limit,
+            |limit| self.db.storage_range_ref(address, order, start, limit),
+        )
+    }
+}
+
 /// Database account representation.
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -458,6 +500,18 @@ impl BenchmarkDB {
     }
 }
 
+impl DatabaseStorageIter for BenchmarkDB {
+    fn storage_range(
+        &mut self,
+        _address: Address,
+        _order: StorageOrder,
+        _start: B256,
+        _limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        Ok(StorageRange::default())
+    }
+}
+
 impl Database for BenchmarkDB {
     type Error = Infallible;
     /// Get basic account information.
@@ -507,8 +561,8 @@ impl Database for BenchmarkDB {
 
 #[cfg(test)]
 mod tests {
-    use super::{CacheDB, EmptyDB};
-    use database_interface::Database;
+    use super::{AccountState, CacheDB, EmptyDB};
+    use database_interface::{Database, DatabaseStorageIter, DatabaseStorageIterRef};
     use primitives::{Address, HashMap, StorageKey, StorageValue};
     use state::AccountInfo;
 
@@ -564,6 +618,37 @@ mod tests {
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
 
+    #[test]
+    fn test_account_storage() {
+        let account = Address::with_last_byte(42);
+        let mut init_state = CacheDB::new(EmptyDB::default());
+        init_state.insert_account_info(account, AccountInfo::default());
+        for i in 1..=3 {
+            init_state
+                .insert_account_storage(account, StorageKey::from(i), StorageValue::from(i))
+                .unwrap();
+        }
+
+        let mut new_state = CacheDB::new(init_state);
+        new_state
+            .insert_account_storage(account, StorageKey::from(2), StorageValue::ZERO)
+            .unwrap();
+        new_state
+            .insert_account_storage(account, StorageKey::from(4), StorageValue::from(4))
+            .unwrap();
+        let expected = [1, 3, 4].map(|i| (StorageKey::from(i), StorageValue::from(i)));
+        assert_eq!(new_state.account_storage(account).unwrap(), expected);
+
+        new_state
+            .load_account(account)
+            .unwrap()
+            .update_account_state(AccountState::StorageCleared);
+        assert_eq!(
+            new_state.account_storage_ref(account).unwrap(),
+            [(StorageKey::from(4), StorageValue::from(4))]
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialize_deserialize_cachedb() {
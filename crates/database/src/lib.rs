@@ -11,15 +11,23 @@ pub use database_interface::*;
 
 /// In-memory database implementations.
 pub mod in_memory_db;
+/// Database metrics and access recording.
+#[cfg(feature = "std")]
+pub mod metrics;
 /// State management and tracking.
 pub mod states;
+/// Execution witness recording and stateless re-execution.
+pub mod witness;
 
 #[cfg(feature = "alloydb")]
 pub use alloydb::{AlloyDB, BlockId, DBTransportError};
 
 pub use in_memory_db::*;
+#[cfg(feature = "std")]
+pub use metrics::{AccessKey, AccessWitness, CallMetrics, DatabaseMetrics, MetricsDB};
 pub use states::{
-    AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox,
-    OriginalValuesKnown, PlainAccount, RevertToSlot, State, StateBuilder, StateDBBox,
-    StorageWithOriginalValues, TransitionAccount, TransitionState,
+    AccountRevert, AccountStatus, BundleAccount, BundleDecodeError, BundleEncodeError, BundleState,
+    BundleView, CacheState, DBBox, OriginalValuesKnown, PlainAccount, RevertToSlot, State,
+    StateBuilder, StateDBBox, StorageWithOriginalValues, TransitionAccount, TransitionState,
 };
+pub use witness::{ExecutionWitness, RecordingDB, WitnessDB, WitnessError};
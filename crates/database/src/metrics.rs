@@ -0,0 +1,463 @@
+//! Database wrapper that collects call metrics and records accessed keys.
+use database_interface::{Database, DatabaseCommit, DatabaseRef};
+use primitives::{Address, HashMap, StorageKey, StorageValue, B256};
+use state::{Account, AccountInfo, Bytecode};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Mutex,
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+/// Count and timing of calls to a single database method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallMetrics {
+    /// Number of calls, including the failed ones.
+    pub calls: u64,
+    /// Number of calls that returned an error.
+    pub errors: u64,
+    /// Total time spent in the wrapped database.
+    pub total_time: Duration,
+    /// Longest single call.
+    pub max_time: Duration,
+}
+
+impl CallMetrics {
+    /// Returns the average time of a call, or zero if there were no calls.
+    pub fn average_time(&self) -> Duration {
+        if self.calls == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total_time.as_nanos() / self.calls as u128) as u64)
+    }
+
+    fn record(&mut self, elapsed: Duration, is_err: bool) {
+        self.calls += 1;
+        self.errors += is_err as u64;
+        self.total_time += elapsed;
+        self.max_time = self.max_time.max(elapsed);
+    }
+}
+
+/// Metrics of all [`Database`] methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DatabaseMetrics {
+    /// Metrics of [`Database::basic`].
+    pub basic: CallMetrics,
+    /// Metrics of [`Database::code_by_hash`].
+    pub code_by_hash: CallMetrics,
+    /// Metrics of [`Database::storage`].
+    pub storage: CallMetrics,
+    /// Metrics of [`Database::block_hash`].
+    pub block_hash: CallMetrics,
+}
+
+impl DatabaseMetrics {
+    /// Returns the total number of calls.
+    pub fn total_calls(&self) -> u64 {
+        self.basic.calls + self.code_by_hash.calls + self.storage.calls + self.block_hash.calls
+    }
+
+    /// Returns the total time spent in the wrapped database.
+    pub fn total_time(&self) -> Duration {
+        self.basic.total_time
+            + self.code_by_hash.total_time
+            + self.storage.total_time
+            + self.block_hash.total_time
+    }
+}
+
+/// A single key read from the database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccessKey {
+    /// Account read with [`Database::basic`].
+    Account(Address),
+    /// Bytecode read with [`Database::code_by_hash`].
+    Code(B256),
+    /// Storage slot read with [`Database::storage`].
+    Storage(Address, StorageKey),
+    /// Block hash read with [`Database::block_hash`].
+    BlockHash(u64),
+}
+
+/// Set of keys that were read from the database.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessWitness {
+    /// Accounts that were read.
+    pub accounts: BTreeSet<Address>,
+    /// Bytecode hashes that were read.
+    pub codes: BTreeSet<B256>,
+    /// Storage slots that were read, grouped by account.
+    pub storage: BTreeMap<Address, BTreeSet<StorageKey>>,
+    /// Block numbers whose hashes were read.
+    pub block_hashes: BTreeSet<u64>,
+}
+
+impl AccessWitness {
+    /// Returns `true` if no key was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+            && self.codes.is_empty()
+            && self.storage.is_empty()
+            && self.block_hashes.is_empty()
+    }
+
+    /// Returns the number of recorded keys.
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+            + self.codes.len()
+            + self.storage.values().map(BTreeSet::len).sum::<usize>()
+            + self.block_hashes.len()
+    }
+
+    /// Records a key.
+    pub fn insert(&mut self, key: AccessKey) {
+        match key {
+            AccessKey::Account(address) => {
+                self.accounts.insert(address);
+            }
+            AccessKey::Code(hash) => {
+                self.codes.insert(hash);
+            }
+            AccessKey::Storage(address, index) => {
+                self.storage.entry(address).or_default().insert(index);
+            }
+            AccessKey::BlockHash(number) => {
+                self.block_hashes.insert(number);
+            }
+        }
+    }
+
+    /// Merges keys of another witness into this one.
+    pub fn extend(&mut self, other: AccessWitness) {
+        self.accounts.extend(other.accounts);
+        self.codes.extend(other.codes);
+        for (address, slots) in other.storage {
+            self.storage.entry(address).or_default().extend(slots);
+        }
+        self.block_hashes.extend(other.block_hashes);
+    }
+
+    /// Returns the recorded keys as a prefetch list.
+    ///
+    /// Accounts are listed before their storage slots, followed by bytecodes and block hashes.
+    pub fn prefetch_list(&self) -> Vec<AccessKey> {
+        let mut list = Vec::with_capacity(self.len());
+        for address in &self.accounts {
+            list.push(AccessKey::Account(*address));
+            if let Some(slots) = self.storage.get(address) {
+                list.extend(
+                    slots
+                        .iter()
+                        .map(|index| AccessKey::Storage(*address, *index)),
+                );
+            }
+        }
+        // Storage of accounts that were not read with `basic`.
+        for (address, slots) in &self.storage {
+            if !self.accounts.contains(address) {
+                list.extend(
+                    slots
+                        .iter()
+                        .map(|index| AccessKey::Storage(*address, *index)),
+                );
+            }
+        }
+        list.extend(self.codes.iter().copied().map(AccessKey::Code));
+        list.extend(self.block_hashes.iter().copied().map(AccessKey::BlockHash));
+        list
+    }
+
+    /// Reads every recorded key from `db`, warming up its caches.
+    pub fn prefetch<DB: Database>(&self, db: &mut DB) -> Result<(), DB::Error> {
+        for key in self.prefetch_list() {
+            match key {
+                AccessKey::Account(address) => {
+                    db.basic(address)?;
+                }
+                AccessKey::Code(hash) => {
+                    db.code_by_hash(hash)?;
+                }
+                AccessKey::Storage(address, index) => {
+                    db.storage(address, index)?;
+                }
+                AccessKey::BlockHash(number) => {
+                    db.block_hash(number)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<AccessKey> for AccessWitness {
+    fn from_iter<T: IntoIterator<Item = AccessKey>>(iter: T) -> Self {
+        let mut witness = Self::default();
+        for key in iter {
+            witness.insert(key);
+        }
+        witness
+    }
+}
+
+/// Metrics and accessed keys collected by [`MetricsDB`].
+#[derive(Clone, Debug, Default)]
+struct Recorded {
+    metrics: DatabaseMetrics,
+    witness: AccessWitness,
+}
+
+impl Recorded {
+    /// Records a call that read `key` and took `elapsed` time.
+    ///
+    /// Keys of failed calls are not added to the witness.
+    fn record(&mut self, key: AccessKey, elapsed: Duration, is_err: bool) {
+        let metrics = match key {
+            AccessKey::Account(_) => &mut self.metrics.basic,
+            AccessKey::Code(_) => &mut self.metrics.code_by_hash,
+            AccessKey::Storage(..) => &mut self.metrics.storage,
+            AccessKey::BlockHash(_) => &mut self.metrics.block_hash,
+        };
+        metrics.record(elapsed, is_err);
+        if !is_err {
+            self.witness.insert(key);
+        }
+    }
+}
+
+/// Database wrapper that counts and times calls to the wrapped database and records every key
+/// that was read.
+///
+/// Both [`Database`] and [`DatabaseRef`] are implemented, so it can be placed under a cache,
+/// e.g. `CacheDB<MetricsDB<DB>>` measures only the calls that missed the cache.
+#[derive(Debug, Default)]
+pub struct MetricsDB<DB> {
+    db: DB,
+    recorded: Mutex<Recorded>,
+}
+
+impl<DB> MetricsDB<DB> {
+    /// Wraps the database.
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            recorded: Mutex::default(),
+        }
+    }
+
+    /// Returns a reference to the wrapped database.
+    pub fn inner(&self) -> &DB {
+        &self.db
+    }
+
+    /// Returns a mutable reference to the wrapped database.
+    pub fn inner_mut(&mut self) -> &mut DB {
+        &mut self.db
+    }
+
+    /// Consumes the wrapper and returns the wrapped database.
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+
+    /// Returns a snapshot of the collected metrics.
+    pub fn metrics(&self) -> DatabaseMetrics {
+        self.recorded().metrics
+    }
+
+    /// Returns a snapshot of the keys read so far.
+    pub fn witness(&self) -> AccessWitness {
+        self.recorded().witness.clone()
+    }
+
+    /// Takes the keys read so far, leaving an empty witness in place.
+    pub fn take_witness(&mut self) -> AccessWitness {
+        core::mem::take(&mut self.recorded_mut().witness)
+    }
+
+    /// Clears collected metrics and accessed keys.
+    pub fn reset(&mut self) {
+        *self.recorded_mut() = Recorded::default();
+    }
+
+    fn recorded(&self) -> std::sync::MutexGuard<'_, Recorded> {
+        self.recorded.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn recorded_mut(&mut self) -> &mut Recorded {
+        self.recorded.get_mut().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<DB: Database> Database for MetricsDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.basic(address);
+        self.recorded_mut().record(
+            AccessKey::Account(address),
+            start.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.code_by_hash(code_hash);
+        self.recorded_mut()
+            .record(AccessKey::Code(code_hash), start.elapsed(), result.is_err());
+        result
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.storage(address, index);
+        self.recorded_mut().record(
+            AccessKey::Storage(address, index),
+            start.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.block_hash(number);
+        self.recorded_mut().record(
+            AccessKey::BlockHash(number),
+            start.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for MetricsDB<DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.basic_ref(address);
+        self.recorded().record(
+            AccessKey::Account(address),
+            start.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.code_by_hash_ref(code_hash);
+        self.recorded()
+            .record(AccessKey::Code(code_hash), start.elapsed(), result.is_err());
+        result
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.storage_ref(address, index);
+        self.recorded().record(
+            AccessKey::Storage(address, index),
+            start.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        let start = Instant::now();
+        let result = self.db.block_hash_ref(number);
+        self.recorded().record(
+            AccessKey::BlockHash(number),
+            start.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for MetricsDB<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CacheDB, EmptyDB, InMemoryDB};
+    use primitives::U256;
+
+    #[test]
+    fn counts_calls_and_records_keys() {
+        let address = Address::with_last_byte(1);
+        let mut inner = InMemoryDB::default();
+        inner.insert_account_info(address, AccountInfo::from_balance(U256::from(10)));
+
+        let mut db = MetricsDB::new(inner);
+        db.basic(address).unwrap();
+        db.basic(address).unwrap();
+        db.storage(address, StorageKey::from(7)).unwrap();
+        db.block_hash(3).unwrap();
+
+        let metrics = db.metrics();
+        assert_eq!(metrics.basic.calls, 2);
+        assert_eq!(metrics.storage.calls, 1);
+        assert_eq!(metrics.code_by_hash.calls, 0);
+        assert_eq!(metrics.block_hash.calls, 1);
+        assert_eq!(metrics.total_calls(), 4);
+
+        let witness = db.witness();
+        assert_eq!(
+            witness.prefetch_list(),
+            vec![
+                AccessKey::Account(address),
+                AccessKey::Storage(address, StorageKey::from(7)),
+                AccessKey::BlockHash(3),
+            ]
+        );
+
+        db.reset();
+        assert_eq!(db.metrics(), DatabaseMetrics::default());
+        assert!(db.witness().is_empty());
+    }
+
+    #[test]
+    fn records_only_cache_misses() {
+        let address = Address::with_last_byte(1);
+        let mut cache = CacheDB::new(MetricsDB::new(EmptyDB::default()));
+        for _ in 0..3 {
+            cache.basic(address).unwrap();
+            cache.storage(address, StorageKey::from(1)).unwrap();
+        }
+        assert_eq!(cache.db.metrics().basic.calls, 1);
+        assert_eq!(cache.db.witness().len(), 1);
+    }
+
+    #[test]
+    fn prefetch_replays_witness() {
+        let witness = AccessWitness::from_iter([
+            AccessKey::Storage(Address::with_last_byte(2), StorageKey::from(1)),
+            AccessKey::Account(Address::with_last_byte(1)),
+            AccessKey::Code(B256::with_last_byte(5)),
+            AccessKey::BlockHash(10),
+        ]);
+
+        let mut db = MetricsDB::new(EmptyDB::default());
+        witness.prefetch(&mut db).unwrap();
+        assert_eq!(db.take_witness(), witness);
+        assert!(db.witness().is_empty());
+        assert_eq!(db.metrics().total_calls(), 4);
+    }
+}
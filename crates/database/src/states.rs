@@ -2,10 +2,17 @@
 
 /// Account status tracking.
 pub mod account_status;
+/// Background merging of transitions.
+#[cfg(feature = "std")]
+pub mod background_merge;
 /// Bundle account representation.
 pub mod bundle_account;
+/// Binary encoding of bundle state.
+pub mod bundle_codec;
 /// Bundle state management.
 pub mod bundle_state;
+/// Bundle state layered over a database.
+pub mod bundle_view;
 /// Cache state implementation.
 pub mod cache;
 /// Cache account representation.
@@ -27,8 +34,12 @@ pub mod transition_state;
 
 /// Account status for Block and Bundle states.
 pub use account_status::AccountStatus;
+#[cfg(feature = "std")]
+pub use background_merge::BackgroundMerge;
 pub use bundle_account::BundleAccount;
+pub use bundle_codec::{BundleDecodeError, BundleEncodeError};
 pub use bundle_state::{BundleBuilder, BundleState, OriginalValuesKnown};
+pub use bundle_view::BundleView;
 pub use cache::CacheState;
 pub use cache_account::CacheAccount;
 pub use changes::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset};
@@ -0,0 +1,123 @@
+//! Merging of transitions into a [`BundleState`] on a worker thread.
+use super::{bundle_state::BundleRetention, BundleState, TransitionState};
+use std::{
+    io,
+    sync::mpsc::{self, Sender, SyncSender},
+    thread::{self, JoinHandle},
+};
+
+/// Merges [`TransitionState`] batches into a [`BundleState`] on a worker thread.
+///
+/// The worker is started lazily on the first [`merge`](Self::merge) and owns the bundle until
+/// [`join`](Self::join) is called, so the executing thread only pays for handing transitions
+/// over. Batches are applied in the order they were sent, which makes the final bundle and its
+/// reverts identical to the ones created by merging synchronously.
+#[derive(Debug, Default)]
+pub struct BackgroundMerge {
+    worker: Option<Worker>,
+    /// Estimated size of the bundle once all sent transitions are merged.
+    size_hint: usize,
+}
+
+#[derive(Debug)]
+struct Worker {
+    sender: Sender<(TransitionState, BundleRetention)>,
+    handle: JoinHandle<BundleState>,
+}
+
+impl Worker {
+    /// Spawns the worker thread, which merges into the bundle sent on the returned sender.
+    fn spawn() -> io::Result<(Self, SyncSender<BundleState>)> {
+        let (sender, receiver) = mpsc::channel::<(TransitionState, BundleRetention)>();
+        // The bundle is sent only once the thread is spawned, so it is not lost if spawning fails.
+        let (bundle_sender, bundle_receiver) = mpsc::sync_channel::<BundleState>(1);
+        let handle = thread::Builder::new()
+            .name("revm-transition-merge".into())
+            .spawn(move || {
+                let mut bundle = bundle_receiver.recv().unwrap_or_default();
+                for (transitions, retention) in receiver {
+                    bundle.apply_transitions_and_create_reverts(transitions, retention);
+                }
+                bundle
+            })?;
+        Ok((Self { sender, handle }, bundle_sender))
+    }
+}
+
+impl BackgroundMerge {
+    /// Creates a new background merge, the worker thread is not started yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the worker thread is running and owns the bundle.
+    pub fn is_running(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    /// Returns the estimated [`BundleState::size_hint`] of the bundle owned by the worker once all
+    /// sent transitions are merged.
+    ///
+    /// Every transition is assumed to add a new account and, if reverts are retained, a new
+    /// revert, so the estimate is an upper bound.
+    pub fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+
+    /// Hands transitions over to the worker thread.
+    ///
+    /// If the worker is not running it is started with the bundle returned by `bundle`. If the
+    /// thread can't be spawned, `bundle` is not called and the transitions are returned with the
+    /// retention so that they can be merged on the calling thread.
+    pub fn merge(
+        &mut self,
+        bundle: impl FnOnce() -> BundleState,
+        transitions: TransitionState,
+        retention: BundleRetention,
+    ) -> Result<(), (TransitionState, BundleRetention)> {
+        let worker = match &mut self.worker {
+            Some(worker) => worker,
+            None => {
+                let Ok((worker, bundle_sender)) = Worker::spawn() else {
+                    return Err((transitions, retention));
+                };
+                let bundle = bundle();
+                self.size_hint = bundle.size_hint();
+                // The worker waits for the bundle, so the buffered send does not fail.
+                let _ = bundle_sender.send(bundle);
+                self.worker.insert(worker)
+            }
+        };
+
+        let transitions_size = transitions
+            .transitions
+            .values()
+            .map(|transition| 1 + transition.storage.len())
+            .sum::<usize>();
+        self.size_hint += transitions_size * (1 + retention.includes_reverts() as usize);
+
+        // Send can fail only if the worker panicked, the panic is propagated on join.
+        let _ = worker.sender.send((transitions, retention));
+        Ok(())
+    }
+
+    /// Waits for all sent transitions to be merged and returns the bundle.
+    ///
+    /// Returns `None` if the worker was not running. The next [`merge`](Self::merge) starts a new
+    /// worker.
+    ///
+    /// # Panics
+    ///
+    /// Propagates the panic of the worker thread.
+    pub fn join(&mut self) -> Option<BundleState> {
+        let Worker { sender, handle } = self.worker.take()?;
+        self.size_hint = 0;
+        // Closing the channel stops the worker after the last batch.
+        drop(sender);
+        Some(
+            handle
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e)),
+        )
+    }
+}
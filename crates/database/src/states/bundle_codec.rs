@@ -0,0 +1,943 @@
+//! Compact, versioned binary encoding of [`BundleState`].
+//!
+//! The encoding is deterministic: accounts, storage slots and contracts are written in sorted
+//! order, so the same bundle always produces the same bytes.
+//!
+//! # Layout (version 1)
+//!
+//! ```text
+//! magic "RVMB" | version u8
+//! code table   : count, [code_hash B256 | kind u8 | len | bytes | in_contracts u8]*
+//! state        : count, [address | original info | present info | status u8 | storage]*
+//! reverts      : count, [count, [address | account revert]*]*
+//! state_size   | reverts_size
+//! ```
+//!
+//! Integers (lengths, nonces and sizes) are LEB128 varints and `U256` values are written as a
+//! length byte followed by the trimmed big-endian bytes.
+//!
+//! Bytecode is stored once in the code table and referenced by hash from every
+//! `AccountInfo` that carries it, so contracts shared between the present state, original
+//! state and reverts are only encoded once.
+use super::{
+    reverts::{AccountInfoRevert, Reverts},
+    AccountRevert, AccountStatus, BundleAccount, BundleState, RevertToSlot, StorageSlot,
+};
+use bytecode::{eip7702::Eip7702Bytecode, Bytecode, BytecodeDecodeError};
+use core::fmt;
+use primitives::{Address, Bytes, HashMap, StorageKey, B256, U256};
+use state::AccountInfo;
+use std::{collections::BTreeMap, vec::Vec};
+
+/// Magic bytes that prefix every encoded [`BundleState`].
+pub const BUNDLE_CODEC_MAGIC: [u8; 4] = *b"RVMB";
+
+/// Current version of the [`BundleState`] binary encoding.
+pub const BUNDLE_CODEC_VERSION: u8 = 1;
+
+/// Legacy bytecode kind in the code table.
+const CODE_KIND_LEGACY: u8 = 0;
+/// EIP-7702 bytecode kind in the code table.
+const CODE_KIND_EIP7702: u8 = 1;
+
+/// Account info has no code attached.
+const CODE_REF_NONE: u8 = 0;
+/// Account info code is found in the code table under its code hash.
+const CODE_REF_TABLE: u8 = 1;
+/// Account info code is [`Bytecode::default`].
+const CODE_REF_DEFAULT: u8 = 2;
+
+/// Errors that can occur while encoding a [`BundleState`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BundleEncodeError {
+    /// Code does not hash to the code hash it is stored under, so the encoding would not decode.
+    CodeHashMismatch {
+        /// Hash the code is stored under.
+        expected: B256,
+        /// Hash of the code.
+        got: B256,
+    },
+}
+
+impl core::error::Error for BundleEncodeError {}
+
+impl fmt::Display for BundleEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CodeHashMismatch { expected, got } => {
+                write!(f, "code stored under {expected} hashes to {got}")
+            }
+        }
+    }
+}
+
+/// Errors that can occur while decoding an encoded [`BundleState`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BundleDecodeError {
+    /// Input ended before the bundle was fully decoded.
+    UnexpectedEof,
+    /// Input does not start with [`BUNDLE_CODEC_MAGIC`].
+    InvalidMagic,
+    /// Encoding version is not supported by this decoder.
+    UnsupportedVersion(u8),
+    /// Varint does not fit into `u64`.
+    VarintOverflow,
+    /// Encoded `U256` is longer than 32 bytes.
+    InvalidU256Length(u8),
+    /// Unknown tag was found for the given field.
+    InvalidTag {
+        /// Name of the field that contains the tag.
+        field: &'static str,
+        /// Value of the tag.
+        tag: u8,
+    },
+    /// Account references code that is not present in the code table.
+    MissingCode(B256),
+    /// Code in the code table does not hash to its key.
+    CodeHashMismatch {
+        /// Hash the code is stored under.
+        expected: B256,
+        /// Hash of the code.
+        got: B256,
+    },
+    /// Bytecode in the code table could not be decoded.
+    Bytecode(BytecodeDecodeError),
+    /// Input contains bytes after the end of the bundle.
+    TrailingBytes(usize),
+}
+
+impl From<BytecodeDecodeError> for BundleDecodeError {
+    fn from(error: BytecodeDecodeError) -> Self {
+        Self::Bytecode(error)
+    }
+}
+
+impl core::error::Error for BundleDecodeError {}
+
+impl fmt::Display for BundleDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => f.write_str("unexpected end of input"),
+            Self::InvalidMagic => f.write_str("invalid bundle magic bytes"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported bundle encoding version {version}")
+            }
+            Self::VarintOverflow => f.write_str("varint overflows u64"),
+            Self::InvalidU256Length(len) => write!(f, "invalid U256 length {len}"),
+            Self::InvalidTag { field, tag } => write!(f, "invalid tag {tag} for {field}"),
+            Self::MissingCode(hash) => write!(f, "code {hash} is missing from the code table"),
+            Self::CodeHashMismatch { expected, got } => {
+                write!(f, "code stored under {expected} hashes to {got}")
+            }
+            Self::Bytecode(e) => fmt::Display::fmt(e, f),
+            Self::TrailingBytes(len) => write!(f, "{len} trailing bytes after bundle"),
+        }
+    }
+}
+
+impl BundleState {
+    /// Encodes the bundle into the compact binary format described in [`bundle_codec`](super::bundle_codec).
+    ///
+    /// Fails if a code does not hash to the code hash it is stored under.
+    pub fn encode(&self) -> Result<Vec<u8>, BundleEncodeError> {
+        let mut out = Vec::with_capacity(self.size_hint() * 64);
+        self.encode_to(&mut out)?;
+        Ok(out)
+    }
+
+    /// Appends the compact binary encoding of the bundle to `out`.
+    ///
+    /// Fails if a code does not hash to the code hash it is stored under, `out` is left unchanged
+    /// in that case.
+    pub fn encode_to(&self, out: &mut Vec<u8>) -> Result<(), BundleEncodeError> {
+        // Contracts of the bundle take precedence over the code attached to account infos.
+        let mut code_table: BTreeMap<B256, (&Bytecode, bool)> = self
+            .contracts
+            .iter()
+            .map(|(hash, code)| (*hash, (code, true)))
+            .collect();
+        let infos = self
+            .state
+            .values()
+            .flat_map(|account| [account.original_info.as_ref(), account.info.as_ref()])
+            .chain(
+                self.reverts
+                    .iter()
+                    .flatten()
+                    .map(|(_, revert)| match &revert.account {
+                        AccountInfoRevert::RevertTo(info) => Some(info),
+                        _ => None,
+                    }),
+            )
+            .flatten();
+        for info in infos {
+            if let Some(code) = info.code.as_ref().filter(|c| **c != Bytecode::default()) {
+                code_table.entry(info.code_hash).or_insert((code, false));
+            }
+        }
+        for (&expected, (code, _)) in &code_table {
+            let got = code.hash_slow();
+            if got != expected {
+                return Err(BundleEncodeError::CodeHashMismatch { expected, got });
+            }
+        }
+
+        let mut encoder = Encoder { out };
+        encoder.out.extend_from_slice(&BUNDLE_CODEC_MAGIC);
+        encoder.out.push(BUNDLE_CODEC_VERSION);
+
+        encoder.varint(code_table.len() as u64);
+        for (hash, (code, in_contracts)) in code_table {
+            encoder.out.extend_from_slice(hash.as_slice());
+            let (kind, bytes) = match code {
+                Bytecode::LegacyAnalyzed(legacy) => {
+                    (CODE_KIND_LEGACY, legacy.original_byte_slice())
+                }
+                Bytecode::Eip7702(eip7702) => (CODE_KIND_EIP7702, eip7702.raw().as_ref()),
+            };
+            encoder.out.push(kind);
+            encoder.bytes(bytes);
+            encoder.out.push(in_contracts as u8);
+        }
+
+        let mut state: Vec<_> = self.state.iter().collect();
+        state.sort_unstable_by_key(|(address, _)| **address);
+        encoder.varint(state.len() as u64);
+        for (address, account) in state {
+            encoder.out.extend_from_slice(address.as_slice());
+            encoder.account_info(account.original_info.as_ref());
+            encoder.account_info(account.info.as_ref());
+            encoder.out.push(account_status_to_u8(account.status));
+
+            let mut storage: Vec<_> = account.storage.iter().collect();
+            storage.sort_unstable_by_key(|(key, _)| **key);
+            encoder.varint(storage.len() as u64);
+            for (key, slot) in storage {
+                encoder.u256(*key);
+                encoder.u256(slot.previous_or_original_value);
+                encoder.u256(slot.present_value);
+            }
+        }
+
+        encoder.varint(self.reverts.len() as u64);
+        for transition in self.reverts.iter() {
+            encoder.varint(transition.len() as u64);
+            for (address, revert) in transition {
+                encoder.out.extend_from_slice(address.as_slice());
+                encoder.account_revert(revert);
+            }
+        }
+
+        encoder.varint(self.state_size as u64);
+        encoder.varint(self.reverts_size as u64);
+        Ok(())
+    }
+
+    /// Decodes a bundle previously encoded with [`BundleState::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, BundleDecodeError> {
+        let mut decoder = Decoder {
+            bytes,
+            pos: 0,
+            code_table: HashMap::default(),
+        };
+        if decoder.take(BUNDLE_CODEC_MAGIC.len())? != BUNDLE_CODEC_MAGIC {
+            return Err(BundleDecodeError::InvalidMagic);
+        }
+        let version = decoder.u8()?;
+        if version != BUNDLE_CODEC_VERSION {
+            return Err(BundleDecodeError::UnsupportedVersion(version));
+        }
+
+        let count = decoder.len()?;
+        let mut code_table = HashMap::default();
+        let mut contracts = HashMap::default();
+        for _ in 0..count {
+            let hash = decoder.b256()?;
+            let kind = decoder.u8()?;
+            let raw = Bytes::copy_from_slice(decoder.bytes()?);
+            let code = match kind {
+                CODE_KIND_LEGACY => Bytecode::new_legacy(raw),
+                CODE_KIND_EIP7702 => Bytecode::Eip7702(
+                    Eip7702Bytecode::new_raw(raw).map_err(BytecodeDecodeError::from)?,
+                ),
+                tag => {
+                    return Err(BundleDecodeError::InvalidTag {
+                        field: "code kind",
+                        tag,
+                    })
+                }
+            };
+            let code_hash = code.hash_slow();
+            if code_hash != hash {
+                return Err(BundleDecodeError::CodeHashMismatch {
+                    expected: hash,
+                    got: code_hash,
+                });
+            }
+            if decoder.bool("contract flag")? {
+                contracts.insert(hash, code.clone());
+            }
+            code_table.insert(hash, code);
+        }
+        decoder.code_table = code_table;
+
+        let count = decoder.len()?;
+        let mut state = HashMap::default();
+        for _ in 0..count {
+            let address = decoder.address()?;
+            let original_info = decoder.account_info()?;
+            let info = decoder.account_info()?;
+            let status = decoder.account_status()?;
+
+            let slots = decoder.len()?;
+            let mut storage = HashMap::default();
+            for _ in 0..slots {
+                let key = decoder.u256()?;
+                let previous_or_original_value = decoder.u256()?;
+                let present_value = decoder.u256()?;
+                storage.insert(
+                    key,
+                    StorageSlot::new_changed(previous_or_original_value, present_value),
+                );
+            }
+            state.insert(
+                address,
+                BundleAccount::new(original_info, info, storage, status),
+            );
+        }
+
+        let transitions = decoder.len()?;
+        let mut reverts = Vec::with_capacity(transitions);
+        for _ in 0..transitions {
+            let count = decoder.len()?;
+            let mut transition = Vec::with_capacity(count);
+            for _ in 0..count {
+                let address = decoder.address()?;
+                transition.push((address, decoder.account_revert()?));
+            }
+            reverts.push(transition);
+        }
+
+        let state_size = decoder.varint()? as usize;
+        let reverts_size = decoder.varint()? as usize;
+
+        let remaining = decoder.bytes.len() - decoder.pos;
+        if remaining != 0 {
+            return Err(BundleDecodeError::TrailingBytes(remaining));
+        }
+
+        Ok(BundleState {
+            state,
+            contracts,
+            reverts: Reverts::new(reverts),
+            state_size,
+            reverts_size,
+        })
+    }
+}
+
+fn account_status_to_u8(status: AccountStatus) -> u8 {
+    match status {
+        AccountStatus::LoadedNotExisting => 0,
+        AccountStatus::Loaded => 1,
+        AccountStatus::LoadedEmptyEIP161 => 2,
+        AccountStatus::InMemoryChange => 3,
+        AccountStatus::Changed => 4,
+        AccountStatus::Destroyed => 5,
+        AccountStatus::DestroyedChanged => 6,
+        AccountStatus::DestroyedAgain => 7,
+    }
+}
+
+/// Writes primitive values into the output buffer.
+struct Encoder<'a> {
+    out: &'a mut Vec<u8>,
+}
+
+impl Encoder<'_> {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.out.push(value as u8);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.varint(bytes.len() as u64);
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn u256(&mut self, value: U256) {
+        let bytes = value.to_be_bytes::<32>();
+        let skip = (value.leading_zeros() / 8).min(32);
+        self.out.push((32 - skip) as u8);
+        self.out.extend_from_slice(&bytes[skip..]);
+    }
+
+    fn account_info(&mut self, info: Option<&AccountInfo>) {
+        let Some(info) = info else {
+            self.out.push(0);
+            return;
+        };
+        self.out.push(1);
+        self.u256(info.balance);
+        self.varint(info.nonce);
+        self.out.extend_from_slice(info.code_hash.as_slice());
+        let code_ref = match &info.code {
+            None => CODE_REF_NONE,
+            Some(code) if *code == Bytecode::default() => CODE_REF_DEFAULT,
+            Some(_) => CODE_REF_TABLE,
+        };
+        self.out.push(code_ref);
+    }
+
+    fn account_revert(&mut self, revert: &AccountRevert) {
+        match &revert.account {
+            AccountInfoRevert::DoNothing => self.out.push(0),
+            AccountInfoRevert::DeleteIt => self.out.push(1),
+            AccountInfoRevert::RevertTo(info) => {
+                self.out.push(2);
+                self.account_info(Some(info));
+            }
+        }
+
+        let mut storage: Vec<_> = revert.storage.iter().collect();
+        storage.sort_unstable_by_key(|(key, _)| **key);
+        self.varint(storage.len() as u64);
+        for (key, slot) in storage {
+            self.u256(*key);
+            match slot {
+                RevertToSlot::Some(value) => {
+                    self.out.push(0);
+                    self.u256(*value);
+                }
+                RevertToSlot::Destroyed => self.out.push(1),
+            }
+        }
+        self.out.push(account_status_to_u8(revert.previous_status));
+        self.out.push(revert.wipe_storage as u8);
+    }
+}
+
+/// Reads primitive values from the input buffer.
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Code table, filled before any account info is decoded.
+    code_table: HashMap<B256, Bytecode>,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BundleDecodeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(BundleDecodeError::UnexpectedEof)?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, BundleDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self, field: &'static str) -> Result<bool, BundleDecodeError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(BundleDecodeError::InvalidTag { field, tag }),
+        }
+    }
+
+    fn varint(&mut self) -> Result<u64, BundleDecodeError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = (byte & 0x7f) as u64;
+            if shift == 63 && bits > 1 {
+                return Err(BundleDecodeError::VarintOverflow);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BundleDecodeError::VarintOverflow)
+    }
+
+    /// Reads a collection length.
+    ///
+    /// Every encoded element takes at least one byte, so lengths larger than the remaining input
+    /// are rejected early instead of being used for allocation.
+    fn len(&mut self) -> Result<usize, BundleDecodeError> {
+        let len = self.varint()?;
+        if len > (self.bytes.len() - self.pos) as u64 {
+            return Err(BundleDecodeError::UnexpectedEof);
+        }
+        Ok(len as usize)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], BundleDecodeError> {
+        let len = self.len()?;
+        self.take(len)
+    }
+
+    fn b256(&mut self) -> Result<B256, BundleDecodeError> {
+        Ok(B256::from_slice(self.take(32)?))
+    }
+
+    fn address(&mut self) -> Result<Address, BundleDecodeError> {
+        Ok(Address::from_slice(self.take(20)?))
+    }
+
+    fn u256(&mut self) -> Result<StorageKey, BundleDecodeError> {
+        let len = self.u8()?;
+        if len > 32 {
+            return Err(BundleDecodeError::InvalidU256Length(len));
+        }
+        Ok(U256::from_be_slice(self.take(len as usize)?))
+    }
+
+    fn account_status(&mut self) -> Result<AccountStatus, BundleDecodeError> {
+        Ok(match self.u8()? {
+            0 => AccountStatus::LoadedNotExisting,
+            1 => AccountStatus::Loaded,
+            2 => AccountStatus::LoadedEmptyEIP161,
+            3 => AccountStatus::InMemoryChange,
+            4 => AccountStatus::Changed,
+            5 => AccountStatus::Destroyed,
+            6 => AccountStatus::DestroyedChanged,
+            7 => AccountStatus::DestroyedAgain,
+            tag => {
+                return Err(BundleDecodeError::InvalidTag {
+                    field: "account status",
+                    tag,
+                })
+            }
+        })
+    }
+
+    fn account_info(&mut self) -> Result<Option<AccountInfo>, BundleDecodeError> {
+        if !self.bool("account info presence")? {
+            return Ok(None);
+        }
+        let balance = self.u256()?;
+        let nonce = self.varint()?;
+        let code_hash = self.b256()?;
+        let code = match self.u8()? {
+            CODE_REF_NONE => None,
+            CODE_REF_DEFAULT => Some(Bytecode::default()),
+            CODE_REF_TABLE => Some(
+                self.code_table
+                    .get(&code_hash)
+                    .cloned()
+                    .ok_or(BundleDecodeError::MissingCode(code_hash))?,
+            ),
+            tag => {
+                return Err(BundleDecodeError::InvalidTag {
+                    field: "code reference",
+                    tag,
+                })
+            }
+        };
+        Ok(Some(AccountInfo {
+            balance,
+            nonce,
+            code_hash,
+            code,
+        }))
+    }
+
+    fn account_revert(&mut self) -> Result<AccountRevert, BundleDecodeError> {
+        let account = match self.u8()? {
+            0 => AccountInfoRevert::DoNothing,
+            1 => AccountInfoRevert::DeleteIt,
+            2 => AccountInfoRevert::RevertTo(self.account_info()?.ok_or(
+                BundleDecodeError::InvalidTag {
+                    field: "account info presence",
+                    tag: 0,
+                },
+            )?),
+            tag => {
+                return Err(BundleDecodeError::InvalidTag {
+                    field: "account info revert",
+                    tag,
+                })
+            }
+        };
+
+        let count = self.len()?;
+        let mut storage = HashMap::default();
+        for _ in 0..count {
+            let key = self.u256()?;
+            let slot = match self.u8()? {
+                0 => RevertToSlot::Some(self.u256()?),
+                1 => RevertToSlot::Destroyed,
+                tag => {
+                    return Err(BundleDecodeError::InvalidTag {
+                        field: "revert to slot",
+                        tag,
+                    })
+                }
+            };
+            storage.insert(key, slot);
+        }
+        let previous_status = self.account_status()?;
+        let wipe_storage = self.bool("wipe storage")?;
+        Ok(AccountRevert {
+            account,
+            storage,
+            previous_status,
+            wipe_storage,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::KECCAK_EMPTY;
+    use proptest::{
+        collection::{hash_map, vec},
+        prelude::*,
+        sample::Index,
+    };
+
+    fn u256() -> impl Strategy<Value = U256> {
+        // Mix of small and full width values to exercise the trimmed encoding.
+        prop_oneof![
+            Just(U256::ZERO),
+            (0u64..1000).prop_map(U256::from),
+            any::<[u64; 4]>().prop_map(U256::from_limbs),
+        ]
+    }
+
+    fn address() -> impl Strategy<Value = Address> {
+        (0u8..16).prop_map(Address::with_last_byte)
+    }
+
+    fn status() -> impl Strategy<Value = AccountStatus> {
+        prop::sample::select(vec![
+            AccountStatus::LoadedNotExisting,
+            AccountStatus::Loaded,
+            AccountStatus::LoadedEmptyEIP161,
+            AccountStatus::InMemoryChange,
+            AccountStatus::Changed,
+            AccountStatus::Destroyed,
+            AccountStatus::DestroyedChanged,
+            AccountStatus::DestroyedAgain,
+        ])
+    }
+
+    fn code() -> impl Strategy<Value = Bytecode> {
+        prop_oneof![
+            1 => address().prop_map(Bytecode::new_eip7702),
+            3 => vec(any::<u8>(), 0..40).prop_map(|raw| Bytecode::new_legacy(raw.into())),
+        ]
+    }
+
+    fn info() -> impl Strategy<Value = AccountInfo> {
+        let code = prop::option::of(prop_oneof![Just(Bytecode::default()), code()]);
+        (u256(), any::<u64>(), code).prop_map(|(balance, nonce, code)| AccountInfo {
+            balance,
+            nonce,
+            code_hash: code.as_ref().map_or(KECCAK_EMPTY, Bytecode::hash_slow),
+            code,
+        })
+    }
+
+    fn account() -> impl Strategy<Value = BundleAccount> {
+        let slot = (u256(), u256())
+            .prop_map(|(original, present)| StorageSlot::new_changed(original, present));
+        (
+            prop::option::weighted(0.75, info()),
+            prop::option::weighted(0.75, info()),
+            hash_map(u256(), slot, 0..6),
+            status(),
+        )
+            .prop_map(|(original, present, storage, status)| {
+                BundleAccount::new(original, present, storage.into_iter().collect(), status)
+            })
+    }
+
+    fn account_revert() -> impl Strategy<Value = AccountRevert> {
+        let account = prop_oneof![
+            Just(AccountInfoRevert::DoNothing),
+            Just(AccountInfoRevert::DeleteIt),
+            info().prop_map(AccountInfoRevert::RevertTo),
+        ];
+        let slot = prop_oneof![
+            Just(RevertToSlot::Destroyed),
+            u256().prop_map(RevertToSlot::Some),
+        ];
+        (
+            account,
+            hash_map(u256(), slot, 0..4),
+            status(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(account, storage, previous_status, wipe_storage)| AccountRevert {
+                    account,
+                    storage: storage.into_iter().collect(),
+                    previous_status,
+                    wipe_storage,
+                },
+            )
+    }
+
+    fn bundle() -> impl Strategy<Value = BundleState> {
+        (
+            hash_map(address(), account(), 0..8),
+            vec(code(), 0..4),
+            vec(vec((address(), account_revert()), 0..4), 0..4),
+            0usize..1 << 20,
+            0usize..1 << 20,
+        )
+            .prop_map(|(state, contracts, reverts, state_size, reverts_size)| {
+                BundleState {
+                    state: state.into_iter().collect(),
+                    contracts: contracts
+                        .into_iter()
+                        .map(|code| (code.hash_slow(), code))
+                        .collect(),
+                    reverts: Reverts::new(reverts),
+                    state_size,
+                    reverts_size,
+                }
+            })
+    }
+
+    /// Checks equality including the attached bytecode, which [`AccountInfo`]'s `PartialEq` ignores.
+    fn assert_same_code(a: Option<&AccountInfo>, b: Option<&AccountInfo>) {
+        assert_eq!(a, b);
+        assert_eq!(
+            a.and_then(|a| a.code.as_ref()),
+            b.and_then(|b| b.code.as_ref())
+        );
+    }
+
+    fn assert_bundle_eq(a: &BundleState, b: &BundleState) {
+        assert_eq!(a, b);
+        assert_eq!(a.reverts.len(), b.reverts.len());
+        for (address, account) in &a.state {
+            let other = &b.state[address];
+            assert_same_code(account.info.as_ref(), other.info.as_ref());
+            assert_same_code(account.original_info.as_ref(), other.original_info.as_ref());
+        }
+        for (a, b) in a.reverts.iter().zip(b.reverts.iter()) {
+            assert_eq!(a, b);
+            for ((_, a), (_, b)) in a.iter().zip(b.iter()) {
+                if let (AccountInfoRevert::RevertTo(a), AccountInfoRevert::RevertTo(b)) =
+                    (&a.account, &b.account)
+                {
+                    assert_same_code(Some(a), Some(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn roundtrip_empty() {
+        let bundle = BundleState::default();
+        let encoded = bundle.encode().unwrap();
+        assert_eq!(encoded.len(), BUNDLE_CODEC_MAGIC.len() + 6);
+        assert_bundle_eq(&bundle, &BundleState::decode(&encoded).unwrap());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn roundtrip_random_bundles(bundle in bundle()) {
+            let encoded = bundle.encode().unwrap();
+            let decoded = BundleState::decode(&encoded).unwrap();
+            assert_bundle_eq(&bundle, &decoded);
+            // Encoding is deterministic regardless of hash map iteration order.
+            prop_assert_eq!(decoded.encode().unwrap(), encoded);
+        }
+
+        #[test]
+        fn decode_does_not_panic(
+            bundle in bundle(),
+            index in any::<Index>(),
+            byte in any::<u8>(),
+            garbage in vec(any::<u8>(), 0..256),
+        ) {
+            let encoded = bundle.encode().unwrap();
+            // Truncations.
+            for len in 0..encoded.len() {
+                prop_assert!(BundleState::decode(&encoded[..len]).is_err());
+            }
+            // Byte flip.
+            let mut mutated = encoded.clone();
+            mutated[index.index(encoded.len())] = byte;
+            let _ = BundleState::decode(&mutated);
+            // Garbage after a valid header.
+            let mut input = encoded[..BUNDLE_CODEC_MAGIC.len() + 1].to_vec();
+            input.extend(garbage);
+            let _ = BundleState::decode(&input);
+        }
+    }
+
+    #[test]
+    fn contracts_are_deduplicated() {
+        let code = Bytecode::new_legacy(vec![0x60; 1000].into());
+        let code_hash = code.hash_slow();
+        let info = AccountInfo {
+            balance: U256::from(1),
+            nonce: 1,
+            code_hash,
+            code: Some(code.clone()),
+        };
+
+        let mut bundle = BundleState::default();
+        bundle.contracts.insert(code_hash, code.clone());
+        for i in 0..4 {
+            bundle.state.insert(
+                Address::with_last_byte(i),
+                BundleAccount::new(
+                    Some(info.clone()),
+                    Some(info.clone()),
+                    HashMap::default(),
+                    AccountStatus::Changed,
+                ),
+            );
+        }
+        bundle.reverts.push(vec![(
+            Address::with_last_byte(0),
+            AccountRevert {
+                account: AccountInfoRevert::RevertTo(info),
+                ..Default::default()
+            },
+        )]);
+
+        let encoded = bundle.encode().unwrap();
+        assert!(encoded.len() < 2 * 1000);
+        let decoded = BundleState::decode(&encoded).unwrap();
+        assert_bundle_eq(&bundle, &decoded);
+        assert_eq!(decoded.contracts.get(&code_hash), Some(&code));
+    }
+
+    #[test]
+    fn account_code_outside_contracts() {
+        let code = Bytecode::new_legacy(vec![0x5b, 0x00].into());
+        let info = AccountInfo::from_bytecode(code);
+
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            Address::ZERO,
+            BundleAccount::new(None, Some(info), HashMap::default(), AccountStatus::Changed),
+        );
+        let decoded = BundleState::decode(&bundle.encode().unwrap()).unwrap();
+        assert_bundle_eq(&bundle, &decoded);
+        // Code that is only attached to account info is not a contract of the bundle.
+        assert!(decoded.contracts.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_header() {
+        let encoded = BundleState::default().encode().unwrap();
+
+        let mut bad_magic = encoded.clone();
+        bad_magic[0] ^= 0xff;
+        assert_eq!(
+            BundleState::decode(&bad_magic),
+            Err(BundleDecodeError::InvalidMagic)
+        );
+
+        let mut bad_version = encoded.clone();
+        bad_version[BUNDLE_CODEC_MAGIC.len()] = BUNDLE_CODEC_VERSION + 1;
+        assert_eq!(
+            BundleState::decode(&bad_version),
+            Err(BundleDecodeError::UnsupportedVersion(
+                BUNDLE_CODEC_VERSION + 1
+            ))
+        );
+
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert_eq!(
+            BundleState::decode(&trailing),
+            Err(BundleDecodeError::TrailingBytes(1))
+        );
+
+        assert_eq!(
+            BundleState::decode(&encoded[..encoded.len() - 1]),
+            Err(BundleDecodeError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn rejects_missing_code() {
+        let code = Bytecode::new_legacy(vec![0x00].into());
+        let info = AccountInfo::from_bytecode(code);
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            Address::ZERO,
+            BundleAccount::new(
+                None,
+                Some(info.clone()),
+                HashMap::default(),
+                AccountStatus::Changed,
+            ),
+        );
+        let mut encoded = bundle.encode().unwrap();
+
+        // Drop the single code table entry: count(1) hash(32) kind(1) len(1) code(1) flag(1).
+        let header = BUNDLE_CODEC_MAGIC.len() + 1;
+        encoded.drain(header..header + 37);
+        encoded.insert(header, 0);
+        assert_eq!(
+            BundleState::decode(&encoded),
+            Err(BundleDecodeError::MissingCode(info.code_hash))
+        );
+    }
+
+    #[test]
+    fn rejects_code_hash_mismatch() {
+        let code = Bytecode::new_legacy(vec![0x00].into());
+        let mut bundle = BundleState::default();
+        bundle.contracts.insert(code.hash_slow(), code);
+        let mut encoded = bundle.encode().unwrap();
+
+        // Replace the code byte: count(1) hash(32) kind(1) len(1) code(1).
+        let index = BUNDLE_CODEC_MAGIC.len() + 1 + 35;
+        encoded[index] = 0x01;
+        let code = Bytecode::new_legacy(vec![0x01].into());
+        assert_eq!(
+            BundleState::decode(&encoded),
+            Err(BundleDecodeError::CodeHashMismatch {
+                expected: Bytecode::new_legacy(vec![0x00].into()).hash_slow(),
+                got: code.hash_slow(),
+            })
+        );
+    }
+
+    #[test]
+    fn encode_rejects_code_hash_mismatch() {
+        let code = Bytecode::new_legacy(vec![0x00].into());
+        let info = AccountInfo {
+            code_hash: B256::with_last_byte(1),
+            ..AccountInfo::from_bytecode(code.clone())
+        };
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            Address::ZERO,
+            BundleAccount::new(None, Some(info), HashMap::default(), AccountStatus::Changed),
+        );
+
+        let mut out = vec![0xff];
+        assert_eq!(
+            bundle.encode_to(&mut out),
+            Err(BundleEncodeError::CodeHashMismatch {
+                expected: B256::with_last_byte(1),
+                got: code.hash_slow(),
+            })
+        );
+        assert_eq!(out, vec![0xff]);
+    }
+}
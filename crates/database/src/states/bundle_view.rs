@@ -0,0 +1,202 @@
+//! Read-only view of a [`BundleState`] layered over a database.
+use super::BundleState;
+use bytecode::Bytecode;
+use database_interface::{DatabaseRef, DatabaseStorageIterRef, StorageOrder, StorageRange};
+use primitives::{Address, StorageKey, StorageValue, B256};
+use state::AccountInfo;
+
+/// [`BundleState`] layered over a database.
+///
+/// Reads return the present values of the bundle and fall back to the database for accounts,
+/// slots and code the bundle does not know. Use [`WrapDatabaseRef`] to get a [`Database`].
+///
+/// [`WrapDatabaseRef`]: database_interface::WrapDatabaseRef
+/// [`Database`]: database_interface::Database
+#[derive(Clone, Copy, Debug)]
+pub struct BundleView<'a, DB> {
+    /// Bundle with the changes on top of the database.
+    pub bundle: &'a BundleState,
+    /// Database the bundle was built on.
+    pub database: DB,
+}
+
+impl<'a, DB> BundleView<'a, DB> {
+    /// Creates a new view of the bundle over the database.
+    pub fn new(bundle: &'a BundleState, database: DB) -> Self {
+        Self { bundle, database }
+    }
+}
+
+impl BundleState {
+    /// Returns a page of storage of `address` with the bundle changes layered over `base`.
+    ///
+    /// `base` is not called if the bundle knows the whole storage of the account, as it happens
+    /// for destroyed or newly created accounts.
+    pub fn storage_range<E>(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+        base: impl FnOnce(usize) -> Result<StorageRange, E>,
+    ) -> Result<StorageRange, E> {
+        match self.account(&address) {
+            Some(account) => StorageRange::layered(
+                account
+                    .storage
+                    .iter()
+                    .map(|(key, slot)| (*key, slot.present_value)),
+                account.status.is_storage_known(),
+                order,
+                start,
+                limit,
+                base,
+            ),
+            None => base(limit),
+        }
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for BundleView<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.bundle.account(&address) {
+            Some(account) => Ok(account.account_info()),
+            None => self.database.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.bundle.bytecode(&code_hash) {
+            Some(code) => Ok(code),
+            None => self.database.code_by_hash_ref(code_hash),
+        }
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        match self
+            .bundle
+            .account(&address)
+            .and_then(|account| account.storage_slot(index))
+        {
+            Some(value) => Ok(value),
+            None => self.database.storage_ref(address, index),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.database.block_hash_ref(number)
+    }
+}
+
+impl<DB: DatabaseStorageIterRef> DatabaseStorageIterRef for BundleView<'_, DB> {
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        self.bundle
+            .storage_range(address, order, start, limit, |limit| {
+                self.database
+                    .storage_range_ref(address, order, start, limit)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{states::StorageSlot, AccountStatus, BundleAccount, InMemoryDB};
+    use database_interface::{DatabaseStorageIter, WrapDatabaseRef};
+    use primitives::{HashMap, U256};
+
+    #[test]
+    fn storage_range_over_database() {
+        let changed = Address::with_last_byte(1);
+        let destroyed = Address::with_last_byte(2);
+        let untouched = Address::with_last_byte(3);
+        let slot = |i: u64| (StorageKey::from(i), StorageValue::from(i * 10));
+
+        let mut db = InMemoryDB::default();
+        for address in [changed, destroyed, untouched] {
+            db.insert_account_info(address, AccountInfo::from_balance(U256::from(1)));
+            for i in 1..=3 {
+                let (key, value) = slot(i);
+                db.insert_account_storage(address, key, value).unwrap();
+            }
+        }
+
+        // Bundle clears slot 2 and adds slot 4 of the changed account, and recreates the destroyed one.
+        let info = Some(AccountInfo::from_balance(U256::from(2)));
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            changed,
+            BundleAccount::new(
+                info.clone(),
+                info.clone(),
+                HashMap::from_iter([
+                    (
+                        StorageKey::from(2),
+                        StorageSlot::new_changed(StorageValue::from(20), StorageValue::ZERO),
+                    ),
+                    (
+                        StorageKey::from(4),
+                        StorageSlot::new_changed(StorageValue::ZERO, StorageValue::from(40)),
+                    ),
+                ]),
+                AccountStatus::Changed,
+            ),
+        );
+        bundle.state.insert(
+            destroyed,
+            BundleAccount::new(
+                info.clone(),
+                info,
+                HashMap::from_iter([(
+                    StorageKey::from(9),
+                    StorageSlot::new_changed(StorageValue::ZERO, StorageValue::from(90)),
+                )]),
+                AccountStatus::DestroyedChanged,
+            ),
+        );
+
+        let view = BundleView::new(&bundle, &db);
+        assert_eq!(
+            view.account_storage_ref(changed).unwrap(),
+            vec![slot(1), slot(3), slot(4)]
+        );
+        assert_eq!(view.account_storage_ref(destroyed).unwrap(), vec![slot(9)]);
+        assert_eq!(
+            view.account_storage_ref(untouched).unwrap(),
+            vec![slot(1), slot(2), slot(3)]
+        );
+
+        // Point lookups agree with the enumeration.
+        assert_eq!(
+            view.storage_ref(changed, StorageKey::from(2)),
+            Ok(U256::ZERO)
+        );
+        assert_eq!(
+            view.storage_ref(destroyed, StorageKey::from(1)),
+            Ok(U256::ZERO)
+        );
+        assert_eq!(
+            view.storage_ref(untouched, StorageKey::from(1)),
+            Ok(U256::from(10))
+        );
+        assert_eq!(
+            view.basic_ref(changed).unwrap().unwrap().balance,
+            U256::from(2)
+        );
+
+        let mut wrapped = WrapDatabaseRef(view);
+        assert_eq!(wrapped.account_storage(destroyed).unwrap(), vec![slot(9)]);
+    }
+}
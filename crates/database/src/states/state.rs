@@ -1,9 +1,14 @@
+#[cfg(feature = "std")]
+use super::BackgroundMerge;
 use super::{
     bundle_state::BundleRetention, cache::CacheState, plain_account::PlainStorage, BundleState,
     CacheAccount, StateBuilder, TransitionAccount, TransitionState,
 };
 use bytecode::Bytecode;
-use database_interface::{Database, DatabaseCommit, DatabaseRef, EmptyDB};
+use database_interface::{
+    Database, DatabaseCommit, DatabaseRef, DatabaseStorageIter, DatabaseStorageIterRef, EmptyDB,
+    StorageOrder, StorageRange,
+};
 use primitives::{hash_map, Address, HashMap, StorageKey, StorageValue, B256, BLOCK_HASH_HISTORY};
 use state::{Account, AccountInfo};
 use std::{
@@ -63,6 +68,11 @@ pub struct State<DB> {
     ///
     /// The fork block is different or some blocks are not saved inside database.
     pub block_hashes: BTreeMap<u64, B256>,
+    /// If set, [`State::merge_transitions`] hands transitions over to a worker thread
+    /// that owns the bundle until [`State::join_background_merge`] or [`State::take_bundle`]
+    /// is called.
+    #[cfg(feature = "std")]
+    pub background_merge: Option<BackgroundMerge>,
 }
 
 // Have ability to call State::builder without having to specify the type.
@@ -77,7 +87,14 @@ impl<DB: Database> State<DB> {
     /// Returns the size hint for the inner bundle state.
     ///
     /// See [BundleState::size_hint] for more info.
+    ///
+    /// While transitions are merged in the background, the bundle owned by the worker is
+    /// estimated with [`BackgroundMerge::size_hint`].
     pub fn bundle_size_hint(&self) -> usize {
+        #[cfg(feature = "std")]
+        if let Some(background_merge) = self.background_merge.as_ref() {
+            return self.bundle_state.size_hint() + background_merge.size_hint();
+        }
         self.bundle_state.size_hint()
     }
 
@@ -176,10 +193,59 @@ impl<DB: Database> State<DB> {
     /// This action will create final post state and all reverts so that
     /// we at any time revert state of bundle to the state before transition
     /// is applied.
+    ///
+    /// If background merge is enabled, transitions are handed over to the worker thread and
+    /// the merged bundle becomes available after [`State::join_background_merge`]. If the worker
+    /// thread can't be spawned, transitions are merged on the calling thread.
     pub fn merge_transitions(&mut self, retention: BundleRetention) {
-        if let Some(transition_state) = self.transition_state.as_mut().map(TransitionState::take) {
-            self.bundle_state
-                .apply_transitions_and_create_reverts(transition_state, retention);
+        let Some(transition_state) = self.transition_state.as_mut().map(TransitionState::take)
+        else {
+            return;
+        };
+        #[cfg(feature = "std")]
+        let (transition_state, retention) =
+            if let Some(background_merge) = self.background_merge.as_mut() {
+                let bundle_state = &mut self.bundle_state;
+                let use_preloaded_bundle = self.use_preloaded_bundle;
+                let Err(fallback) = background_merge.merge(
+                    || {
+                        // Preloaded bundle is still read during execution, so the worker builds a
+                        // new bundle on top of it that is extended into it on join.
+                        if use_preloaded_bundle {
+                            BundleState::default()
+                        } else {
+                            core::mem::take(bundle_state)
+                        }
+                    },
+                    transition_state,
+                    retention,
+                ) else {
+                    return;
+                };
+                fallback
+            } else {
+                (transition_state, retention)
+            };
+        self.bundle_state
+            .apply_transitions_and_create_reverts(transition_state, retention);
+    }
+
+    /// Waits for the transitions merged in the background and stores the result in
+    /// [`State::bundle_state`].
+    ///
+    /// Does nothing if background merge is not enabled or there is nothing being merged.
+    pub fn join_background_merge(&mut self) {
+        #[cfg(feature = "std")]
+        if let Some(bundle_state) = self
+            .background_merge
+            .as_mut()
+            .and_then(BackgroundMerge::join)
+        {
+            if self.use_preloaded_bundle {
+                self.bundle_state.extend(bundle_state);
+            } else {
+                self.bundle_state = bundle_state;
+            }
         }
     }
 
@@ -222,7 +288,10 @@ impl<DB: Database> State<DB> {
     /// If the `State` has been built with the
     /// [`StateBuilder::with_bundle_prestate`] option, the pre-state will be
     /// taken along with any changes made by [`State::merge_transitions`].
+    ///
+    /// Waits for the background merge to finish, see [`State::join_background_merge`].
     pub fn take_bundle(&mut self) -> BundleState {
+        self.join_background_merge();
         core::mem::take(&mut self.bundle_state)
     }
 }
@@ -312,6 +381,26 @@ impl<DB: Database> Database for State<DB> {
     }
 }
 
+impl<DB: DatabaseStorageIter> DatabaseStorageIter for State<DB> {
+    fn storage_range(
+        &mut self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        layered_storage_range(
+            &self.cache,
+            self.use_preloaded_bundle.then_some(&self.bundle_state),
+            address,
+            order,
+            start,
+            limit,
+            |limit| self.database.storage_range(address, order, start, limit),
+        )
+    }
+}
+
 impl<DB: Database> DatabaseCommit for State<DB> {
     fn commit(&mut self, evm_state: HashMap<Address, Account>) {
         let transitions = self.cache.apply_evm_state(evm_state);
@@ -384,6 +473,65 @@ impl<DB: DatabaseRef> DatabaseRef for State<DB> {
     }
 }
 
+impl<DB: DatabaseStorageIterRef> DatabaseStorageIterRef for State<DB> {
+    fn storage_range_ref(
+        &self,
+        address: Address,
+        order: StorageOrder,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, Self::Error> {
+        layered_storage_range(
+            &self.cache,
+            self.use_preloaded_bundle.then_some(&self.bundle_state),
+            address,
+            order,
+            start,
+            limit,
+            |limit| {
+                self.database
+                    .storage_range_ref(address, order, start, limit)
+            },
+        )
+    }
+}
+
+/// Enumerates storage of the account by layering cache over the preloaded bundle and the bundle
+/// over the database.
+///
+/// Lower layers are skipped if the account storage is known in the upper layer, as it happens
+/// for destroyed or newly created accounts.
+fn layered_storage_range<E>(
+    cache: &CacheState,
+    bundle: Option<&BundleState>,
+    address: Address,
+    order: StorageOrder,
+    start: B256,
+    limit: usize,
+    database: impl FnOnce(usize) -> Result<StorageRange, E>,
+) -> Result<StorageRange, E> {
+    let bundle_layer = |limit| match bundle {
+        Some(bundle) => bundle.storage_range(address, order, start, limit, database),
+        None => database(limit),
+    };
+    match cache.accounts.get(&address) {
+        // Account does not exist so it has no storage.
+        Some(CacheAccount { account: None, .. }) => Ok(StorageRange::default()),
+        Some(CacheAccount {
+            account: Some(account),
+            status,
+        }) => StorageRange::layered(
+            account.storage.iter().map(|(key, value)| (*key, *value)),
+            status.is_storage_known(),
+            order,
+            start,
+            limit,
+            bundle_layer,
+        ),
+        None => bundle_layer(limit),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -892,4 +1040,177 @@ mod tests {
             )])])
         )
     }
+
+    #[test]
+    fn storage_range_layers() {
+        let changed = Address::with_last_byte(1);
+        let destroyed = Address::with_last_byte(2);
+        let slot = |i: u64| (StorageKey::from(i), StorageValue::from(i * 10));
+
+        let mut db = crate::InMemoryDB::default();
+        for address in [changed, destroyed] {
+            db.insert_account_info(address, AccountInfo::from_balance(U256::from(1)));
+            for i in 1..=5 {
+                let (key, value) = slot(i);
+                db.insert_account_storage(address, key, value).unwrap();
+            }
+        }
+
+        // Bundle clears slot 2 and adds slot 6 of the changed account, and recreates the destroyed one.
+        let info = Some(AccountInfo::from_balance(U256::from(1)));
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            changed,
+            BundleAccount::new(
+                info.clone(),
+                info.clone(),
+                HashMap::from_iter([
+                    (
+                        StorageKey::from(2),
+                        StorageSlot::new_changed(StorageValue::from(20), StorageValue::ZERO),
+                    ),
+                    (
+                        StorageKey::from(6),
+                        StorageSlot::new_changed(StorageValue::ZERO, StorageValue::from(60)),
+                    ),
+                ]),
+                AccountStatus::Changed,
+            ),
+        );
+        bundle.state.insert(
+            destroyed,
+            BundleAccount::new(
+                info.clone(),
+                info,
+                HashMap::from_iter([(
+                    StorageKey::from(9),
+                    StorageSlot::new_changed(StorageValue::ZERO, StorageValue::from(90)),
+                )]),
+                AccountStatus::DestroyedChanged,
+            ),
+        );
+
+        let mut state = State::builder()
+            .with_database(db)
+            .with_bundle_prestate(bundle)
+            .build();
+
+        // Cache overrides slot 3 of the changed account.
+        state.storage(changed, StorageKey::from(1)).unwrap();
+        state
+            .cache
+            .accounts
+            .get_mut(&changed)
+            .unwrap()
+            .account
+            .as_mut()
+            .unwrap()
+            .storage
+            .insert(StorageKey::from(3), StorageValue::from(33));
+
+        let expected = vec![
+            slot(1),
+            (StorageKey::from(3), StorageValue::from(33)),
+            slot(4),
+            slot(5),
+            slot(6),
+        ];
+        assert_eq!(state.account_storage(changed).unwrap(), expected);
+        assert_eq!(state.account_storage_ref(changed).unwrap(), expected);
+        assert_eq!(state.account_storage(destroyed).unwrap(), vec![slot(9)]);
+
+        // Pagination in key hash order returns every slot exactly once.
+        let mut start = B256::ZERO;
+        let mut paged = Vec::new();
+        loop {
+            let range = state
+                .storage_range(changed, StorageOrder::KeyHash, start, 2)
+                .unwrap();
+            paged.extend(range.slots);
+            match range.next {
+                Some(next) => start = next,
+                None => break,
+            }
+        }
+        paged.sort();
+        assert_eq!(paged, expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn background_merge_matches_sync_merge() {
+        /// Increments balances over a few blocks, merging transitions after every block.
+        fn execute(mut state: State<crate::InMemoryDB>) -> BundleState {
+            for block in 1..=5u8 {
+                state
+                    .increment_balances(
+                        (0..20u8)
+                            .map(|i| (Address::with_last_byte(i % (block * 4)), i as u128 + 1)),
+                    )
+                    .unwrap();
+                state.merge_transitions(BundleRetention::Reverts);
+            }
+            state.take_bundle()
+        }
+
+        let mut db = crate::InMemoryDB::default();
+        db.insert_account_info(
+            Address::with_last_byte(1),
+            AccountInfo::from_balance(U256::from(10)),
+        );
+
+        let sync = execute(
+            State::builder()
+                .with_database(db.clone())
+                .with_bundle_update()
+                .build(),
+        );
+        let mut background = State::builder()
+            .with_database(db.clone())
+            .with_bundle_update()
+            .with_background_transition_merge()
+            .build();
+        background
+            .increment_balances([(Address::with_last_byte(1), 1)])
+            .unwrap();
+        background.merge_transitions(BundleRetention::Reverts);
+        assert!(background.background_merge.as_ref().unwrap().is_running());
+        // Account and its revert are estimated while the worker owns the bundle.
+        assert_eq!(background.bundle_size_hint(), 2);
+        background.join_background_merge();
+        assert_eq!(background.bundle_size_hint(), 2);
+        assert!(!background.background_merge.as_ref().unwrap().is_running());
+        assert_eq!(
+            background
+                .bundle_state
+                .account(&Address::with_last_byte(1))
+                .unwrap()
+                .info,
+            Some(AccountInfo::from_balance(U256::from(11)))
+        );
+
+        let background = execute(
+            State::builder()
+                .with_database(db.clone())
+                .with_bundle_update()
+                .with_background_transition_merge()
+                .build(),
+        );
+        assert_eq!(background, sync);
+        assert_eq!(background.reverts.len(), 5);
+
+        // Preloaded bundle is still readable while the worker owns its copy.
+        let preloaded = |background: bool| {
+            let builder = State::builder()
+                .with_database(db.clone())
+                .with_bundle_update()
+                .with_bundle_prestate(sync.clone());
+            if background {
+                execute(builder.with_background_transition_merge().build())
+            } else {
+                execute(builder.build())
+            }
+        };
+        assert_eq!(preloaded(true), preloaded(false));
+    }
 }
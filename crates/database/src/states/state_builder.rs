@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use super::BackgroundMerge;
 use super::{cache::CacheState, state::DBBox, BundleState, State, TransitionState};
 use database_interface::{DBErrorMarker, Database, DatabaseRef, EmptyDB, WrapDatabaseRef};
 use primitives::B256;
@@ -143,6 +145,9 @@ impl<DB: Database> StateBuilder<DB> {
 
     /// Starts the thread that will take transitions and do merge to the bundle state
     /// in the background.
+    ///
+    /// See [`State::join_background_merge`] for the join point. Requires the `std` feature,
+    /// otherwise transitions are merged synchronously.
     pub fn with_background_transition_merge(self) -> Self {
         Self {
             with_background_transition_merge: true,
@@ -175,6 +180,10 @@ impl<DB: Database> StateBuilder<DB> {
             bundle_state: self.with_bundle_prestate.unwrap_or_default(),
             use_preloaded_bundle,
             block_hashes: self.with_block_hashes,
+            #[cfg(feature = "std")]
+            background_merge: self
+                .with_background_transition_merge
+                .then(BackgroundMerge::new),
         }
     }
 }
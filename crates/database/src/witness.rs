@@ -0,0 +1,346 @@
+//! Execution witness recording and stateless re-execution.
+//!
+//! [`RecordingDB`] wraps the database used by [`State`](crate::State) and captures every value
+//! read during execution into an [`ExecutionWitness`]. [`WitnessDB`] serves those values back
+//! without access to the full database, so the same block can be re-executed and verified
+//! from the witness alone.
+use core::fmt;
+use database_interface::{DBErrorMarker, Database, DatabaseRef};
+use primitives::{Address, HashMap, StorageKey, StorageValue, B256, KECCAK_EMPTY};
+use state::{AccountInfo, Bytecode};
+use std::collections::BTreeMap;
+
+/// Every value read from the database during execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionWitness {
+    /// Accounts that were read, `None` if the account did not exist.
+    ///
+    /// Bytecode is not kept in account info, it can be found in `codes`.
+    pub accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Storage slots that were read.
+    pub storage: HashMap<Address, HashMap<StorageKey, StorageValue>>,
+    /// Bytecodes that were read, either with the account or by its hash.
+    pub codes: HashMap<B256, Bytecode>,
+    /// Block hashes that were read.
+    pub block_hashes: BTreeMap<u64, B256>,
+}
+
+impl ExecutionWitness {
+    /// Records an account read.
+    ///
+    /// Bytecode attached to the account info is moved to `codes`.
+    pub fn insert_account(&mut self, address: Address, info: Option<AccountInfo>) {
+        let info = info.map(|mut info| {
+            if let Some(code) = info.code.take() {
+                if !code.is_empty() {
+                    self.codes.insert(info.code_hash, code);
+                }
+            }
+            info
+        });
+        self.accounts.insert(address, info);
+    }
+
+    /// Records a storage slot read.
+    pub fn insert_storage(&mut self, address: Address, index: StorageKey, value: StorageValue) {
+        self.storage
+            .entry(address)
+            .or_default()
+            .insert(index, value);
+    }
+
+    /// Records a bytecode read.
+    pub fn insert_code(&mut self, code_hash: B256, code: Bytecode) {
+        self.codes.insert(code_hash, code);
+    }
+
+    /// Records a block hash read.
+    pub fn insert_block_hash(&mut self, number: u64, hash: B256) {
+        self.block_hashes.insert(number, hash);
+    }
+
+    /// Merges values of another witness into this one.
+    pub fn extend(&mut self, other: ExecutionWitness) {
+        self.accounts.extend(other.accounts);
+        for (address, slots) in other.storage {
+            self.storage.entry(address).or_default().extend(slots);
+        }
+        self.codes.extend(other.codes);
+        self.block_hashes.extend(other.block_hashes);
+    }
+
+    /// Returns `true` if nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+            && self.storage.is_empty()
+            && self.codes.is_empty()
+            && self.block_hashes.is_empty()
+    }
+}
+
+/// Database wrapper that records every value read from the wrapped database into an
+/// [`ExecutionWitness`].
+///
+/// It is meant to be placed below [`State`](crate::State), where every key is read at most
+/// once, so the witness contains exactly the pre-state touched by the execution.
+#[derive(Clone, Debug, Default)]
+pub struct RecordingDB<DB> {
+    /// Wrapped database.
+    pub db: DB,
+    /// Values read so far.
+    pub witness: ExecutionWitness,
+}
+
+impl<DB> RecordingDB<DB> {
+    /// Wraps the database.
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            witness: ExecutionWitness::default(),
+        }
+    }
+
+    /// Takes the values read so far, leaving an empty witness in place.
+    pub fn take_witness(&mut self) -> ExecutionWitness {
+        core::mem::take(&mut self.witness)
+    }
+
+    /// Consumes the wrapper and returns the wrapped database and the witness.
+    pub fn into_parts(self) -> (DB, ExecutionWitness) {
+        (self.db, self.witness)
+    }
+}
+
+impl<DB: Database> Database for RecordingDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        self.witness.insert_account(address, info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.witness.insert_code(code_hash, code.clone());
+        Ok(code)
+    }
+
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.witness.insert_storage(address, index, value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.witness.insert_block_hash(number, hash);
+        Ok(hash)
+    }
+}
+
+/// Error returned by [`WitnessDB`] when a key is not part of the witness.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WitnessError {
+    /// Account is missing from the witness.
+    MissingAccount(Address),
+    /// Storage slot is missing from the witness.
+    MissingStorage(Address, StorageKey),
+    /// Bytecode is missing from the witness.
+    MissingCode(B256),
+    /// Block hash is missing from the witness.
+    MissingBlockHash(u64),
+}
+
+impl DBErrorMarker for WitnessError {}
+
+impl core::error::Error for WitnessError {}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAccount(address) => write!(f, "account {address} is missing from witness"),
+            Self::MissingStorage(address, index) => {
+                write!(
+                    f,
+                    "storage slot {index} of {address} is missing from witness"
+                )
+            }
+            Self::MissingCode(hash) => write!(f, "code {hash} is missing from witness"),
+            Self::MissingBlockHash(number) => {
+                write!(f, "block hash {number} is missing from witness")
+            }
+        }
+    }
+}
+
+/// Database that serves values solely from an [`ExecutionWitness`].
+///
+/// Any read of a key that is not in the witness fails with a [`WitnessError`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WitnessDB {
+    /// Witness the values are served from.
+    pub witness: ExecutionWitness,
+}
+
+impl WitnessDB {
+    /// Creates a new database from the witness.
+    pub fn new(witness: ExecutionWitness) -> Self {
+        Self { witness }
+    }
+}
+
+impl From<ExecutionWitness> for WitnessDB {
+    fn from(witness: ExecutionWitness) -> Self {
+        Self::new(witness)
+    }
+}
+
+impl Database for WitnessDB {
+    type Error = WitnessError;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    #[inline]
+    fn storage(
+        &mut self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+impl DatabaseRef for WitnessDB {
+    type Error = WitnessError;
+
+    /// Returns the account with its bytecode attached if the bytecode is in the witness.
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self
+            .witness
+            .accounts
+            .get(&address)
+            .ok_or(WitnessError::MissingAccount(address))?;
+        Ok(info.clone().map(|mut info| {
+            info.code = self.witness.codes.get(&info.code_hash).cloned();
+            info
+        }))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // Empty bytecode is never recorded.
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default());
+        }
+        self.witness
+            .codes
+            .get(&code_hash)
+            .cloned()
+            .ok_or(WitnessError::MissingCode(code_hash))
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.witness
+            .storage
+            .get(&address)
+            .and_then(|slots| slots.get(&index))
+            .copied()
+            .ok_or(WitnessError::MissingStorage(address, index))
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.witness
+            .block_hashes
+            .get(&number)
+            .copied()
+            .ok_or(WitnessError::MissingBlockHash(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InMemoryDB, State};
+    use primitives::U256;
+
+    #[test]
+    fn replay_reads_from_witness() {
+        let address = Address::with_last_byte(1);
+        let missing = Address::with_last_byte(2);
+        let code = Bytecode::new_legacy([0x60, 0x00].into());
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::from(5), 1, code.hash_slow(), code.clone()),
+        );
+        db.insert_account_storage(address, StorageKey::from(1), StorageValue::from(10))
+            .unwrap();
+
+        let mut state = State::builder().with_database(RecordingDB::new(db)).build();
+        let info = state.basic(address).unwrap().unwrap();
+        let value = state.storage(address, StorageKey::from(1)).unwrap();
+        let empty = state.storage(address, StorageKey::from(2)).unwrap();
+        let hash = state.block_hash(7).unwrap();
+        assert_eq!(state.basic(missing).unwrap(), None);
+
+        let witness = state.database.take_witness();
+        assert_eq!(witness.accounts.len(), 2);
+        assert_eq!(witness.codes.get(&info.code_hash), Some(&code));
+        assert!(witness.accounts[&address].as_ref().unwrap().code.is_none());
+
+        let mut replay = State::builder()
+            .with_database(WitnessDB::new(witness))
+            .build();
+        let replayed = replay.basic(address).unwrap().unwrap();
+        assert_eq!(replayed, info);
+        assert_eq!(replayed.code, Some(code));
+        assert_eq!(replay.storage(address, StorageKey::from(1)), Ok(value));
+        assert_eq!(replay.storage(address, StorageKey::from(2)), Ok(empty));
+        assert_eq!(replay.block_hash(7), Ok(hash));
+        assert_eq!(replay.basic(missing), Ok(None));
+    }
+
+    #[test]
+    fn missing_keys_error() {
+        let address = Address::with_last_byte(1);
+        let mut witness = ExecutionWitness::default();
+        witness.insert_account(address, Some(AccountInfo::default()));
+        let mut db = WitnessDB::new(witness);
+
+        assert_eq!(
+            db.basic(Address::ZERO),
+            Err(WitnessError::MissingAccount(Address::ZERO))
+        );
+        assert_eq!(
+            db.storage(address, StorageKey::from(1)),
+            Err(WitnessError::MissingStorage(address, StorageKey::from(1)))
+        );
+        assert_eq!(
+            db.code_by_hash(B256::ZERO),
+            Err(WitnessError::MissingCode(B256::ZERO))
+        );
+        assert_eq!(db.block_hash(1), Err(WitnessError::MissingBlockHash(1)));
+    }
+}
@@ -128,6 +128,7 @@ mod tests {
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_get_then_check)]
 mod op_revm_tests;
 
 #[cfg(test)]
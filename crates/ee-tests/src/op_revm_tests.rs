@@ -95,7 +95,7 @@ fn test_halted_deposit_tx() {
 
     // balance should be 100 + previous balance
     assert_eq!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::FailedDeposit,
             gas_used: eip7825::TX_GAS_LIMIT_CAP,
@@ -138,7 +138,7 @@ fn test_tx_call_p256verify() {
     let output = evm.replay().unwrap();
 
     // assert successful call to P256VERIFY
-    assert!(output.result.is_success());
+    assert!(output.result.result.is_success());
 
     compare_or_save_op_testdata("test_tx_call_p256verify.json", &output);
 }
@@ -167,7 +167,7 @@ fn test_halted_tx_call_p256verify() {
 
     // assert out of gas for P256VERIFY
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -208,7 +208,7 @@ fn test_halted_tx_call_bn254_pair_fjord() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -227,7 +227,7 @@ fn test_halted_tx_call_bn254_pair_granite() {
 
     // assert bails early because input size too big
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -261,7 +261,7 @@ fn test_halted_tx_call_bls12_381_g1_add_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -297,7 +297,7 @@ fn test_halted_tx_call_bls12_381_g1_add_input_wrong_size() {
 
     // assert fails post gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -376,7 +376,7 @@ fn test_halted_tx_call_bls12_381_g1_msm_input_wrong_size() {
 
     // assert fails pre gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -423,7 +423,7 @@ fn test_halted_tx_call_bls12_381_g1_msm_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -445,7 +445,7 @@ fn test_halted_tx_call_bls12_381_g1_msm_wrong_input_layout() {
 
     // assert fails post gas check, because input is wrong layout
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -482,7 +482,7 @@ fn test_halted_tx_call_bls12_381_g2_add_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -519,7 +519,7 @@ fn test_halted_tx_call_bls12_381_g2_add_input_wrong_size() {
 
     // assert fails post gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -598,7 +598,7 @@ fn test_halted_tx_call_bls12_381_g2_msm_input_wrong_size() {
 
     // assert fails pre gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -645,7 +645,7 @@ fn test_halted_tx_call_bls12_381_g2_msm_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -667,7 +667,7 @@ fn test_halted_tx_call_bls12_381_g2_msm_wrong_input_layout() {
 
     // assert fails post gas check, because input is wrong layout
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -741,7 +741,7 @@ fn test_halted_tx_call_bls12_381_pairing_input_wrong_size() {
 
     // assert fails pre gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -785,7 +785,7 @@ fn test_halted_tx_call_bls12_381_pairing_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -807,7 +807,7 @@ fn test_tx_call_bls12_381_pairing_wrong_input_layout() {
 
     // assert fails post gas check, because input is wrong layout
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -849,7 +849,7 @@ fn test_halted_tx_call_bls12_381_map_fp_to_g1_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -891,7 +891,7 @@ fn test_halted_tx_call_bls12_381_map_fp_to_g1_input_wrong_size() {
 
     // assert fails post gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -933,7 +933,7 @@ fn test_halted_tx_call_bls12_381_map_fp2_to_g2_out_of_gas() {
 
     // assert out of gas
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::OutOfGas(OutOfGasError::Precompile)),
             ..
@@ -979,7 +979,7 @@ fn test_l1block_load_for_pre_regolith() {
     let output = evm.replay().unwrap();
 
     // assert out of gas
-    assert!(output.result.is_success());
+    assert!(output.result.result.is_success());
 
     compare_or_save_op_testdata("test_l1block_load_for_pre_regolith.json", &output);
 }
@@ -1013,7 +1013,7 @@ fn test_halted_tx_call_bls12_381_map_fp2_to_g2_input_wrong_size() {
 
     // assert fails post gas check, because input is wrong size
     assert!(matches!(
-        output.result,
+        output.result.result,
         ExecutionResult::Halt {
             reason: OpHaltReason::Base(HaltReason::PrecompileErrorWithContext(ref msg)),
             ..
@@ -1069,7 +1069,7 @@ fn test_disable_balance_check() {
         )
         .unwrap();
 
-    assert!(result.is_success());
+    assert!(result.result.is_success());
 
     let returned_balance = U256::from_be_slice(result.output().unwrap().as_ref());
     let expected_balance = U256::ZERO;
@@ -1141,7 +1141,7 @@ fn test_system_call_inspection() {
         .unwrap();
 
     // Should succeed
-    assert!(result.is_success());
+    assert!(result.result.is_success());
 
     // Test system call inspection with caller
     let custom_caller = Address::from([0x12; 20]);
@@ -1150,7 +1150,7 @@ fn test_system_call_inspection() {
         .unwrap();
 
     // Should also succeed
-    assert!(result.is_success());
+    assert!(result.result.is_success());
 
     // Test system call inspection with inspector
     let result = evm
@@ -1162,7 +1162,7 @@ fn test_system_call_inspection() {
         .unwrap();
 
     // Should succeed
-    assert!(result.is_success());
+    assert!(result.result.is_success());
 }
 
 #[test]
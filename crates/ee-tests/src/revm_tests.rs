@@ -3,11 +3,15 @@
 use crate::TestdataConfig;
 use revm::{
     bytecode::opcode,
-    context::{ContextTr, TxEnv},
-    database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET},
+    context::{ContextTr, GasParams, TxEnv},
+    database::{
+        states::bundle_state::BundleRetention, BenchmarkDB, InMemoryDB, RecordingDB, State,
+        WitnessDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET,
+    },
+    handler::instructions::InstructionProvider,
     primitives::{address, b256, hardfork::SpecId, Bytes, TxKind, KECCAK_EMPTY, U256},
-    state::{AccountStatus, Bytecode},
-    Context, ExecuteEvm, MainBuilder, MainContext,
+    state::{AccountInfo, AccountStatus, Bytecode},
+    Context, ExecuteCommitEvm, ExecuteEvm, MainBuilder, MainContext,
 };
 use std::path::PathBuf;
 
@@ -236,6 +240,633 @@ fn test_frame_stack_index() {
     compare_or_save_revm_testdata("test_frame_stack_index.json", &result1);
 }
 
+#[test]
+fn test_gas_params_override() {
+    const SLOAD_BYTECODE: &[u8] = &[opcode::PUSH1, 0x00, opcode::SLOAD, opcode::STOP];
+
+    let gas_used = |gas_params: Option<GasParams>| {
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.gas_params = gas_params)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                SLOAD_BYTECODE.into(),
+            )))
+            .build_mainnet();
+        let result = evm
+            .transact_one(
+                TxEnv::builder_for_bench()
+                    .data(Bytes::from_static(&[0x00, 0x01]))
+                    .build_fill(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+        result.gas_used()
+    };
+
+    // base + one zero and one non-zero calldata byte + PUSH1 + cold SLOAD
+    assert_eq!(gas_used(None), 21_000 + 4 + 16 + 3 + 2_100);
+
+    let params = GasParams {
+        cold_sload: 3_000,
+        tx_token_cost: 8,
+        ..GasParams::new_spec(SpecId::PRAGUE)
+    };
+    assert_eq!(gas_used(Some(params)), 21_000 + 8 + 32 + 3 + 3_000);
+}
+
+#[test]
+fn test_threaded_code_matches_plain() {
+    use revm::interpreter::ThreadedCodeCache;
+    use std::sync::Arc;
+
+    // Counts down from 32, storing the counter in memory and in storage on every iteration.
+    const LOOP_BYTECODE: &[u8] = &[
+        opcode::PUSH1,
+        0x20,
+        opcode::JUMPDEST,
+        opcode::DUP1,
+        opcode::DUP1,
+        opcode::MSTORE,
+        opcode::DUP1,
+        opcode::DUP1,
+        opcode::SSTORE,
+        opcode::PUSH1,
+        0x01,
+        opcode::SWAP1,
+        opcode::SUB,
+        opcode::DUP1,
+        opcode::PUSH1,
+        0x02,
+        opcode::JUMPI,
+        opcode::MSIZE,
+        opcode::PUSH1,
+        0x00,
+        opcode::RETURN,
+    ];
+
+    let run = |cache: Option<Arc<ThreadedCodeCache<_, _>>>, gas_limit: u64| {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                LOOP_BYTECODE.into(),
+            )))
+            .build_mainnet();
+        if let Some(cache) = cache {
+            evm.instruction.threaded_cache = Some(cache);
+        }
+        evm.transact(TxEnv::builder_for_bench().gas_limit(gas_limit).build_fill())
+            .unwrap()
+    };
+
+    let cache = Arc::new(ThreadedCodeCache::default());
+    for gas_limit in [21_100, 30_000, 100_000, 500_000, 1_000_000] {
+        let plain = run(None, gas_limit);
+        let threaded = run(Some(cache.clone()), gas_limit);
+        assert_eq!(threaded, plain, "gas limit {gas_limit}");
+    }
+    assert!(run(None, 1_000_000).result.is_success());
+    assert_eq!(cache.len(), 1);
+
+    // A modified table gets its own entry and leaves the shared ones in place.
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+            LOOP_BYTECODE.into(),
+        )))
+        .build_mainnet();
+    evm.instruction.threaded_cache = Some(cache.clone());
+    let msize = evm.instruction.instruction_table()[opcode::MSIZE as usize];
+    evm.instruction.insert_instruction(opcode::MSIZE, msize);
+    let modified = evm
+        .transact(TxEnv::builder_for_bench().gas_limit(1_000_000).build_fill())
+        .unwrap();
+    assert_eq!(modified, run(None, 1_000_000));
+    assert_eq!(cache.len(), 2);
+    assert!(run(Some(cache.clone()), 1_000_000).result.is_success());
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_execution_limits() {
+    use revm::{
+        context::{
+            result::{ExecutionResult, HaltReason},
+            CfgEnv,
+        },
+        inspector::NoOpInspector,
+        interpreter::ThreadedCodeCache,
+        InspectEvm,
+    };
+    use std::{sync::Arc, time::Duration};
+
+    // Loops forever, three instructions per iteration.
+    const INFINITE_LOOP: &[u8] = &[opcode::JUMPDEST, opcode::PUSH1, 0x00, opcode::JUMP];
+    // Pushes and pops ten times and stops, 21 instructions in total.
+    const STRAIGHT_LINE: &[u8] = &[
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::PUSH0,
+        opcode::POP,
+        opcode::STOP,
+    ];
+
+    let cache = Arc::new(ThreadedCodeCache::default());
+    // Runs the bytecode plain, threaded and inspected, and checks that the results are equal.
+    let run = |code: &[u8], cfg: CfgEnv| {
+        let ctx = || {
+            Context::mainnet()
+                .with_cfg(cfg.clone())
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    Bytes::copy_from_slice(code),
+                )))
+        };
+        let tx = || TxEnv::builder_for_bench().gas_limit(1_000_000).build_fill();
+
+        let plain = ctx().build_mainnet().transact(tx()).unwrap().result;
+
+        let mut evm = ctx().build_mainnet();
+        evm.instruction.threaded_cache = Some(cache.clone());
+        let threaded = evm.transact(tx()).unwrap().result;
+        assert_eq!(threaded, plain);
+
+        let inspected = ctx()
+            .build_mainnet_with_inspector(NoOpInspector)
+            .inspect_one_tx(tx())
+            .unwrap();
+        assert_eq!(inspected, plain);
+        plain
+    };
+
+    let halt_reason = |result: ExecutionResult| match result {
+        ExecutionResult::Halt { reason, .. } => Some(reason),
+        _ => None,
+    };
+
+    let result = run(INFINITE_LOOP, CfgEnv::default().with_step_limit(100));
+    assert_eq!(result.gas_used(), 1_000_000);
+    assert_eq!(halt_reason(result), Some(HaltReason::StepLimitReached));
+
+    // Limit is exact and applies to the whole transaction.
+    assert!(run(STRAIGHT_LINE, CfgEnv::default().with_step_limit(21)).is_success());
+    let result = run(STRAIGHT_LINE, CfgEnv::default().with_step_limit(20));
+    assert_eq!(halt_reason(result), Some(HaltReason::StepLimitReached));
+
+    let result = run(
+        INFINITE_LOOP,
+        CfgEnv::default().with_time_limit(Duration::ZERO),
+    );
+    assert_eq!(halt_reason(result), Some(HaltReason::TimeLimitReached));
+
+    // Without limits the loop runs out of gas.
+    let result = run(INFINITE_LOOP, CfgEnv::default());
+    assert!(matches!(halt_reason(result), Some(HaltReason::OutOfGas(_))));
+}
+
+#[test]
+fn test_execution_snapshot_resume() {
+    use revm::{
+        context::result::EVMError,
+        handler::{
+            EthFrame, EvmTr, ExecutionSnapshot, FrameResult, Handler, ItemOrResult, MainnetContext,
+            MainnetEvm, MainnetHandler,
+        },
+        primitives::Address,
+    };
+    use std::convert::Infallible;
+
+    const CALLEE: Address = address!("0x00000000000000000000000000000000000000cc");
+    // Stores 42 in slot 1 and returns it.
+    const CALLEE_BYTECODE: &[u8] = &[
+        opcode::PUSH1,
+        0x2a,
+        opcode::PUSH1,
+        0x01,
+        opcode::SSTORE,
+        opcode::PUSH1,
+        0x2a,
+        opcode::PUSH1,
+        0x00,
+        opcode::MSTORE,
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH1,
+        0x00,
+        opcode::RETURN,
+    ];
+    // Writes to memory, calls the callee and returns its output together with the written word.
+    let mut caller_bytecode = vec![
+        opcode::PUSH1,
+        0x11,
+        opcode::PUSH1,
+        0x40,
+        opcode::MSTORE,
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH20,
+    ];
+    caller_bytecode.extend_from_slice(CALLEE.as_slice());
+    caller_bytecode.extend_from_slice(&[
+        opcode::GAS,
+        opcode::CALL,
+        opcode::PUSH1,
+        0x60,
+        opcode::PUSH1,
+        0x00,
+        opcode::RETURN,
+    ]);
+
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        BENCH_CALLER,
+        AccountInfo::from_balance(BENCH_CALLER_BALANCE),
+    );
+    for (address, code) in [
+        (BENCH_TARGET, caller_bytecode),
+        (CALLEE, CALLEE_BYTECODE.to_vec()),
+    ] {
+        let code = Bytecode::new_legacy(code.into());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+    }
+    let tx = TxEnv::builder_for_bench().build_fill();
+    let new_evm = || {
+        let mut evm = Context::mainnet().with_db(db.clone()).build_mainnet();
+        evm.ctx.tx = tx.clone();
+        evm
+    };
+    let mut handler = MainnetHandler::<
+        MainnetEvm<MainnetContext<InMemoryDB>>,
+        EVMError<Infallible>,
+        EthFrame,
+    >::default();
+
+    // Run until the callee frame is pushed.
+    let mut evm = new_evm();
+    let init_and_floor_gas = handler.validate(&mut evm).unwrap();
+    let eip7702_refund = handler.pre_execution(&mut evm).unwrap() as i64;
+    let first_frame_input = handler
+        .first_frame_input(&mut evm, tx.gas_limit - init_and_floor_gas.initial_gas)
+        .unwrap();
+    assert!(evm.frame_init(first_frame_input).unwrap().is_item());
+    let ItemOrResult::Item(call) = evm.frame_run().unwrap() else {
+        panic!("expected a call");
+    };
+    assert!(evm.frame_init(call).unwrap().is_item());
+
+    let snapshot = serde_json::to_string(&ExecutionSnapshot::capture(&mut evm)).unwrap();
+    let expected = handler.resume_exec_loop(&mut evm).unwrap();
+
+    // Resume the execution in a new EVM.
+    let mut resumed_evm = new_evm();
+    let snapshot: ExecutionSnapshot = serde_json::from_str(&snapshot).unwrap();
+    assert_eq!(snapshot.frames.len(), 2);
+    assert_eq!(snapshot.journal.depth, 2);
+    snapshot.restore(&mut resumed_evm);
+    let resumed = handler.resume_exec_loop(&mut resumed_evm).unwrap();
+
+    let FrameResult::Call(outcome) = &resumed else {
+        panic!("expected a call outcome");
+    };
+    assert!(outcome.result.is_ok());
+    let mut output = [0u8; 0x60];
+    output[0x1f] = 0x2a;
+    output[0x5f] = 0x11;
+    assert_eq!(outcome.result.output, Bytes::copy_from_slice(&output));
+    assert_eq!(resumed.interpreter_result(), expected.interpreter_result());
+
+    // Both executions finish with the same result and state.
+    let mut finish = |evm: &mut MainnetEvm<MainnetContext<InMemoryDB>>, mut result| {
+        handler.last_frame_result(evm, &mut result).unwrap();
+        handler
+            .post_execution(evm, &mut result, init_and_floor_gas, eip7702_refund)
+            .unwrap();
+        handler.execution_result(evm, result).unwrap()
+    };
+    let expected = finish(&mut evm, expected);
+    let resumed = finish(&mut resumed_evm, resumed);
+    assert!(resumed.is_success());
+    assert_eq!(resumed, expected);
+    assert_eq!(
+        resumed,
+        new_evm().transact(tx.clone()).unwrap().result,
+        "same as an uninterrupted execution"
+    );
+}
+
+#[test]
+fn test_execution_snapshot_resume_step_limit() {
+    use revm::{
+        context::{
+            result::{EVMError, ExecutionResult, HaltReason},
+            CfgEnv,
+        },
+        handler::{
+            EthFrame, EvmTr, ExecutionSnapshot, Handler, ItemOrResult, MainnetContext, MainnetEvm,
+            MainnetHandler,
+        },
+        primitives::Address,
+    };
+    use std::convert::Infallible;
+
+    const CALLEE: Address = address!("0x00000000000000000000000000000000000000cc");
+    // Loops forever.
+    const CALLEE_BYTECODE: &[u8] = &[opcode::JUMPDEST, opcode::PUSH1, 0x00, opcode::JUMP];
+    // Calls the callee and stops, eight instructions up to the call.
+    let mut caller_bytecode = vec![opcode::PUSH0; 5];
+    caller_bytecode.push(opcode::PUSH20);
+    caller_bytecode.extend_from_slice(CALLEE.as_slice());
+    caller_bytecode.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        BENCH_CALLER,
+        AccountInfo::from_balance(BENCH_CALLER_BALANCE),
+    );
+    for (address, code) in [
+        (BENCH_TARGET, caller_bytecode),
+        (CALLEE, CALLEE_BYTECODE.to_vec()),
+    ] {
+        let code = Bytecode::new_legacy(code.into());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+    }
+    let tx = TxEnv::builder_for_bench().gas_limit(1_000_000).build_fill();
+    let new_evm = |cfg: CfgEnv| {
+        let mut evm = Context::mainnet()
+            .with_cfg(cfg)
+            .with_db(db.clone())
+            .build_mainnet();
+        evm.ctx.tx = tx.clone();
+        evm
+    };
+    let mut handler = MainnetHandler::<
+        MainnetEvm<MainnetContext<InMemoryDB>>,
+        EVMError<Infallible>,
+        EthFrame,
+    >::default();
+
+    // Run until the callee frame is pushed.
+    let cfg = CfgEnv::default().with_step_limit(100);
+    let mut evm = new_evm(cfg.clone());
+    let init_and_floor_gas = handler.validate(&mut evm).unwrap();
+    let eip7702_refund = handler.pre_execution(&mut evm).unwrap() as i64;
+    let first_frame_input = handler
+        .first_frame_input(&mut evm, tx.gas_limit - init_and_floor_gas.initial_gas)
+        .unwrap();
+    assert!(evm.frame_init(first_frame_input).unwrap().is_item());
+    let ItemOrResult::Item(call) = evm.frame_run().unwrap() else {
+        panic!("expected a call");
+    };
+    assert!(evm.frame_init(call).unwrap().is_item());
+    let snapshot = ExecutionSnapshot::capture(&mut evm);
+    assert_eq!(snapshot.remaining_steps, Some(92));
+
+    // The remaining steps are restored even if the new EVM has no step limit.
+    let mut resumed_evm = new_evm(CfgEnv::default());
+    snapshot.restore(&mut resumed_evm);
+    let mut resumed = handler.resume_exec_loop(&mut resumed_evm).unwrap();
+    handler
+        .last_frame_result(&mut resumed_evm, &mut resumed)
+        .unwrap();
+    handler
+        .post_execution(
+            &mut resumed_evm,
+            &mut resumed,
+            init_and_floor_gas,
+            eip7702_refund,
+        )
+        .unwrap();
+    let resumed = handler.execution_result(&mut resumed_evm, resumed).unwrap();
+    assert!(matches!(
+        resumed,
+        ExecutionResult::Halt {
+            reason: HaltReason::StepLimitReached,
+            ..
+        }
+    ));
+    assert_eq!(
+        resumed,
+        new_evm(cfg).transact(tx.clone()).unwrap().result,
+        "same as an uninterrupted execution"
+    );
+}
+
+#[test]
+fn test_exec_step_external_call() {
+    use revm::{
+        context::result::EVMError,
+        handler::{
+            EthFrame, FrameResult, Handler, ItemOrResult, MainnetContext, MainnetEvm,
+            MainnetHandler,
+        },
+        interpreter::{
+            CallInputs, CallOutcome, CallValue, FrameInput, Gas, InstructionResult,
+            InterpreterResult,
+        },
+        primitives::Address,
+    };
+    use std::convert::Infallible;
+
+    const CALLEE: Address = address!("0x00000000000000000000000000000000000000cc");
+    // Returns 42.
+    const CALLEE_BYTECODE: &[u8] = &[
+        opcode::PUSH1,
+        0x2a,
+        opcode::PUSH1,
+        0x00,
+        opcode::MSTORE,
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH1,
+        0x00,
+        opcode::RETURN,
+    ];
+    // Calls the callee and returns its output.
+    let mut caller_bytecode = vec![
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH1,
+        0x00,
+        opcode::PUSH20,
+    ];
+    caller_bytecode.extend_from_slice(CALLEE.as_slice());
+    caller_bytecode.extend_from_slice(&[
+        opcode::GAS,
+        opcode::CALL,
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH1,
+        0x00,
+        opcode::RETURN,
+    ]);
+
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        BENCH_CALLER,
+        AccountInfo::from_balance(BENCH_CALLER_BALANCE),
+    );
+    for (address, code) in [
+        (BENCH_TARGET, caller_bytecode),
+        (CALLEE, CALLEE_BYTECODE.to_vec()),
+    ] {
+        let code = Bytecode::new_legacy(code.into());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+    }
+    let tx = TxEnv::builder_for_bench().build_fill();
+    let word = |value: u8| {
+        let mut word = [0u8; 0x20];
+        word[0x1f] = value;
+        Bytes::copy_from_slice(&word)
+    };
+
+    // Executes the transaction step by step, calls to the callee are resolved by `resolve`.
+    let run = |resolve: &dyn Fn(&CallInputs) -> Option<CallOutcome>| {
+        let mut evm = Context::mainnet().with_db(db.clone()).build_mainnet();
+        evm.ctx.tx = tx.clone();
+        let mut handler = MainnetHandler::<
+            MainnetEvm<MainnetContext<InMemoryDB>>,
+            EVMError<Infallible>,
+            EthFrame,
+        >::default();
+
+        let init_and_floor_gas = handler.validate(&mut evm).unwrap();
+        let eip7702_refund = handler.pre_execution(&mut evm).unwrap() as i64;
+        let first_frame_input = handler
+            .first_frame_input(&mut evm, tx.gas_limit - init_and_floor_gas.initial_gas)
+            .unwrap();
+        let mut result = handler
+            .exec_step_frame(&mut evm, first_frame_input)
+            .unwrap();
+        let mut steps = 0;
+        let mut frame_result = loop {
+            if let Some(result) = result.take() {
+                break result;
+            }
+            steps += 1;
+            match handler.exec_step(&mut evm).unwrap() {
+                ItemOrResult::Item(init) => {
+                    let FrameInput::Call(inputs) = &init.frame_input else {
+                        panic!("expected a call");
+                    };
+                    assert_eq!(inputs.target_address, CALLEE);
+                    let Some(outcome) = resolve(inputs) else {
+                        result = handler.exec_step_frame(&mut evm, init).unwrap();
+                        continue;
+                    };
+
+                    // Value transfers and mismatched outcomes are rejected.
+                    let mut with_value = init.clone();
+                    if let FrameInput::Call(inputs) = &mut with_value.frame_input {
+                        inputs.value = CallValue::Transfer(U256::from(1));
+                    }
+                    assert!(handler
+                        .exec_step_outcome(&mut evm, with_value, FrameResult::Call(outcome.clone()))
+                        .is_err());
+                    let create = FrameResult::Create(revm::interpreter::CreateOutcome::new(
+                        outcome.result.clone(),
+                        None,
+                    ));
+                    assert!(handler
+                        .exec_step_outcome(&mut evm, init.clone(), create)
+                        .is_err());
+
+                    handler
+                        .exec_step_outcome(&mut evm, init, FrameResult::Call(outcome))
+                        .unwrap();
+                }
+                ItemOrResult::Result(frame_result) => break frame_result,
+            }
+        };
+        handler
+            .last_frame_result(&mut evm, &mut frame_result)
+            .unwrap();
+        handler
+            .post_execution(
+                &mut evm,
+                &mut frame_result,
+                init_and_floor_gas,
+                eip7702_refund,
+            )
+            .unwrap();
+        let result = handler.execution_result(&mut evm, frame_result).unwrap();
+
+        // There is no frame left to return an outcome to.
+        let outcome = CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Stop, Bytes::new(), Gas::new(0)),
+            0..0,
+        );
+        let init = handler
+            .first_frame_input(&mut evm, tx.gas_limit - init_and_floor_gas.initial_gas)
+            .unwrap();
+        assert!(handler
+            .exec_step_outcome(&mut evm, init, FrameResult::Call(outcome))
+            .is_err());
+        (result, steps)
+    };
+
+    // Callee is executed by the EVM, same as a regular transaction.
+    let expected = Context::mainnet()
+        .with_db(db.clone())
+        .build_mainnet()
+        .transact_one(tx.clone())
+        .unwrap();
+    let (internal, steps) = run(&|_| None);
+    assert_eq!(internal, expected);
+    assert_eq!(internal.output(), Some(&word(0x2a)));
+    // Steps end on the call and at the end of the execution.
+    assert_eq!(steps, 2);
+
+    // Callee is resolved externally, returning 0x99 and all of its gas.
+    let (external, steps) = run(&|inputs| {
+        Some(CallOutcome::new(
+            InterpreterResult::new(
+                InstructionResult::Return,
+                word(0x99),
+                Gas::new(inputs.gas_limit),
+            ),
+            inputs.return_memory_offset.clone(),
+        ))
+    });
+    assert_eq!(steps, 2);
+    assert!(external.is_success());
+    assert_eq!(external.output(), Some(&word(0x99)));
+    assert!(external.gas_used() < expected.gas_used());
+}
+
 #[test]
 #[cfg(feature = "optional_balance_check")]
 fn test_disable_balance_check() {
@@ -283,3 +914,88 @@ fn test_disable_balance_check() {
     let expected_balance = U256::ZERO;
     assert_eq!(returned_balance, expected_balance);
 }
+
+#[test]
+fn test_stateless_reexecution_from_witness() {
+    // Increments slot 0 and stores the hash of the previous block in slot 1.
+    const BYTECODE: &[u8] = &[
+        opcode::PUSH1,
+        0x00,
+        opcode::SLOAD,
+        opcode::PUSH1,
+        0x01,
+        opcode::ADD,
+        opcode::PUSH1,
+        0x00,
+        opcode::SSTORE,
+        opcode::PUSH1,
+        0x01,
+        opcode::NUMBER,
+        opcode::SUB,
+        opcode::BLOCKHASH,
+        opcode::PUSH1,
+        0x01,
+        opcode::SSTORE,
+        opcode::STOP,
+    ];
+
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        BENCH_CALLER,
+        AccountInfo::from_balance(BENCH_CALLER_BALANCE),
+    );
+    let code = Bytecode::new_legacy(BYTECODE.into());
+    db.insert_account_info(
+        BENCH_TARGET,
+        AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+    );
+    db.insert_account_storage(BENCH_TARGET, U256::ZERO, U256::from(41))
+        .unwrap();
+
+    /// Executes two transactions calling the contract and returns the results and the bundle.
+    fn execute_block<DB: revm::Database>(
+        state: State<DB>,
+    ) -> (Vec<revm::context::result::ExecutionResult>, State<DB>)
+    where
+        DB::Error: core::fmt::Debug,
+    {
+        let mut evm = Context::mainnet()
+            .modify_block_chained(|block| block.number = U256::from(10))
+            .with_db(state)
+            .build_mainnet();
+        let results = (0..2)
+            .map(|nonce| {
+                evm.transact_commit(TxEnv::builder_for_bench().nonce(nonce).build_fill())
+                    .unwrap()
+            })
+            .collect();
+        let mut state = evm.ctx.journaled_state.database;
+        state.merge_transitions(BundleRetention::Reverts);
+        (results, state)
+    }
+
+    let state = State::builder()
+        .with_database(RecordingDB::new(db))
+        .with_bundle_update()
+        .build();
+    let (results, mut state) = execute_block(state);
+    let bundle = state.take_bundle();
+    let witness = state.database.take_witness();
+    assert_eq!(witness.storage[&BENCH_TARGET].len(), 2);
+    assert_eq!(witness.block_hashes.len(), 1);
+
+    let stateless = State::builder()
+        .with_database(WitnessDB::new(witness))
+        .with_bundle_update()
+        .build();
+    let (stateless_results, mut stateless) = execute_block(stateless);
+    assert_eq!(stateless_results, results);
+    assert_eq!(stateless.take_bundle(), bundle);
+    assert_eq!(
+        bundle
+            .account(&BENCH_TARGET)
+            .unwrap()
+            .storage_slot(U256::ZERO),
+        Some(U256::from(43))
+    );
+}
@@ -0,0 +1,464 @@
+//! Declarative chain specification.
+//!
+//! [`ChainSpec`] describes an EVM variant as data: the base [`CfgEnv`], opcodes to disable and
+//! precompiles to add or remove. With the `serde` feature it can be loaded from any serde format
+//! (e.g. JSON or TOML) and turned into a ready [`Evm`] with [`ChainSpec::build_evm`], without
+//! writing a new handler type.
+use crate::{
+    frame::EthFrame, instructions::EthInstructions, precompile_provider::run_precompile,
+    MainnetContext, PrecompileProvider,
+};
+use context::{Cfg, CfgEnv, Context, ContextTr, Evm, FrameStack};
+use context_interface::Database;
+use core::fmt;
+use interpreter::{
+    interpreter::EthInterpreter, CallInputs, Host, InstructionTableBuilder, InterpreterResult,
+    InterpreterTypes,
+};
+use precompile::{PrecompileSpecId, Precompiles};
+use primitives::{hardfork::SpecId, Address};
+use std::{boxed::Box, string::String, vec::Vec};
+
+/// Type alias for an EVM built from a [`ChainSpec`].
+pub type ChainSpecEvm<CTX, INSP = ()> = Evm<
+    CTX,
+    INSP,
+    EthInstructions<EthInterpreter, CTX>,
+    ChainPrecompiles,
+    EthFrame<EthInterpreter>,
+>;
+
+/// Data-driven description of an EVM variant.
+///
+/// Missing fields are set to their default value when deserialized, so an empty document
+/// describes mainnet at the default [`SpecId`]. A `cfg` is deserialized as a [`CfgEnv`] and has
+/// to be complete. Opcodes are deserialized from their names, e.g. `"SELFDESTRUCT"`.
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "cfg": { "spec": "PRAGUE", "chain_id": 1337, "base_fee_recipient": "0x…", … },
+///   "disabled_opcodes": ["SELFDESTRUCT", "BLOBHASH"],
+///   "removed_precompiles": ["0x0000000000000000000000000000000000000009"]
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChainSpec {
+    /// Base configuration: spec, chain ID, gas limits, fee recipients and validation toggles.
+    pub cfg: CfgEnv,
+    /// Opcodes that halt with [`NotActivated`](interpreter::InstructionResult::NotActivated).
+    #[cfg_attr(feature = "serde", serde(with = "opcode_names"))]
+    pub disabled_opcodes: Vec<u8>,
+    /// If set, only these opcodes are enabled and all others halt with
+    /// [`NotActivated`](interpreter::InstructionResult::NotActivated).
+    ///
+    /// [`disabled_opcodes`](Self::disabled_opcodes) still applies to the listed opcodes.
+    #[cfg_attr(feature = "serde", serde(with = "opcode_names::option"))]
+    pub enabled_opcodes: Option<Vec<u8>>,
+    /// Precompiles enabled regardless of the spec.
+    ///
+    /// Addresses must be precompiles of [`Precompiles::latest`].
+    pub added_precompiles: Vec<Address>,
+    /// Precompiles disabled regardless of the spec.
+    pub removed_precompiles: Vec<Address>,
+}
+
+impl ChainSpec {
+    /// Creates a new chain spec with the given spec and no other changes to mainnet.
+    pub fn new(spec: SpecId) -> Self {
+        Self {
+            cfg: CfgEnv::new_with_spec(spec),
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether the opcode is enabled in this chain spec.
+    pub fn is_opcode_enabled(&self, opcode: u8) -> bool {
+        !self.disabled_opcodes.contains(&opcode)
+            && self
+                .enabled_opcodes
+                .as_ref()
+                .is_none_or(|enabled| enabled.contains(&opcode))
+    }
+
+    /// Returns the mainnet instruction table with the disabled opcodes, see
+    /// [`InstructionTableBuilder::disable`].
+    pub fn instructions<WIRE, HOST>(&self) -> EthInstructions<WIRE, HOST>
+    where
+        WIRE: InterpreterTypes,
+        HOST: Host,
+    {
+        let builder = (0..=u8::MAX)
+            .filter(|opcode| !self.is_opcode_enabled(*opcode))
+            .fold(InstructionTableBuilder::mainnet(), |builder, opcode| {
+                builder.disable(opcode)
+            });
+        EthInstructions::new(builder.build(self.cfg.spec))
+    }
+
+    /// Returns the precompile provider of this chain spec.
+    ///
+    /// Fails if an added precompile is not known.
+    pub fn precompiles(&self) -> Result<ChainPrecompiles, ChainSpecError> {
+        let latest = Precompiles::latest();
+        if let Some(address) = self
+            .added_precompiles
+            .iter()
+            .find(|address| !latest.contains(address))
+        {
+            return Err(ChainSpecError::UnknownPrecompile(*address));
+        }
+        Ok(ChainPrecompiles::new(
+            self.cfg.spec,
+            self.added_precompiles.clone(),
+            self.removed_precompiles.clone(),
+        ))
+    }
+
+    /// Builds an EVM for this chain spec over the given database.
+    pub fn build_evm<DB: Database>(
+        &self,
+        db: DB,
+    ) -> Result<ChainSpecEvm<MainnetContext<DB>>, ChainSpecError> {
+        self.build_evm_with_inspector(db, ())
+    }
+
+    /// Builds an EVM for this chain spec over the given database, with the provided inspector.
+    pub fn build_evm_with_inspector<DB: Database, INSP>(
+        &self,
+        db: DB,
+        inspector: INSP,
+    ) -> Result<ChainSpecEvm<MainnetContext<DB>, INSP>, ChainSpecError> {
+        Ok(Evm {
+            ctx: Context::new(db, self.cfg.spec).with_cfg(self.cfg.clone()),
+            inspector,
+            instruction: self.instructions(),
+            precompiles: self.precompiles()?,
+            frame_stack: FrameStack::new_prealloc(8),
+        })
+    }
+}
+
+/// Error returned when a [`ChainSpec`] is invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainSpecError {
+    /// Added precompile address is not a known precompile.
+    UnknownPrecompile(Address),
+}
+
+impl fmt::Display for ChainSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPrecompile(address) => write!(f, "unknown precompile {address}"),
+        }
+    }
+}
+
+impl core::error::Error for ChainSpecError {}
+
+/// The [`PrecompileProvider`] of a [`ChainSpec`].
+///
+/// Contains the precompiles of the current spec, with precompiles added and removed by the
+/// chain spec.
+#[derive(Clone, Debug)]
+pub struct ChainPrecompiles {
+    /// Precompiles for the current spec.
+    pub precompiles: Precompiles,
+    /// Current spec.
+    pub spec: SpecId,
+    /// Precompiles enabled regardless of the spec.
+    pub added: Vec<Address>,
+    /// Precompiles disabled regardless of the spec.
+    pub removed: Vec<Address>,
+}
+
+impl ChainPrecompiles {
+    /// Creates the precompiles of the given spec with added and removed precompiles.
+    ///
+    /// Added addresses that are not precompiles of [`Precompiles::latest`] are ignored.
+    pub fn new(spec: SpecId, added: Vec<Address>, removed: Vec<Address>) -> Self {
+        let mut this = Self {
+            precompiles: Precompiles::default(),
+            spec,
+            added,
+            removed,
+        };
+        this.precompiles = this.build(spec);
+        this
+    }
+
+    fn build(&self, spec: SpecId) -> Precompiles {
+        let base = Precompiles::new(PrecompileSpecId::from_spec_id(spec));
+        let latest = Precompiles::latest();
+        let added = self
+            .added
+            .iter()
+            .filter_map(|address| latest.get(address).cloned());
+
+        let mut precompiles = Precompiles::default();
+        precompiles.extend(
+            base.inner()
+                .values()
+                .cloned()
+                .chain(added)
+                .filter(|precompile| !self.removed.contains(precompile.address())),
+        );
+        precompiles
+    }
+}
+
+impl<CTX: ContextTr> PrecompileProvider<CTX> for ChainPrecompiles {
+    type Output = InterpreterResult;
+
+    fn set_spec(&mut self, spec: <CTX::Cfg as Cfg>::Spec) -> bool {
+        let spec = spec.into();
+        if spec == self.spec {
+            return false;
+        }
+        self.precompiles = self.build(spec);
+        self.spec = spec;
+        true
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CallInputs,
+    ) -> Result<Option<InterpreterResult>, String> {
+        run_precompile(&self.precompiles, context, inputs)
+    }
+
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        Box::new(self.precompiles.addresses().cloned())
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.precompiles.contains(address)
+    }
+}
+
+/// Serializes opcodes as their names.
+#[cfg(feature = "serde")]
+mod opcode_names {
+    use bytecode::opcode::OpCode;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use std::{borrow::Cow, format, vec::Vec};
+
+    pub(super) fn serialize<S: Serializer>(
+        opcodes: &[u8],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        opcodes
+            .iter()
+            .map(|&opcode| {
+                OpCode::new(opcode).map(|op| op.as_str()).ok_or_else(|| {
+                    serde::ser::Error::custom(format!("unknown opcode {opcode:#04x}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        Vec::<Cow<'de, str>>::deserialize(deserializer)?
+            .iter()
+            .map(|name| {
+                OpCode::parse(name)
+                    .map(|op| op.get())
+                    .ok_or_else(|| D::Error::custom(format!("unknown opcode {name}")))
+            })
+            .collect()
+    }
+
+    pub(super) mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+        use std::vec::Vec;
+
+        pub(in super::super) fn serialize<S: Serializer>(
+            opcodes: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match opcodes {
+                Some(opcodes) => super::serialize(opcodes, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(in super::super) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            #[derive(Deserialize)]
+            struct Opcodes(#[serde(with = "super")] Vec<u8>);
+
+            Ok(Option::<Opcodes>::deserialize(deserializer)?.map(|opcodes| opcodes.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExecuteEvm;
+    use bytecode::{
+        opcode::{CALL, PUSH0, PUSH1, PUSH2, SSTORE, STOP},
+        Bytecode,
+    };
+    use context::TxEnv;
+    use context_interface::result::{ExecutionResult, HaltReason};
+    use database::{CacheDB, EmptyDB, BENCH_CALLER, BENCH_TARGET};
+    use primitives::{address, Bytes, TxKind, U256};
+    use state::AccountInfo;
+
+    const BLAKE2F: Address = address!("0x0000000000000000000000000000000000000009");
+    const P256VERIFY: Address = address!("0x0000000000000000000000000000000000000100");
+
+    fn db_with_code(code: Vec<u8>) -> CacheDB<EmptyDB> {
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(
+            BENCH_TARGET,
+            AccountInfo::default().with_code(Bytecode::new_legacy(Bytes::from(code))),
+        );
+        db.insert_account_info(
+            BENCH_CALLER,
+            AccountInfo::default().with_balance(U256::from(10).pow(U256::from(18))),
+        );
+        db
+    }
+
+    fn call_tx() -> TxEnv {
+        TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_price(10)
+            .gas_limit(100_000)
+            .build_fill()
+    }
+
+    fn call_target(spec: &ChainSpec, code: Vec<u8>) -> ExecutionResult {
+        let mut evm = spec.build_evm(db_with_code(code)).unwrap();
+        evm.transact(call_tx()).unwrap().result
+    }
+
+    /// Calls `precompile` with no input and 10000 gas, and stores the success flag in slot 0.
+    fn call_precompile_code(precompile: Address) -> Vec<u8> {
+        let mut code = vec![PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, 0x73];
+        code.extend_from_slice(precompile.as_slice());
+        code.extend_from_slice(&[PUSH2, 0x27, 0x10, CALL, PUSH0, SSTORE, STOP]);
+        code
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_json() {
+        let mut cfg = serde_json::to_value(CfgEnv::new_with_spec(SpecId::CANCUN)).unwrap();
+        cfg["chain_id"] = 1337.into();
+        let spec: ChainSpec = serde_json::from_value(serde_json::json!({
+            "cfg": cfg,
+            "disabled_opcodes": ["SELFDESTRUCT", "BLOBHASH"],
+            "added_precompiles": ["0x0000000000000000000000000000000000000100"]
+        }))
+        .unwrap();
+
+        assert_eq!(spec.cfg.spec, SpecId::CANCUN);
+        assert_eq!(spec.cfg.chain_id, 1337);
+        assert_eq!(spec.disabled_opcodes, vec![0xff, 0x49]);
+        assert_eq!(spec.enabled_opcodes, None);
+        assert_eq!(spec.added_precompiles, vec![P256VERIFY]);
+
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(json.contains(r#""disabled_opcodes":["SELFDESTRUCT","BLOBHASH"]"#));
+        assert_eq!(serde_json::from_str::<ChainSpec>(&json).unwrap(), spec);
+
+        assert!(serde_json::from_str::<ChainSpec>(r#"{ "disabled_opcodes": ["NOPE"] }"#).is_err());
+        // The cfg is not completed with default values.
+        assert!(serde_json::from_str::<ChainSpec>(r#"{ "cfg": { "chain_id": 1 } }"#).is_err());
+    }
+
+    #[test]
+    fn disabled_opcode_halts() {
+        let code = vec![PUSH1, 1, PUSH0, SSTORE, STOP];
+        let result = call_target(&ChainSpec::new(SpecId::PRAGUE), code.clone());
+        assert!(result.is_success());
+
+        let spec = ChainSpec {
+            disabled_opcodes: vec![SSTORE],
+            ..ChainSpec::new(SpecId::PRAGUE)
+        };
+        let result = call_target(&spec, code.clone());
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::NotActivated,
+                ..
+            }
+        ));
+
+        let spec = ChainSpec {
+            enabled_opcodes: Some(vec![PUSH1, PUSH0, STOP]),
+            ..ChainSpec::new(SpecId::PRAGUE)
+        };
+        let result = call_target(&spec, code);
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::NotActivated,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn added_and_removed_precompiles() {
+        let spec = ChainSpec {
+            added_precompiles: vec![P256VERIFY],
+            removed_precompiles: vec![BLAKE2F],
+            ..ChainSpec::new(SpecId::CANCUN)
+        };
+        let precompiles = spec.precompiles().unwrap();
+        assert!(precompiles.precompiles.contains(&P256VERIFY));
+        assert!(!precompiles.precompiles.contains(&BLAKE2F));
+
+        // BLAKE2F fails on empty input, while calling the removed precompile succeeds.
+        for (spec, success) in [(ChainSpec::new(SpecId::CANCUN), 0), (spec, 1)] {
+            let mut evm = spec
+                .build_evm(db_with_code(call_precompile_code(BLAKE2F)))
+                .unwrap();
+            let state = evm.transact(call_tx()).unwrap().state;
+            assert_eq!(
+                state[&BENCH_TARGET].storage[&U256::ZERO].present_value,
+                U256::from(success)
+            );
+        }
+
+        let spec = ChainSpec {
+            added_precompiles: vec![address!("0x00000000000000000000000000000000000000ff")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            spec.precompiles(),
+            Err(ChainSpecError::UnknownPrecompile(_))
+        ));
+    }
+
+    #[test]
+    fn base_fee_recipient_is_credited() {
+        let recipient = address!("0x000000000000000000000000000000000000fee5");
+        let mut spec = ChainSpec::new(SpecId::PRAGUE);
+        spec.cfg = spec.cfg.with_base_fee_recipient(recipient);
+
+        let mut evm = spec.build_evm(db_with_code(vec![STOP])).unwrap();
+        evm.ctx.block.basefee = 7;
+        let output = evm.transact(call_tx()).unwrap();
+        let gas_used = output.result.gas_used();
+
+        assert_eq!(
+            output.state[&recipient].info.balance,
+            U256::from(7 * gas_used)
+        );
+    }
+}
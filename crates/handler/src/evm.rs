@@ -3,9 +3,12 @@ use crate::{
     ItemOrResult, PrecompileProvider,
 };
 use auto_impl::auto_impl;
-use context::{ContextTr, Database, Evm, FrameStack};
+use context::{ContextTr, Database, Evm, FrameStack, LocalContextTr};
 use context_interface::context::ContextError;
-use interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, InterpreterResult};
+use interpreter::{
+    interpreter::EthInterpreter, interpreter_action::FrameInit, interpreter_types::LoopControl,
+    InterpreterResult,
+};
 
 /// Type alias for database error within a context
 pub type ContextDbError<CTX> = ContextError<ContextTrDbError<CTX>>;
@@ -202,9 +205,30 @@ where
         let context = &mut self.ctx;
         let instructions = &mut self.instruction;
 
-        let action = frame
+        // Execution budget is shared by all frames of the transaction.
+        frame
+            .interpreter
+            .bytecode
+            .set_execution_budget(context.local().execution_budget());
+
+        let threaded = frame
             .interpreter
-            .run_plain(instructions.instruction_table(), context);
+            .bytecode
+            .hash()
+            .and_then(|hash| instructions.threaded_code(hash, &frame.interpreter.bytecode));
+        let action = match threaded {
+            Some(code) => {
+                frame
+                    .interpreter
+                    .run_threaded(&code, instructions.instruction_table(), context)
+            }
+            None => frame
+                .interpreter
+                .run_plain(instructions.instruction_table(), context),
+        };
+        context
+            .local_mut()
+            .set_execution_budget(frame.interpreter.bytecode.execution_budget());
 
         frame.process_next_action(context, action).inspect(|i| {
             if i.is_result() {
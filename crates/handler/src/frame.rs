@@ -38,6 +38,14 @@ use std::{borrow::ToOwned, boxed::Box, vec::Vec};
     <IW as InterpreterTypes>::RuntimeFlag,
     <IW as InterpreterTypes>::Extend,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "Interpreter<IW>: serde::Serialize",
+        deserialize = "Interpreter<IW>: serde::Deserialize<'de>"
+    ))
+)]
 pub struct EthFrame<IW: InterpreterTypes = EthInterpreter> {
     /// Frame-specific data (Call, Create, or EOFCreate).
     pub data: FrameData,
@@ -1,6 +1,6 @@
 use crate::{
-    evm::FrameTr, execution, post_execution, pre_execution, validation, EvmTr, FrameResult,
-    ItemOrResult,
+    evm::FrameTr, execution, post_execution, pre_execution, validation, EvmTr, FrameInitOrResult,
+    FrameResult, ItemOrResult,
 };
 use context::{
     result::{ExecutionResult, FromStringError},
@@ -9,9 +9,11 @@ use context::{
 use context_interface::{
     context::ContextError,
     result::{HaltReasonTr, InvalidHeader, InvalidTransaction},
-    Cfg, ContextTr, Database, JournalTr, Transaction,
+    Cfg, ContextTr, Database, ExecutionBudget, JournalTr, Transaction,
+};
+use interpreter::{
+    interpreter_action::FrameInit, FrameInput, Gas, InitialAndFloorGas, SharedMemory,
 };
-use interpreter::{interpreter_action::FrameInit, Gas, InitialAndFloorGas, SharedMemory};
 use primitives::U256;
 use state::Bytecode;
 
@@ -252,9 +254,10 @@ pub trait Handler {
     #[inline]
     fn validate_initial_tx_gas(&self, evm: &Self::Evm) -> Result<InitialAndFloorGas, Self::Error> {
         let ctx = evm.ctx_ref();
-        validation::validate_initial_tx_gas(
+        validation::validate_initial_tx_gas_with_params(
             ctx.tx(),
             ctx.cfg().spec().into(),
+            ctx.cfg().gas_params(),
             ctx.cfg().is_eip7623_disabled(),
         )
         .map_err(From::from)
@@ -300,6 +303,8 @@ pub trait Handler {
         let ctx = evm.ctx_mut();
         let mut memory = SharedMemory::new_with_buffer(ctx.local().shared_memory_buffer().clone());
         memory.set_memory_limit(ctx.cfg().memory_limit());
+        let budget = ExecutionBudget::new(ctx.cfg().step_limit(), ctx.cfg().time_limit());
+        ctx.local_mut().set_execution_budget(budget);
 
         let (tx, journal) = ctx.tx_journal_mut();
         let bytecode = if let Some(&to) = tx.kind().to() {
@@ -375,6 +380,15 @@ pub trait Handler {
             return Ok(frame_result);
         }
 
+        self.resume_exec_loop(evm)
+    }
+
+    /// Continues the frame processing loop with the frames that are already on the frame stack.
+    ///
+    /// The current frame must be ready to run, e.g. restored from an
+    /// [`ExecutionSnapshot`](crate::ExecutionSnapshot).
+    #[inline]
+    fn resume_exec_loop(&mut self, evm: &mut Self::Evm) -> Result<FrameResult, Self::Error> {
         loop {
             let call_or_result = evm.frame_run()?;
 
@@ -397,6 +411,95 @@ pub trait Handler {
         }
     }
 
+    /// Runs the frames on the frame stack until a frame requests a new frame or the execution
+    /// finishes.
+    ///
+    /// This is a step-wise alternative to [`Handler::resume_exec_loop`] that returns control to
+    /// the caller on every call or create. Requested frame is continued with
+    /// [`Handler::exec_step_frame`] to execute it in the EVM, or with
+    /// [`Handler::exec_step_outcome`] to provide its result from the outside.
+    ///
+    /// Returns [`ItemOrResult::Result`] with the result of the first frame when execution is done.
+    #[inline]
+    fn exec_step(
+        &mut self,
+        evm: &mut Self::Evm,
+    ) -> Result<FrameInitOrResult<<Self::Evm as EvmTr>::Frame>, Self::Error> {
+        loop {
+            let result = match evm.frame_run()? {
+                ItemOrResult::Item(init) => return Ok(ItemOrResult::Item(init)),
+                ItemOrResult::Result(result) => result,
+            };
+            if let Some(result) = evm.frame_return_result(result)? {
+                return Ok(ItemOrResult::Result(result));
+            }
+        }
+    }
+
+    /// Initializes the requested frame in the EVM, the frame is run by the next
+    /// [`Handler::exec_step`].
+    ///
+    /// Used for the first frame too. Returns the result of the first frame if it finished
+    /// without running, e.g. a call to a precompile.
+    #[inline]
+    fn exec_step_frame(
+        &mut self,
+        evm: &mut Self::Evm,
+        frame_input: <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameInit,
+    ) -> Result<Option<FrameResult>, Self::Error> {
+        let is_first = evm.frame_stack().index().is_none();
+        match evm.frame_init(frame_input)? {
+            ItemOrResult::Item(_) => Ok(None),
+            ItemOrResult::Result(result) if is_first => Ok(Some(result)),
+            // Frame was not created, result is returned to the current frame.
+            ItemOrResult::Result(result) => Ok(evm.frame_return_result(result)?),
+        }
+    }
+
+    /// Returns an externally provided result of the requested frame to the current frame.
+    ///
+    /// `frame_input` is the request returned by [`Handler::exec_step`]. The outcome has to match
+    /// it: [`FrameResult::Call`] with the return memory range of the call for a call,
+    /// [`FrameResult::Create`] for a create.
+    ///
+    /// The requested frame is not initialized in the EVM: no journal checkpoint is created, the
+    /// call depth and the caller balance are not checked and the state is not changed, only the
+    /// gas, output and status of the outcome are applied to the current frame. As value
+    /// transfers can't be skipped, requests that transfer value are rejected.
+    ///
+    /// Returns an error if there is no current frame, if the request transfers value or if the
+    /// outcome does not match the request.
+    #[inline]
+    fn exec_step_outcome(
+        &mut self,
+        evm: &mut Self::Evm,
+        frame_input: FrameInit,
+        outcome: FrameResult,
+    ) -> Result<(), Self::Error> {
+        if evm.frame_stack().index().is_none() {
+            return Err(Self::Error::from_string(
+                "there is no frame to return the outcome to".into(),
+            ));
+        }
+        let transfers_value = match (&frame_input.frame_input, &outcome) {
+            (FrameInput::Call(inputs), FrameResult::Call(_)) => inputs.transfers_value(),
+            (FrameInput::Create(inputs), FrameResult::Create(_)) => !inputs.value.is_zero(),
+            _ => {
+                return Err(Self::Error::from_string(
+                    "outcome does not match the requested frame".into(),
+                ))
+            }
+        };
+        if transfers_value {
+            return Err(Self::Error::from_string(
+                "externally resolved frame can't transfer value".into(),
+            ));
+        }
+        let result = evm.frame_return_result(outcome)?;
+        debug_assert!(result.is_none());
+        Ok(())
+    }
+
     /* POST EXECUTION */
 
     /// Validates that the minimum gas floor requirements are satisfied.
@@ -435,14 +538,16 @@ pub trait Handler {
             .map_err(From::from)
     }
 
-    /// Transfers transaction fees to the block beneficiary's account.
+    /// Transfers transaction fees to the block beneficiary's account, and the base fee to the
+    /// [`Cfg::base_fee_recipient`] if it is set.
     #[inline]
     fn reward_beneficiary(
         &self,
         evm: &mut Self::Evm,
         exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
-        post_execution::reward_beneficiary(evm.ctx(), exec_result.gas()).map_err(From::from)
+        post_execution::reward_beneficiary(evm.ctx(), exec_result.gas())?;
+        post_execution::reward_base_fee_recipient(evm.ctx(), exec_result.gas()).map_err(From::from)
     }
 
     /// Processes the final execution output.
@@ -1,9 +1,28 @@
 use auto_impl::auto_impl;
+use bytecode::Bytecode;
+#[cfg(feature = "std")]
+use interpreter::ThreadedCodeCache;
 use interpreter::{
     instructions::{instruction_table, InstructionTable},
-    Host, Instruction, InterpreterTypes,
+    Host, Instruction, InterpreterTypes, ThreadedCode,
 };
-use std::boxed::Box;
+use primitives::B256;
+use std::{boxed::Box, sync::Arc};
+
+/// Identifier of the unmodified mainnet instruction table in the threaded code cache.
+#[cfg(feature = "std")]
+const MAINNET_TABLE_ID: u64 = 0;
+
+/// Source of [`EthInstructions`] table identifiers used to key the threaded code cache.
+#[cfg(feature = "std")]
+static NEXT_TABLE_ID: core::sync::atomic::AtomicU64 =
+    core::sync::atomic::AtomicU64::new(MAINNET_TABLE_ID + 1);
+
+/// Returns a new, process-wide unique instruction table identifier.
+#[cfg(feature = "std")]
+fn next_table_id() -> u64 {
+    NEXT_TABLE_ID.fetch_add(1, core::sync::atomic::Ordering::Relaxed)
+}
 
 /// Stores instructions for EVM.
 #[auto_impl(&, Arc, Rc)]
@@ -15,13 +34,38 @@ pub trait InstructionProvider {
 
     /// Returns the instruction table that is used by EvmTr to execute instructions.
     fn instruction_table(&self) -> &InstructionTable<Self::InterpreterTypes, Self::Context>;
+
+    /// Returns threaded code of the bytecode translated with [`Self::instruction_table`].
+    ///
+    /// If `None` is returned, the bytecode is executed instruction by instruction.
+    #[inline]
+    fn threaded_code(
+        &self,
+        code_hash: B256,
+        bytecode: &Bytecode,
+    ) -> Option<Arc<ThreadedCode<Self::InterpreterTypes, Self::Context>>> {
+        let _ = (code_hash, bytecode);
+        None
+    }
 }
 
 /// Ethereum instruction contains list of mainnet instructions that is used for Interpreter execution.
 #[derive(Debug)]
 pub struct EthInstructions<WIRE: InterpreterTypes, HOST: ?Sized> {
     /// Table containing instruction implementations indexed by opcode.
-    pub instruction_table: Box<InstructionTable<WIRE, HOST>>,
+    ///
+    /// Changed only through [`EthInstructions::insert_instruction`] and
+    /// [`EthInstructions::instruction_table_mut`], so code translated with the old table is not
+    /// reused from the threaded code cache.
+    instruction_table: Box<InstructionTable<WIRE, HOST>>,
+    /// Cache of threaded code translated with the instruction table.
+    ///
+    /// If set, bytecode with a known hash is executed with the threaded code backend.
+    #[cfg(feature = "std")]
+    pub threaded_cache: Option<Arc<ThreadedCodeCache<WIRE, HOST>>>,
+    /// Identifier of the instruction table in the threaded code cache.
+    #[cfg(feature = "std")]
+    table_id: u64,
 }
 
 impl<WIRE, HOST: Host + ?Sized> Clone for EthInstructions<WIRE, HOST>
@@ -31,6 +75,10 @@ where
     fn clone(&self) -> Self {
         Self {
             instruction_table: self.instruction_table.clone(),
+            #[cfg(feature = "std")]
+            threaded_cache: self.threaded_cache.clone(),
+            #[cfg(feature = "std")]
+            table_id: self.table_id,
         }
     }
 }
@@ -41,22 +89,61 @@ where
     HOST: Host,
 {
     /// Returns `EthInstructions` with mainnet spec.
+    ///
+    /// All mainnet tables share the same identifier, so EVMs built with them share the entries
+    /// of a threaded code cache.
     pub fn new_mainnet() -> Self {
-        Self::new(instruction_table::<WIRE, HOST>())
+        let this = Self::new(instruction_table::<WIRE, HOST>());
+        #[cfg(feature = "std")]
+        let this = Self {
+            table_id: MAINNET_TABLE_ID,
+            ..this
+        };
+        this
     }
 
     /// Returns a new instance of `EthInstructions` with custom instruction table.
+    ///
+    /// The table gets a new identifier in the threaded code cache, it is shared only with clones.
     #[inline]
     pub fn new(base_table: InstructionTable<WIRE, HOST>) -> Self {
         Self {
             instruction_table: Box::new(base_table),
+            #[cfg(feature = "std")]
+            threaded_cache: None,
+            #[cfg(feature = "std")]
+            table_id: next_table_id(),
         }
     }
 
+    /// Sets the threaded code cache, bytecode found in the cache is executed as threaded code.
+    ///
+    /// The cache can be shared between EVMs, entries are keyed by the instruction table.
+    #[cfg(feature = "std")]
+    pub fn with_threaded_cache(mut self, cache: Arc<ThreadedCodeCache<WIRE, HOST>>) -> Self {
+        self.threaded_cache = Some(cache);
+        self
+    }
+
     /// Inserts a new instruction into the instruction table.
+    ///
+    /// The table gets a new identifier, so threaded code translated with the old table is no
+    /// longer used. Other users of the threaded code cache are not affected.
     #[inline]
     pub fn insert_instruction(&mut self, opcode: u8, instruction: Instruction<WIRE, HOST>) {
-        self.instruction_table[opcode as usize] = instruction;
+        self.instruction_table_mut()[opcode as usize] = instruction;
+    }
+
+    /// Returns a mutable reference to the instruction table.
+    ///
+    /// The table gets a new identifier, same as with [`EthInstructions::insert_instruction`].
+    #[inline]
+    pub fn instruction_table_mut(&mut self) -> &mut InstructionTable<WIRE, HOST> {
+        #[cfg(feature = "std")]
+        {
+            self.table_id = next_table_id();
+        }
+        &mut self.instruction_table
     }
 }
 
@@ -71,6 +158,21 @@ where
     fn instruction_table(&self) -> &InstructionTable<Self::InterpreterTypes, Self::Context> {
         &self.instruction_table
     }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn threaded_code(
+        &self,
+        code_hash: B256,
+        bytecode: &Bytecode,
+    ) -> Option<Arc<ThreadedCode<Self::InterpreterTypes, Self::Context>>> {
+        self.threaded_cache.as_ref()?.get_or_translate(
+            code_hash,
+            self.table_id,
+            bytecode,
+            &self.instruction_table,
+        )
+    }
 }
 
 impl<WIRE, HOST> Default for EthInstructions<WIRE, HOST>
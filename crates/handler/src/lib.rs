@@ -9,6 +9,7 @@ extern crate alloc as std;
 
 /// EVM execution API traits and implementations.
 pub mod api;
+pub mod chain_spec;
 /// Core EVM traits for execution and frame management.
 pub mod evm;
 /// EVM execution logic and utilities.
@@ -26,6 +27,7 @@ mod mainnet_handler;
 pub mod post_execution;
 pub mod pre_execution;
 mod precompile_provider;
+mod snapshot;
 /// System call implementations for special EVM operations.
 pub mod system_call;
 /// Transaction and environment validation utilities.
@@ -33,6 +35,7 @@ pub mod validation;
 
 // Public exports
 pub use api::{ExecuteCommitEvm, ExecuteEvm};
+pub use chain_spec::{ChainPrecompiles, ChainSpec, ChainSpecError, ChainSpecEvm};
 pub use evm::{EvmTr, FrameTr};
 pub use frame::{return_create, ContextTrDbError, EthFrame};
 pub use frame_data::{CallFrame, CreateFrame, FrameData, FrameResult};
@@ -41,4 +44,5 @@ pub use item_or_result::{FrameInitOrResult, ItemOrResult};
 pub use mainnet_builder::{MainBuilder, MainContext, MainnetContext, MainnetEvm};
 pub use mainnet_handler::MainnetHandler;
 pub use precompile_provider::{EthPrecompiles, PrecompileProvider};
+pub use snapshot::ExecutionSnapshot;
 pub use system_call::{SystemCallCommitEvm, SystemCallEvm, SystemCallTx, SYSTEM_ADDRESS};
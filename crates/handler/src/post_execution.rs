@@ -53,6 +53,8 @@ pub fn reimburse_caller<CTX: ContextTr>(
 }
 
 /// Rewards the beneficiary with transaction fees.
+///
+/// From London, the base fee is not part of the reward, see [`reward_base_fee_recipient`].
 #[inline]
 pub fn reward_beneficiary<CTX: ContextTr>(
     context: &mut CTX,
@@ -78,6 +80,30 @@ pub fn reward_beneficiary<CTX: ContextTr>(
     Ok(())
 }
 
+/// Credits the base fee to [`Cfg::base_fee_recipient`] instead of burning it.
+///
+/// Does nothing before London or if the recipient is not set.
+#[inline]
+pub fn reward_base_fee_recipient<CTX: ContextTr>(
+    context: &mut CTX,
+    gas: &Gas,
+) -> Result<(), <CTX::Db as Database>::Error> {
+    let (block, _, cfg, journal, _, _) = context.all_mut();
+    let Some(base_fee_recipient) = cfg
+        .base_fee_recipient()
+        .filter(|_| cfg.spec().into().is_enabled_in(SpecId::LONDON))
+    else {
+        return Ok(());
+    };
+
+    let basefee = block.basefee() as u128;
+    journal
+        .load_account_mut(base_fee_recipient)?
+        .incr_balance(U256::from(basefee.saturating_mul(gas.used() as u128)));
+
+    Ok(())
+}
+
 /// Calculate last gas spent and transform internal reason to external.
 ///
 /// TODO make Journal FinalOutput more generic.
@@ -93,62 +93,7 @@ impl<CTX: ContextTr> PrecompileProvider<CTX> for EthPrecompiles {
         context: &mut CTX,
         inputs: &CallInputs,
     ) -> Result<Option<InterpreterResult>, String> {
-        let Some(precompile) = self.precompiles.get(&inputs.bytecode_address) else {
-            return Ok(None);
-        };
-
-        let mut result = InterpreterResult {
-            result: InstructionResult::Return,
-            gas: Gas::new(inputs.gas_limit),
-            output: Bytes::new(),
-        };
-
-        let exec_result = {
-            let r;
-            let input_bytes = match &inputs.input {
-                CallInput::SharedBuffer(range) => {
-                    if let Some(slice) = context.local().shared_memory_buffer_slice(range.clone()) {
-                        r = slice;
-                        r.as_ref()
-                    } else {
-                        &[]
-                    }
-                }
-                CallInput::Bytes(bytes) => bytes.0.iter().as_slice(),
-            };
-            precompile.execute(input_bytes, inputs.gas_limit)
-        };
-
-        match exec_result {
-            Ok(output) => {
-                result.gas.record_refund(output.gas_refunded);
-                let underflow = result.gas.record_cost(output.gas_used);
-                assert!(underflow, "Gas underflow is not possible");
-                result.result = if output.reverted {
-                    InstructionResult::Revert
-                } else {
-                    InstructionResult::Return
-                };
-                result.output = output.bytes;
-            }
-            Err(PrecompileError::Fatal(e)) => return Err(e),
-            Err(e) => {
-                result.result = if e.is_oog() {
-                    InstructionResult::PrecompileOOG
-                } else {
-                    InstructionResult::PrecompileError
-                };
-                // If this is a top-level precompile call (depth == 1), persist the error message
-                // into the local context so it can be returned as output in the final result.
-                // Only do this for non-OOG errors (OOG is a distinct halt reason without output).
-                if !e.is_oog() && context.journal().depth() == 1 {
-                    context
-                        .local_mut()
-                        .set_precompile_error_context(e.to_string());
-                }
-            }
-        }
-        Ok(Some(result))
+        run_precompile(self.precompiles, context, inputs)
     }
 
     fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
@@ -159,3 +104,69 @@ impl<CTX: ContextTr> PrecompileProvider<CTX> for EthPrecompiles {
         Self::contains(self, address)
     }
 }
+
+/// Runs the precompile of `precompiles` at the bytecode address of the call.
+///
+/// Returns `None` if there is no precompile at the address.
+pub(crate) fn run_precompile<CTX: ContextTr>(
+    precompiles: &Precompiles,
+    context: &mut CTX,
+    inputs: &CallInputs,
+) -> Result<Option<InterpreterResult>, String> {
+    let Some(precompile) = precompiles.get(&inputs.bytecode_address) else {
+        return Ok(None);
+    };
+
+    let mut result = InterpreterResult {
+        result: InstructionResult::Return,
+        gas: Gas::new(inputs.gas_limit),
+        output: Bytes::new(),
+    };
+
+    let exec_result = {
+        let r;
+        let input_bytes = match &inputs.input {
+            CallInput::SharedBuffer(range) => {
+                if let Some(slice) = context.local().shared_memory_buffer_slice(range.clone()) {
+                    r = slice;
+                    r.as_ref()
+                } else {
+                    &[]
+                }
+            }
+            CallInput::Bytes(bytes) => bytes.0.iter().as_slice(),
+        };
+        precompile.execute(input_bytes, inputs.gas_limit)
+    };
+
+    match exec_result {
+        Ok(output) => {
+            result.gas.record_refund(output.gas_refunded);
+            let underflow = result.gas.record_cost(output.gas_used);
+            assert!(underflow, "Gas underflow is not possible");
+            result.result = if output.reverted {
+                InstructionResult::Revert
+            } else {
+                InstructionResult::Return
+            };
+            result.output = output.bytes;
+        }
+        Err(PrecompileError::Fatal(e)) => return Err(e),
+        Err(e) => {
+            result.result = if e.is_oog() {
+                InstructionResult::PrecompileOOG
+            } else {
+                InstructionResult::PrecompileError
+            };
+            // If this is a top-level precompile call (depth == 1), persist the error message
+            // into the local context so it can be returned as output in the final result.
+            // Only do this for non-OOG errors (OOG is a distinct halt reason without output).
+            if !e.is_oog() && context.journal().depth() == 1 {
+                context
+                    .local_mut()
+                    .set_precompile_error_context(e.to_string());
+            }
+        }
+    }
+    Ok(Some(result))
+}
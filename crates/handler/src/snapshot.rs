@@ -0,0 +1,104 @@
+use crate::{EthFrame, EvmTr};
+use context::{
+    journal::{JournalEntry, JournalEntryTr},
+    Journal, JournalInner,
+};
+use context_interface::{Cfg, ContextTr, ExecutionBudget, LocalContextTr};
+use core::cell::RefCell;
+use interpreter::interpreter::EthInterpreter;
+use std::{rc::Rc, vec::Vec};
+
+/// Snapshot of a transaction in the middle of its execution.
+///
+/// It contains the call stack together with the stack, memory, program counter, gas and return
+/// data of every frame, and the journal the frame checkpoints refer to. With the `serde` feature
+/// it can be serialized to checkpoint a long simulation or to fork the execution, and resumed
+/// in a fresh context over the same database with
+/// [`Handler::resume_exec_loop`](crate::Handler::resume_exec_loop).
+///
+/// A snapshot is taken between iterations of the execution loop, when the current frame is
+/// ready to run. The remaining steps of the execution budget are kept, while the time limit of
+/// the restoring context starts again on restore.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionSnapshot<F = EthFrame<EthInterpreter>, J = JournalInner<JournalEntry>> {
+    /// Frames ordered from the first call to the current one.
+    pub frames: Vec<F>,
+    /// Journal with the state loaded and changed so far by the transaction.
+    pub journal: J,
+    /// Steps left in the execution budget, `None` if steps are not limited.
+    pub remaining_steps: Option<u64>,
+}
+
+impl<ENTRY: JournalEntryTr + Clone>
+    ExecutionSnapshot<EthFrame<EthInterpreter>, JournalInner<ENTRY>>
+{
+    /// Captures the frames and the journal of the EVM.
+    ///
+    /// Memory is copied, so the snapshot is not affected by further execution.
+    pub fn capture<EVM, DB>(evm: &mut EVM) -> Self
+    where
+        EVM: EvmTr<Frame = EthFrame<EthInterpreter>>,
+        EVM::Context: ContextTr<Journal = Journal<DB, ENTRY>>,
+    {
+        let mut frames = evm.frame_stack().frames().to_vec();
+        if let Some(buffer) = frames
+            .first()
+            .and_then(|frame| frame.interpreter.memory.shared_buffer())
+        {
+            let buffer = Rc::new(RefCell::new(buffer.borrow().clone()));
+            for frame in &mut frames {
+                frame.interpreter.memory.set_shared_buffer(buffer.clone());
+            }
+        }
+        let journal = evm.ctx().journal_ref().inner.clone();
+        let remaining_steps = evm.ctx().local().execution_budget().remaining_steps();
+        Self {
+            frames,
+            journal,
+            remaining_steps,
+        }
+    }
+
+    /// Replaces the frames and the journal of the EVM with the ones of the snapshot.
+    ///
+    /// Memory of the frames is copied into the shared memory buffer of the context. The database
+    /// and the bytecode cache of the EVM are kept. The execution budget gets the remaining steps
+    /// of the snapshot and the time limit of the EVM configuration.
+    pub fn restore<EVM, DB>(self, evm: &mut EVM)
+    where
+        EVM: EvmTr<Frame = EthFrame<EthInterpreter>>,
+        EVM::Context: ContextTr<Journal = Journal<DB, ENTRY>>,
+    {
+        let Self {
+            mut frames,
+            journal,
+            remaining_steps,
+        } = self;
+        let local = evm.ctx().local().shared_memory_buffer().clone();
+        if let Some(buffer) = frames
+            .first()
+            .and_then(|frame| frame.interpreter.memory.shared_buffer())
+        {
+            if !Rc::ptr_eq(buffer, &local) {
+                local.borrow_mut().clone_from(&buffer.borrow());
+            }
+        }
+        for frame in &mut frames {
+            frame.interpreter.memory.set_shared_buffer(local.clone());
+        }
+        evm.frame_stack().set_frames(frames);
+
+        let inner = &mut evm.ctx().journal_mut().inner;
+        #[cfg(feature = "std")]
+        let journal = JournalInner {
+            code_cache: inner.code_cache.take(),
+            ..journal
+        };
+        *inner = journal;
+
+        let ctx = evm.ctx();
+        let budget = ExecutionBudget::new(remaining_steps, ctx.cfg().time_limit());
+        ctx.local_mut().set_execution_budget(budget);
+    }
+}
@@ -4,7 +4,7 @@ use context_interface::{
     Block, Cfg, ContextTr,
 };
 use core::cmp;
-use interpreter::gas::{self, InitialAndFloorGas};
+use interpreter::gas::{self, GasParams, InitialAndFloorGas};
 use primitives::{eip4844, hardfork::SpecId, B256};
 
 /// Validates the execution environment including block and transaction parameters.
@@ -230,7 +230,17 @@ pub fn validate_initial_tx_gas(
     spec: SpecId,
     is_eip7623_disabled: bool,
 ) -> Result<InitialAndFloorGas, InvalidTransaction> {
-    let mut gas = gas::calculate_initial_tx_gas_for_tx(&tx, spec);
+    validate_initial_tx_gas_with_params(tx, spec, GasParams::mainnet(spec), is_eip7623_disabled)
+}
+
+/// Validate initial transaction gas calculated from the given gas schedule.
+pub fn validate_initial_tx_gas_with_params(
+    tx: impl Transaction,
+    spec: SpecId,
+    gas_params: &GasParams,
+    is_eip7623_disabled: bool,
+) -> Result<InitialAndFloorGas, InvalidTransaction> {
+    let mut gas = gas::calculate_initial_tx_gas_for_tx_with_params(&tx, gas_params);
 
     if is_eip7623_disabled {
         gas.floor_gas = 0
@@ -1,11 +1,12 @@
-use context::{ContextTr, FrameStack, JournalTr};
+use context::{ContextTr, FrameStack, JournalTr, LocalContextTr};
 use handler::{
     evm::{ContextDbError, FrameInitResult, FrameTr},
     instructions::InstructionProvider,
     EthFrame, EvmTr, FrameInitOrResult, FrameResult, ItemOrResult,
 };
 use interpreter::{
-    interpreter::EthInterpreter, interpreter_action::FrameInit, CallOutcome, InterpreterTypes,
+    interpreter::EthInterpreter, interpreter_action::FrameInit, interpreter_types::LoopControl,
+    CallOutcome, InterpreterTypes,
 };
 
 use crate::{
@@ -149,12 +150,19 @@ pub trait InspectorEvmTr:
             return self.frame_run();
         };
 
+        // Execution budget is shared by all frames of the transaction.
+        frame
+            .interpreter
+            .bytecode
+            .set_execution_budget(ctx.local().execution_budget());
         let next_action = inspect_instructions(
             ctx,
             &mut frame.interpreter,
             inspector,
             instructions.instruction_table(),
         );
+        ctx.local_mut()
+            .set_execution_budget(frame.interpreter.bytecode.execution_budget());
         let mut result = frame.process_next_action(ctx, next_action);
 
         if let Ok(ItemOrResult::Result(frame_result)) = &mut result {
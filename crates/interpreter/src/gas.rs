@@ -5,6 +5,7 @@ mod constants;
 
 pub use calc::*;
 pub use constants::*;
+pub use context_interface::cfg::GasParams;
 
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
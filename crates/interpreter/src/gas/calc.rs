@@ -1,60 +1,15 @@
 use super::constants::*;
 use crate::{num_words, tri, SStoreResult, SelfDestructResult, StateLoad};
 use context_interface::{
-    journaled_state::AccountLoad, transaction::AccessListItemTr as _, Transaction, TransactionType,
+    cfg::GasParams, journaled_state::AccountLoad, transaction::AccessListItemTr as _, Transaction,
+    TransactionType,
 };
-use primitives::{eip7702, hardfork::SpecId, U256};
+use primitives::{hardfork::SpecId, U256};
 
 /// `SSTORE` opcode refund calculation.
-#[allow(clippy::collapsible_else_if)]
 #[inline]
 pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
-    if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // EIP-3529: Reduction in refunds
-        let sstore_clears_schedule = if spec_id.is_enabled_in(SpecId::LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
-        } else {
-            REFUND_SSTORE_CLEARS
-        };
-        if vals.is_new_eq_present() {
-            0
-        } else {
-            if vals.is_original_eq_present() && vals.is_new_zero() {
-                sstore_clears_schedule
-            } else {
-                let mut refund = 0;
-
-                if !vals.is_original_zero() {
-                    if vals.is_present_zero() {
-                        refund -= sstore_clears_schedule;
-                    } else if vals.is_new_zero() {
-                        refund += sstore_clears_schedule;
-                    }
-                }
-
-                if vals.is_original_eq_new() {
-                    let (gas_sstore_reset, gas_sload) = if spec_id.is_enabled_in(SpecId::BERLIN) {
-                        (SSTORE_RESET - COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
-                    } else {
-                        (SSTORE_RESET, sload_cost(spec_id, false))
-                    };
-                    if vals.is_original_zero() {
-                        refund += (SSTORE_SET - gas_sload) as i64;
-                    } else {
-                        refund += (gas_sstore_reset - gas_sload) as i64;
-                    }
-                }
-
-                refund
-            }
-        }
-    } else {
-        if !vals.is_present_zero() && vals.is_new_zero() {
-            REFUND_SSTORE_CLEARS
-        } else {
-            0
-        }
-    }
+    GasParams::mainnet(spec_id).sstore_refund(spec_id.is_enabled_in(SpecId::ISTANBUL), vals)
 }
 
 /// `CREATE2` opcode cost calculation.
@@ -63,46 +18,10 @@ pub const fn create2_cost(len: usize) -> Option<u64> {
     CREATE.checked_add(tri!(cost_per_word(len, KECCAK256WORD)))
 }
 
-#[inline]
-const fn log2floor(value: U256) -> u64 {
-    let mut l: u64 = 256;
-    let mut i = 3;
-    loop {
-        if value.as_limbs()[i] == 0u64 {
-            l -= 64;
-        } else {
-            l -= value.as_limbs()[i].leading_zeros() as u64;
-            if l == 0 {
-                return l;
-            } else {
-                return l - 1;
-            }
-        }
-        if i == 0 {
-            break;
-        }
-        i -= 1;
-    }
-    l
-}
-
 /// `EXP` opcode cost calculation.
 #[inline]
 pub fn exp_cost(spec_id: SpecId, power: U256) -> Option<u64> {
-    if power.is_zero() {
-        Some(EXP)
-    } else {
-        // EIP-160: EXP cost increase
-        let gas_byte = U256::from(if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
-            50
-        } else {
-            10
-        });
-        let gas = U256::from(EXP)
-            .checked_add(gas_byte.checked_mul(U256::from(log2floor(power) / 8 + 1))?)?;
-
-        u64::try_from(gas).ok()
-    }
+    GasParams::mainnet(spec_id).exp_cost(power)
 }
 
 /// `*COPY` opcodes cost calculation.
@@ -114,13 +33,11 @@ pub const fn copy_cost_verylow(len: usize) -> Option<u64> {
 /// `EXTCODECOPY` opcode cost calculation.
 #[inline]
 pub const fn extcodecopy_cost(spec_id: SpecId, len: usize, is_cold: bool) -> Option<u64> {
-    let base_gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        warm_cold_cost(is_cold)
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        700
-    } else {
-        20
-    };
+    let params = GasParams::mainnet(spec_id);
+    let mut base_gas = params.extcodecopy;
+    if is_cold {
+        base_gas += params.cold_account_access_additional();
+    }
     copy_cost(base_gas, len)
 }
 
@@ -164,149 +81,60 @@ pub const fn initcode_cost(len: usize) -> u64 {
 /// `SLOAD` opcode cost calculation.
 #[inline]
 pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        if is_cold {
-            COLD_SLOAD_COST
-        } else {
-            WARM_STORAGE_READ_COST
-        }
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // EIP-1884: Repricing for trie-size-dependent opcodes
-        ISTANBUL_SLOAD_GAS
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        // EIP-150: Gas cost changes for IO-heavy operations
-        200
-    } else {
-        50
-    }
+    GasParams::mainnet(spec_id).sload_cost(is_cold)
 }
 
 /// Static gas cost for sstore.
 #[inline]
 pub const fn sstore_cost_static(spec_id: SpecId) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        WARM_STORAGE_READ_COST
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        ISTANBUL_SLOAD_GAS
-    } else {
-        SSTORE_RESET
-    }
+    static_sstore_cost(spec_id)
 }
 
 /// Dynamic gas cost for sstore.
 #[inline]
 pub const fn sstore_cost_dynamic(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
-    sstore_cost(spec_id, vals, is_cold) - sstore_cost_static(spec_id)
+    dyn_sstore_cost(spec_id, vals, is_cold)
 }
 
 /// Static gas cost for sstore.
 #[inline]
 pub const fn static_sstore_cost(spec_id: SpecId) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        WARM_STORAGE_READ_COST
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        ISTANBUL_SLOAD_GAS
-    } else {
-        SSTORE_RESET
-    }
+    GasParams::mainnet(spec_id).sstore_static_cost(spec_id.is_enabled_in(SpecId::ISTANBUL))
 }
 
 /// Dynamic gas cost for sstore.
 #[inline]
 pub const fn dyn_sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
-    sstore_cost(spec_id, vals, is_cold) - static_sstore_cost(spec_id)
+    GasParams::mainnet(spec_id).sstore_dynamic_cost(
+        spec_id.is_enabled_in(SpecId::ISTANBUL),
+        vals,
+        is_cold,
+    )
 }
 
 /// `SSTORE` opcode cost calculation.
 #[inline]
 pub const fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        // Berlin specification logic
-        let mut gas_cost = istanbul_sstore_cost::<WARM_STORAGE_READ_COST, WARM_SSTORE_RESET>(vals);
-
-        if is_cold {
-            gas_cost += COLD_SLOAD_COST;
-        }
-        gas_cost
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // Istanbul logic
-        istanbul_sstore_cost::<ISTANBUL_SLOAD_GAS, SSTORE_RESET>(vals)
-    } else {
-        // Frontier logic
-        frontier_sstore_cost(vals)
-    }
-}
-
-/// EIP-2200: Structured Definitions for Net Gas Metering
-#[inline]
-const fn istanbul_sstore_cost<const SLOAD_GAS: u64, const SSTORE_RESET_GAS: u64>(
-    vals: &SStoreResult,
-) -> u64 {
-    if vals.is_new_eq_present() {
-        SLOAD_GAS
-    } else if vals.is_original_eq_present() && vals.is_original_zero() {
-        SSTORE_SET
-    } else if vals.is_original_eq_present() {
-        SSTORE_RESET_GAS
-    } else {
-        SLOAD_GAS
-    }
-}
-
-/// Frontier sstore cost just had two cases set and reset values.
-#[inline]
-const fn frontier_sstore_cost(vals: &SStoreResult) -> u64 {
-    if vals.is_present_zero() && !vals.is_new_zero() {
-        SSTORE_SET
-    } else {
-        SSTORE_RESET
-    }
+    GasParams::mainnet(spec_id).sstore_cost(spec_id.is_enabled_in(SpecId::ISTANBUL), vals, is_cold)
 }
 
 /// Static gas cost for selfdestruct.
 #[inline]
 pub const fn static_selfdestruct_cost(spec_id: SpecId) -> u64 {
-    // EIP-150: Gas cost changes for IO-heavy operations
-    if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        5000
-    } else {
-        0
-    }
+    GasParams::mainnet(spec_id).selfdestruct
 }
 
 /// `SELFDESTRUCT` opcode cost calculation.
 #[inline]
 pub const fn dyn_selfdestruct_cost(spec_id: SpecId, res: &StateLoad<SelfDestructResult>) -> u64 {
-    let is_tangerine = spec_id.is_enabled_in(SpecId::TANGERINE);
-    let mut gas = 0;
-
-    // EIP-161: State trie clearing (invariant-preserving alternative)
-    let should_charge_topup = if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
-        res.data.had_value && !res.data.target_exists
-    } else {
-        !res.data.target_exists
-    };
-
-    // EIP-150: Gas cost changes for IO-heavy operations
-    if is_tangerine && should_charge_topup {
-        gas += NEWACCOUNT
-    }
-
-    if res.is_cold {
-        gas += selfdestruct_cold_beneficiary_cost(spec_id);
-    }
-
-    gas
+    GasParams::mainnet(spec_id)
+        .selfdestruct_dynamic_cost(spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON), res)
 }
 
 /// EIP-2929: Gas cost increases for state access opcodes
 #[inline]
 pub const fn selfdestruct_cold_beneficiary_cost(spec_id: SpecId) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        COLD_ACCOUNT_ACCESS_COST
-    } else {
-        0
-    }
+    GasParams::mainnet(spec_id).cold_account_access
 }
 
 /// `SELFDESTRUCT` opcode cost calculation.
@@ -322,22 +150,7 @@ pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResul
 /// * If there is transfer value. additional gas of [`CALLVALUE`] is added.
 #[inline]
 pub fn calc_call_static_gas(spec_id: SpecId, has_transfer: bool) -> u64 {
-    // Account access.
-    let mut gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        WARM_STORAGE_READ_COST
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        // EIP-150: Gas cost changes for IO-heavy operations
-        700
-    } else {
-        40
-    };
-
-    // Transfer value cost
-    if has_transfer {
-        gas += CALLVALUE;
-    }
-
-    gas
+    GasParams::mainnet(spec_id).call_static_cost(has_transfer)
 }
 
 /// Berlin warm and cold storage access cost for account access.
@@ -396,6 +209,8 @@ impl InitialAndFloorGas {
 /// Initial gas that is deducted for transaction to be included.
 /// Initial gas contains initial stipend gas, gas for access list and input data.
 ///
+/// Uses the mainnet gas schedule of the spec, see [`calculate_initial_tx_gas_with_params`].
+///
 /// # Returns
 ///
 /// - Intrinsic gas
@@ -407,43 +222,56 @@ pub fn calculate_initial_tx_gas(
     access_list_accounts: u64,
     access_list_storages: u64,
     authorization_list_num: u64,
+) -> InitialAndFloorGas {
+    calculate_initial_tx_gas_with_params(
+        GasParams::mainnet(spec_id),
+        input,
+        is_create,
+        access_list_accounts,
+        access_list_storages,
+        authorization_list_num,
+    )
+}
+
+/// Initial gas that is deducted for transaction to be included, calculated from the given gas
+/// schedule.
+///
+/// Floor gas is zero if the schedule has no EIP-7623 floor cost.
+pub fn calculate_initial_tx_gas_with_params(
+    params: &GasParams,
+    input: &[u8],
+    is_create: bool,
+    access_list_accounts: u64,
+    access_list_storages: u64,
+    authorization_list_num: u64,
 ) -> InitialAndFloorGas {
     let mut gas = InitialAndFloorGas::default();
 
     // Initdate stipend
-    let tokens_in_calldata = get_tokens_in_calldata(input, spec_id.is_enabled_in(SpecId::ISTANBUL));
+    let tokens_in_calldata = params.tokens_in_calldata(input);
 
-    gas.initial_gas += tokens_in_calldata * STANDARD_TOKEN_COST;
+    gas.initial_gas += tokens_in_calldata * params.tx_token_cost;
 
     // Get number of access list account and storages.
-    gas.initial_gas += access_list_accounts * ACCESS_LIST_ADDRESS;
-    gas.initial_gas += access_list_storages * ACCESS_LIST_STORAGE_KEY;
+    gas.initial_gas += access_list_accounts * params.tx_access_list_address;
+    gas.initial_gas += access_list_storages * params.tx_access_list_storage_key;
 
     // Base stipend
-    gas.initial_gas += if is_create {
-        if spec_id.is_enabled_in(SpecId::HOMESTEAD) {
-            // EIP-2: Homestead Hard-fork Changes
-            53000
-        } else {
-            21000
-        }
-    } else {
-        21000
-    };
-
-    // EIP-3860: Limit and meter initcode
-    // Init code stipend for bytecode analysis
-    if spec_id.is_enabled_in(SpecId::SHANGHAI) && is_create {
-        gas.initial_gas += initcode_cost(input.len())
+    gas.initial_gas += params.tx_base;
+    if is_create {
+        // EIP-2: Homestead Hard-fork Changes
+        gas.initial_gas += params.tx_create;
+
+        // EIP-3860: Limit and meter initcode
+        // Init code stipend for bytecode analysis
+        gas.initial_gas += params.tx_initcode_word * num_words(input.len()) as u64;
     }
 
     // EIP-7702
-    if spec_id.is_enabled_in(SpecId::PRAGUE) {
-        gas.initial_gas += authorization_list_num * eip7702::PER_EMPTY_ACCOUNT_COST;
+    gas.initial_gas += authorization_list_num * params.tx_authorization;
 
-        // Calculate gas floor for EIP-7623
-        gas.floor_gas = calc_tx_floor_cost(tokens_in_calldata);
-    }
+    // Calculate gas floor for EIP-7623
+    gas.floor_gas = params.tx_floor_cost(tokens_in_calldata);
 
     gas
 }
@@ -456,6 +284,16 @@ pub fn calculate_initial_tx_gas(
 /// - Intrinsic gas
 /// - Number of tokens in calldata
 pub fn calculate_initial_tx_gas_for_tx(tx: impl Transaction, spec: SpecId) -> InitialAndFloorGas {
+    calculate_initial_tx_gas_for_tx_with_params(tx, GasParams::mainnet(spec))
+}
+
+/// Initial gas of the transaction calculated from the given gas schedule.
+///
+/// See [`calculate_initial_tx_gas_with_params`].
+pub fn calculate_initial_tx_gas_for_tx_with_params(
+    tx: impl Transaction,
+    params: &GasParams,
+) -> InitialAndFloorGas {
     let mut accounts = 0;
     let mut storages = 0;
     // legacy is only tx type that does not have access list.
@@ -473,8 +311,8 @@ pub fn calculate_initial_tx_gas_for_tx(tx: impl Transaction, spec: SpecId) -> In
             .unwrap_or_default();
     }
 
-    calculate_initial_tx_gas(
-        spec,
+    calculate_initial_tx_gas_with_params(
+        params,
         tx.input(),
         tx.kind().is_create(),
         accounts as u64,
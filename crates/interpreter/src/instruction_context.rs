@@ -10,6 +10,18 @@ pub struct InstructionContext<'a, H: ?Sized, ITy: InterpreterTypes> {
     pub host: &'a mut H,
 }
 
+impl<H: ?Sized, ITy: InterpreterTypes> InstructionContext<'_, H, ITy> {
+    /// Reborrows the context, for example to execute the wrapped instruction and keep using the
+    /// context afterwards.
+    #[inline]
+    pub fn reborrow(&mut self) -> InstructionContext<'_, H, ITy> {
+        InstructionContext {
+            interpreter: self.interpreter,
+            host: self.host,
+        }
+    }
+}
+
 impl<H: ?Sized, ITy: InterpreterTypes> std::fmt::Debug for InstructionContext<'_, H, ITy> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("InstructionContext")
@@ -1,6 +1,7 @@
 use context_interface::{
     journaled_state::TransferError,
     result::{HaltReason, OutOfGasError, SuccessReason},
+    ExecutionLimit,
 };
 use core::fmt::Debug;
 
@@ -81,6 +82,10 @@ pub enum InstructionResult {
     CreateInitCodeSizeLimit,
     /// Fatal external error. Returned by database.
     FatalExternalError,
+    /// Step limit of the execution budget was reached.
+    StepLimitReached,
+    /// Time limit of the execution budget was reached.
+    TimeLimitReached,
 }
 
 impl From<TransferError> for InstructionResult {
@@ -93,6 +98,15 @@ impl From<TransferError> for InstructionResult {
     }
 }
 
+impl From<ExecutionLimit> for InstructionResult {
+    fn from(limit: ExecutionLimit) -> Self {
+        match limit {
+            ExecutionLimit::Steps => InstructionResult::StepLimitReached,
+            ExecutionLimit::Time => InstructionResult::TimeLimitReached,
+        }
+    }
+}
+
 impl From<SuccessReason> for InstructionResult {
     fn from(value: SuccessReason) -> Self {
         match value {
@@ -133,6 +147,8 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::CallNotAllowedInsideStatic => Self::CallNotAllowedInsideStatic,
             HaltReason::OutOfFunds => Self::OutOfFunds,
             HaltReason::CallTooDeep => Self::CallTooDeep,
+            HaltReason::StepLimitReached => Self::StepLimitReached,
+            HaltReason::TimeLimitReached => Self::TimeLimitReached,
         }
     }
 }
@@ -189,6 +205,8 @@ macro_rules! return_error {
             | $crate::InstructionResult::CreateContractSizeLimit
             | $crate::InstructionResult::CreateContractStartingWithEF
             | $crate::InstructionResult::CreateInitCodeSizeLimit
+            | $crate::InstructionResult::StepLimitReached
+            | $crate::InstructionResult::TimeLimitReached
             | $crate::InstructionResult::FatalExternalError
     };
 }
@@ -342,6 +360,8 @@ impl<HaltReasonTr: From<HaltReason>> From<InstructionResult> for SuccessOrHalt<H
             InstructionResult::CreateInitCodeSizeLimit => {
                 Self::Halt(HaltReason::CreateInitCodeSizeLimit.into())
             }
+            InstructionResult::StepLimitReached => Self::Halt(HaltReason::StepLimitReached.into()),
+            InstructionResult::TimeLimitReached => Self::Halt(HaltReason::TimeLimitReached.into()),
             // TODO : (EOF) Add proper Revert subtype.
             InstructionResult::InvalidEOFInitCode => Self::Revert,
             InstructionResult::FatalExternalError => Self::FatalExternalError,
@@ -411,6 +431,8 @@ mod tests {
             InstructionResult::CreateContractSizeLimit,
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
+            InstructionResult::StepLimitReached,
+            InstructionResult::TimeLimitReached,
             InstructionResult::FatalExternalError,
         ];
         for result in error_results {
@@ -8,6 +8,8 @@ pub mod arithmetic;
 pub mod bitwise;
 /// Block information instructions (COINBASE, TIMESTAMP, etc.).
 pub mod block_info;
+/// Builder for customized instruction tables.
+pub mod builder;
 /// Contract operations (CALL, CREATE, DELEGATECALL, etc.).
 pub mod contract;
 /// Control flow instructions (JUMP, JUMPI, REVERT, etc.).
@@ -24,38 +26,104 @@ pub mod stack;
 pub mod system;
 /// Transaction information instructions (ORIGIN, GASPRICE, etc.).
 pub mod tx_info;
+/// Fast paths for unsigned 256-bit arithmetic.
+pub mod u256;
 /// Utility functions and helpers for instruction implementation.
 pub mod utility;
+mod wrapped;
 
 use crate::{interpreter_types::InterpreterTypes, Host, InstructionContext};
 
+/// Wrapper of an instruction, executed in place of the wrapped instruction `next`.
+///
+/// The wrapper decides if and when `next` is executed, see [`Instruction::wrap`].
+pub type InstructionWrapper<W, H> = fn(InstructionContext<'_, H, W>, next: Instruction<W, H>);
+
 /// EVM opcode function signature.
+#[repr(C)]
 #[derive(Debug)]
 pub struct Instruction<W: InterpreterTypes, H: ?Sized> {
     fn_: fn(InstructionContext<'_, H, W>),
     static_gas: u64,
+    /// Wrapper and the wrapped instruction, executed instead of `fn_` if set.
+    wrapped: Option<&'static wrapped::Node>,
 }
 
 impl<W: InterpreterTypes, H: Host + ?Sized> Instruction<W, H> {
     /// Creates a new instruction with the given function and static gas cost.
     #[inline]
     pub const fn new(fn_: fn(InstructionContext<'_, H, W>), static_gas: u64) -> Self {
-        Self { fn_, static_gas }
+        Self {
+            fn_,
+            static_gas,
+            wrapped: None,
+        }
     }
 
     /// Creates an unknown/invalid instruction.
     #[inline]
     pub const fn unknown() -> Self {
+        Self::new(control::unknown, 0)
+    }
+
+    /// Creates an instruction that halts with [`NotActivated`](crate::InstructionResult::NotActivated).
+    #[inline]
+    pub const fn not_activated() -> Self {
+        Self::new(control::not_activated, 0)
+    }
+
+    /// Returns a new instruction that executes `wrapper` with this instruction as `next`.
+    ///
+    /// Static gas of this instruction is kept and is charged once, before the wrapper is called.
+    /// Wrapping an already wrapped instruction composes the wrappers, the last one runs first.
+    ///
+    /// The pair of `wrapper` and this instruction is kept for the rest of the program. Equal
+    /// pairs are shared, so wrapping the same instruction again, e.g. when a table is rebuilt,
+    /// does not use more memory.
+    pub fn wrap(self, wrapper: InstructionWrapper<W, H>) -> Self {
         Self {
-            fn_: control::unknown,
-            static_gas: 0,
+            wrapped: Some(wrapped::intern(wrapper, self)),
+            ..self
         }
     }
 
+    /// Returns the same instruction with the given static gas cost.
+    #[inline]
+    pub const fn with_static_gas(self, static_gas: u64) -> Self {
+        Self { static_gas, ..self }
+    }
+
+    /// Returns `true` if the instruction is wrapped with [`Instruction::wrap`].
+    #[inline]
+    pub const fn is_wrapped(&self) -> bool {
+        self.wrapped.is_some()
+    }
+
+    /// Returns `true` if both instructions execute the same function.
+    #[inline]
+    pub(crate) fn has_same_fn(&self, other: &Self) -> bool {
+        core::ptr::fn_addr_eq(self.fn_, other.fn_)
+            && match (self.wrapped, other.wrapped) {
+                (None, None) => true,
+                (Some(this), Some(other)) => core::ptr::eq(this, other),
+                _ => false,
+            }
+    }
+
+    /// Returns `true` if both instructions execute the same function with the same static gas.
+    #[inline]
+    fn is_same(&self, other: &Self) -> bool {
+        self.static_gas == other.static_gas && self.has_same_fn(other)
+    }
+
     /// Executes the instruction with the given context.
     #[inline(always)]
     pub fn execute(self, ctx: InstructionContext<'_, H, W>) {
-        (self.fn_)(ctx)
+        match self.wrapped {
+            None => (self.fn_)(ctx),
+            // SAFETY: Only `wrap` sets the node, with a pair of the same interpreter types.
+            Some(node) => unsafe { node.execute(ctx) },
+        }
     }
 
     /// Returns the static gas cost of this instruction.
@@ -77,11 +145,13 @@ pub type InstructionTable<W, H> = [Instruction<W, H>; 256];
 
 /// Returns the default instruction table for the given interpreter types and host.
 #[inline]
-pub const fn instruction_table<WIRE: InterpreterTypes, H: Host>() -> [Instruction<WIRE, H>; 256] {
+pub const fn instruction_table<WIRE: InterpreterTypes, H: Host + ?Sized>(
+) -> [Instruction<WIRE, H>; 256] {
     const { instruction_table_impl::<WIRE, H>() }
 }
 
-const fn instruction_table_impl<WIRE: InterpreterTypes, H: Host>() -> [Instruction<WIRE, H>; 256] {
+const fn instruction_table_impl<WIRE: InterpreterTypes, H: Host + ?Sized>(
+) -> [Instruction<WIRE, H>; 256] {
     use bytecode::opcode::*;
     let mut table = [Instruction::unknown(); 256];
 
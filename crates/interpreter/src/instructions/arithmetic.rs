@@ -1,8 +1,10 @@
-use super::i256::{i256_div, i256_mod};
+use super::{
+    i256::{i256_div, i256_mod},
+    u256,
+};
 use crate::{
-    gas,
-    interpreter_types::{InterpreterTypes, RuntimeFlag, StackTr},
-    InstructionContext,
+    interpreter_types::{InterpreterTypes, StackTr},
+    Host, InstructionContext,
 };
 use primitives::U256;
 
@@ -17,7 +19,7 @@ pub fn add<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H,
 pub fn mul<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     //gas!(context.interpreter, gas::LOW);
     popn_top!([op1], op2, context.interpreter);
-    *op2 = op1.wrapping_mul(*op2);
+    *op2 = u256::mul(op1, *op2);
 }
 
 /// Implements the SUB instruction - subtracts two values from stack.
@@ -31,9 +33,7 @@ pub fn sub<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H,
 pub fn div<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     //gas!(context.interpreter, gas::LOW);
     popn_top!([op1], op2, context.interpreter);
-    if !op2.is_zero() {
-        *op2 = op1.wrapping_div(*op2);
-    }
+    *op2 = u256::div(op1, *op2);
 }
 
 /// Implements the SDIV instruction.
@@ -51,9 +51,7 @@ pub fn sdiv<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H
 pub fn rem<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     //gas!(context.interpreter, gas::LOW);
     popn_top!([op1], op2, context.interpreter);
-    if !op2.is_zero() {
-        *op2 = op1.wrapping_rem(*op2);
-    }
+    *op2 = u256::rem(op1, *op2);
 }
 
 /// Implements the SMOD instruction.
@@ -71,7 +69,7 @@ pub fn smod<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H
 pub fn addmod<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     //gas!(context.interpreter, gas::MID);
     popn_top!([op1, op2], op3, context.interpreter);
-    *op3 = op1.add_mod(op2, *op3)
+    *op3 = u256::add_mod(op1, op2, *op3)
 }
 
 /// Implements the MULMOD instruction.
@@ -80,15 +78,17 @@ pub fn addmod<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_,
 pub fn mulmod<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     //gas!(context.interpreter, gas::MID);
     popn_top!([op1, op2], op3, context.interpreter);
-    *op3 = op1.mul_mod(op2, *op3)
+    *op3 = u256::mul_mod(op1, op2, *op3)
 }
 
 /// Implements the EXP instruction - exponentiates two values from stack.
-pub fn exp<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
-    let spec_id = context.interpreter.runtime_flag.spec_id();
+pub fn exp<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     popn_top!([op1], op2, context.interpreter);
-    gas_or_fail!(context.interpreter, gas::exp_cost(spec_id, *op2));
-    *op2 = op1.pow(*op2);
+    gas_or_fail!(
+        context.interpreter,
+        context.host.gas_params().exp_cost(*op2)
+    );
+    *op2 = u256::pow(op1, *op2);
 }
 
 /// Implements the `SIGNEXTEND` opcode as defined in the Ethereum Yellow Paper.
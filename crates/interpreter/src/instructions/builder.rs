@@ -0,0 +1,320 @@
+//! Builder for customized instruction tables.
+use super::{instruction_table, Instruction, InstructionTable, InstructionWrapper};
+use crate::{Host, InterpreterTypes};
+use primitives::hardfork::SpecId;
+use std::{boxed::Box, vec::Vec};
+
+/// Change of a single opcode in the instruction table.
+#[derive(Debug)]
+enum Change<W: InterpreterTypes, H: ?Sized> {
+    /// Replaces the instruction.
+    Insert(Instruction<W, H>),
+    /// Wraps the current instruction.
+    Wrap(InstructionWrapper<W, H>),
+    /// Replaces the instruction with one that halts with `NotActivated`.
+    Disable,
+    /// Sets the static gas of the current instruction.
+    Reprice(u64),
+}
+
+impl<W: InterpreterTypes, H: Host + ?Sized> Clone for Change<W, H> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Insert(instruction) => Self::Insert(*instruction),
+            Self::Wrap(wrapper) => Self::Wrap(*wrapper),
+            Self::Disable => Self::Disable,
+            Self::Reprice(gas) => Self::Reprice(*gas),
+        }
+    }
+}
+
+/// Builder of instruction tables that changes single opcodes of a base table.
+///
+/// Opcodes can be replaced, wrapped, disabled or re-priced. Changes are applied in the order
+/// they were added, so wrapping a wrapped opcode composes the wrappers and re-pricing after
+/// wrapping keeps the wrapper.
+///
+/// Changes can be limited to the specs in which they are active with
+/// [`since`](InstructionTableBuilder::since), and [`build`](InstructionTableBuilder::build)
+/// derives the table for a given spec.
+///
+/// ```
+/// use revm_interpreter::{
+///     gas, host::DummyHost, instructions::builder::InstructionTableBuilder,
+///     interpreter::EthInterpreter, Instruction, InstructionContext,
+/// };
+/// use bytecode::opcode;
+/// use primitives::hardfork::SpecId;
+///
+/// // Charges additional gas for every SLOAD.
+/// fn metered(
+///     context: InstructionContext<'_, DummyHost, EthInterpreter>,
+///     next: Instruction<EthInterpreter, DummyHost>,
+/// ) {
+///     gas!(context.interpreter, 100);
+///     next.execute(context);
+/// }
+///
+/// let builder = InstructionTableBuilder::<EthInterpreter, DummyHost>::mainnet()
+///     .wrap(opcode::SLOAD, metered)
+///     .disable(opcode::SELFDESTRUCT)
+///     .since(SpecId::CANCUN)
+///     .reprice(opcode::ADD, 5);
+///
+/// let london = builder.build(SpecId::LONDON);
+/// let cancun = builder.build(SpecId::CANCUN);
+/// assert_eq!(london[opcode::ADD as usize].static_gas(), 3);
+/// assert_eq!(cancun[opcode::ADD as usize].static_gas(), 5);
+/// assert!(cancun[opcode::SLOAD as usize].is_wrapped());
+/// ```
+#[derive(Debug)]
+pub struct InstructionTableBuilder<W: InterpreterTypes, H: ?Sized> {
+    /// Table the changes are applied to.
+    base: Box<InstructionTable<W, H>>,
+    /// Opcode changes with the spec from which they are active.
+    changes: Vec<(SpecId, u8, Change<W, H>)>,
+    /// Spec from which newly added changes are active.
+    since: SpecId,
+}
+
+impl<W: InterpreterTypes, H: Host + ?Sized> Clone for InstructionTableBuilder<W, H> {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            changes: self.changes.clone(),
+            since: self.since,
+        }
+    }
+}
+
+impl<W: InterpreterTypes, H: Host> InstructionTableBuilder<W, H> {
+    /// Creates a new builder with the mainnet instruction table as base.
+    pub fn mainnet() -> Self {
+        Self::new(instruction_table::<W, H>())
+    }
+}
+
+impl<W: InterpreterTypes, H: Host + ?Sized> InstructionTableBuilder<W, H> {
+    /// Creates a new builder with the given base table.
+    pub fn new(base: InstructionTable<W, H>) -> Self {
+        Self {
+            base: Box::new(base),
+            changes: Vec::new(),
+            since: SpecId::FRONTIER,
+        }
+    }
+
+    /// Sets the spec from which the changes added after this call are active.
+    ///
+    /// Changes added before the first call are active in all specs.
+    pub fn since(mut self, spec: SpecId) -> Self {
+        self.since = spec;
+        self
+    }
+
+    /// Replaces the instruction of the opcode.
+    pub fn insert(self, opcode: u8, instruction: Instruction<W, H>) -> Self {
+        self.push(opcode, Change::Insert(instruction))
+    }
+
+    /// Wraps the instruction of the opcode, see [`Instruction::wrap`].
+    pub fn wrap(self, opcode: u8, wrapper: InstructionWrapper<W, H>) -> Self {
+        self.push(opcode, Change::Wrap(wrapper))
+    }
+
+    /// Disables the opcode, executing it halts with
+    /// [`NotActivated`](crate::InstructionResult::NotActivated).
+    pub fn disable(self, opcode: u8) -> Self {
+        self.push(opcode, Change::Disable)
+    }
+
+    /// Sets the static gas of the opcode.
+    pub fn reprice(self, opcode: u8, static_gas: u64) -> Self {
+        self.push(opcode, Change::Reprice(static_gas))
+    }
+
+    /// Builds the instruction table with the changes that are active in the given spec.
+    ///
+    /// Rebuilding a table reuses the wrapped instructions of the previous builds, see
+    /// [`Instruction::wrap`].
+    pub fn build(&self, spec: SpecId) -> InstructionTable<W, H> {
+        let mut table = *self.base;
+        for (since, opcode, change) in &self.changes {
+            if !spec.is_enabled_in(*since) {
+                continue;
+            }
+            let instruction = &mut table[*opcode as usize];
+            *instruction = match change {
+                Change::Insert(new) => *new,
+                Change::Wrap(wrapper) => instruction.wrap(*wrapper),
+                Change::Disable => Instruction::not_activated(),
+                Change::Reprice(gas) => instruction.with_static_gas(*gas),
+            };
+        }
+        table
+    }
+
+    fn push(mut self, opcode: u8, change: Change<W, H>) -> Self {
+        self.changes.push((self.since, opcode, change));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interpreter::{EthInterpreter, ExtBytecode},
+        InputsImpl, InstructionContext, InstructionResult, Interpreter, MockHost, SharedMemory,
+    };
+    use bytecode::{opcode, Bytecode};
+    use primitives::{Bytes, U256};
+
+    type Table = InstructionTable<EthInterpreter, MockHost>;
+
+    /// Runs the code and returns the result, gas used and the top of the stack.
+    fn run(table: &Table, code: &[u8]) -> (InstructionResult, u64, Option<U256>) {
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            SharedMemory::new(),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(code))),
+            InputsImpl::default(),
+            false,
+            SpecId::default(),
+            100_000,
+        );
+        let action = interpreter.run_plain(table, &mut MockHost::default());
+        (
+            action.instruction_result().unwrap(),
+            interpreter.gas.spent(),
+            interpreter.stack.data().last().copied(),
+        )
+    }
+
+    /// Charges 100 additional gas.
+    fn metered(
+        context: InstructionContext<'_, MockHost, EthInterpreter>,
+        next: Instruction<EthInterpreter, MockHost>,
+    ) {
+        gas!(context.interpreter, 100);
+        next.execute(context);
+    }
+
+    /// Doubles the result of the wrapped instruction.
+    fn doubled(
+        mut context: InstructionContext<'_, MockHost, EthInterpreter>,
+        next: Instruction<EthInterpreter, MockHost>,
+    ) {
+        next.execute(context.reborrow());
+        if let Some(top) = context.interpreter.stack.data_mut().last_mut() {
+            *top *= U256::from(2);
+        }
+    }
+
+    const ADD_CODE: &[u8] = &[
+        opcode::PUSH1,
+        1,
+        opcode::PUSH1,
+        2,
+        opcode::ADD,
+        opcode::STOP,
+    ];
+
+    #[test]
+    fn unchanged() {
+        let builder = InstructionTableBuilder::mainnet();
+        let table = builder.build(SpecId::default());
+        assert_eq!(
+            run(&table, ADD_CODE),
+            (InstructionResult::Stop, 9, Some(U256::from(3)))
+        );
+    }
+
+    #[test]
+    fn wrap_and_compose() {
+        let table = InstructionTableBuilder::mainnet()
+            .wrap(opcode::ADD, metered)
+            .build(SpecId::default());
+        assert_eq!(
+            run(&table, ADD_CODE),
+            (InstructionResult::Stop, 109, Some(U256::from(3)))
+        );
+
+        let table = InstructionTableBuilder::mainnet()
+            .wrap(opcode::ADD, metered)
+            .wrap(opcode::ADD, doubled)
+            .reprice(opcode::ADD, 10)
+            .build(SpecId::default());
+        assert_eq!(
+            run(&table, ADD_CODE),
+            (InstructionResult::Stop, 116, Some(U256::from(6)))
+        );
+    }
+
+    #[test]
+    fn rebuild_reuses_wrapped_instructions() {
+        let builder = InstructionTableBuilder::mainnet()
+            .wrap(opcode::ADD, metered)
+            .wrap(opcode::ADD, doubled);
+        let first = builder.build(SpecId::default())[opcode::ADD as usize];
+        for _ in 0..10 {
+            let table = builder.build(SpecId::default());
+            assert!(table[opcode::ADD as usize].is_same(&first));
+        }
+        assert!(first.is_wrapped());
+        assert!(!builder.build(SpecId::default())[opcode::SUB as usize].is_wrapped());
+    }
+
+    #[test]
+    fn wrap_all_opcodes_twice() {
+        let mut builder = InstructionTableBuilder::mainnet();
+        for opcode in 0..=u8::MAX {
+            builder = builder.wrap(opcode, metered);
+        }
+        for opcode in 0..=u8::MAX {
+            builder = builder.wrap(opcode, doubled);
+        }
+        for spec in [SpecId::LONDON, SpecId::CANCUN, SpecId::PRAGUE] {
+            let table = builder.build(spec);
+            assert!(table.iter().all(Instruction::is_wrapped));
+        }
+        // All four instructions are metered and double the top of the stack: 2, 4, 12, 24.
+        assert_eq!(
+            run(&builder.build(SpecId::default()), ADD_CODE),
+            (InstructionResult::Stop, 409, Some(U256::from(24)))
+        );
+    }
+
+    #[test]
+    fn disable() {
+        let table = InstructionTableBuilder::mainnet()
+            .disable(opcode::ADD)
+            .build(SpecId::default());
+        assert_eq!(run(&table, ADD_CODE).0, InstructionResult::NotActivated);
+    }
+
+    #[test]
+    fn per_spec() {
+        let builder = InstructionTableBuilder::mainnet()
+            .reprice(opcode::ADD, 4)
+            .since(SpecId::CANCUN)
+            .disable(opcode::ADD)
+            .since(SpecId::PRAGUE)
+            .insert(
+                opcode::ADD,
+                Instruction::new(crate::instructions::arithmetic::mul, 1),
+            );
+
+        let table = builder.build(SpecId::SHANGHAI);
+        assert_eq!(
+            run(&table, ADD_CODE),
+            (InstructionResult::Stop, 10, Some(U256::from(3)))
+        );
+        let table = builder.build(SpecId::CANCUN);
+        assert_eq!(run(&table, ADD_CODE).0, InstructionResult::NotActivated);
+        let table = builder.build(SpecId::PRAGUE);
+        assert_eq!(
+            run(&table, ADD_CODE),
+            (InstructionResult::Stop, 7, Some(U256::from(2)))
+        );
+    }
+}
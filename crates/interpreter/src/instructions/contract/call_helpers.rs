@@ -1,8 +1,5 @@
 use crate::{
-    gas::{
-        self, calc_call_static_gas, COLD_ACCOUNT_ACCESS_COST_ADDITIONAL, NEWACCOUNT,
-        WARM_STORAGE_READ_COST,
-    },
+    gas::NEWACCOUNT,
     interpreter::Interpreter,
     interpreter_types::{InterpreterTypes, MemoryTr, RuntimeFlag, StackTr},
     InstructionContext,
@@ -61,9 +58,8 @@ pub fn load_acc_and_calc_gas<H: Host + ?Sized>(
     create_empty_account: bool,
     stack_gas_limit: u64,
 ) -> Option<(u64, Bytecode, B256)> {
-    let spec = context.interpreter.runtime_flag.spec_id();
     // calculate static gas first. For berlin hardfork it will take warm gas.
-    let static_gas = calc_call_static_gas(spec, transfers_value);
+    let static_gas = context.host.gas_params().call_static_cost(transfers_value);
     gas!(context.interpreter, static_gas, None);
 
     // load account delegated and deduct dynamic gas.
@@ -85,7 +81,7 @@ pub fn load_acc_and_calc_gas<H: Host + ?Sized>(
     gas!(interpreter, gas_limit, None);
     // Add call stipend if there is value to be transferred.
     if transfers_value {
-        gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
+        gas_limit = gas_limit.saturating_add(context.host.gas_params().call_stipend);
     }
 
     Some((gas_limit, bytecode, code_hash))
@@ -135,36 +131,40 @@ pub fn load_account_delegated<H: Host + ?Sized>(
     let mut cost = 0;
     let is_berlin = spec.is_enabled_in(SpecId::BERLIN);
     let is_spurious_dragon = spec.is_enabled_in(SpecId::SPURIOUS_DRAGON);
+    let params = host.gas_params();
+    let warm_access = params.warm_account_access;
+    let cold_additional = params.cold_account_access_additional();
+    let new_account = params.new_account_cost(is_spurious_dragon, transfers_value);
 
-    let skip_cold_load = is_berlin && remaining_gas < COLD_ACCOUNT_ACCESS_COST_ADDITIONAL;
+    let skip_cold_load = is_berlin && remaining_gas < cold_additional;
     let account = host.load_account_info_skip_cold_load(address, true, skip_cold_load)?;
     if is_berlin && account.is_cold {
-        cost += COLD_ACCOUNT_ACCESS_COST_ADDITIONAL;
+        cost += cold_additional;
     }
     let mut bytecode = account.code.clone().unwrap_or_default();
     let mut code_hash = account.code_hash();
     // New account cost, as account is empty there is no delegated account and we can return early.
     if create_empty_account && account.is_empty {
-        cost += new_account_cost(is_spurious_dragon, transfers_value);
+        cost += new_account;
         return Ok((cost, bytecode, code_hash));
     }
 
     // load delegate code if account is EIP-7702
     if let Some(Bytecode::Eip7702(code)) = &account.code {
         // EIP-7702 is enabled after berlin hardfork.
-        cost += WARM_STORAGE_READ_COST;
+        cost += warm_access;
         if cost > remaining_gas {
             return Err(LoadError::ColdLoadSkipped);
         }
         let address = code.address();
 
         // skip cold load if there is enough gas to cover the cost.
-        let skip_cold_load = remaining_gas < cost + COLD_ACCOUNT_ACCESS_COST_ADDITIONAL;
+        let skip_cold_load = remaining_gas < cost + cold_additional;
         let delegate_account =
             host.load_account_info_skip_cold_load(address, true, skip_cold_load)?;
 
         if delegate_account.is_cold {
-            cost += COLD_ACCOUNT_ACCESS_COST_ADDITIONAL;
+            cost += cold_additional;
         }
         bytecode = delegate_account.code.clone().unwrap_or_default();
         code_hash = delegate_account.code_hash();
@@ -117,3 +117,8 @@ pub fn invalid<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_
 pub fn unknown<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     context.interpreter.halt(InstructionResult::OpcodeNotFound);
 }
+
+/// Halts with `NotActivated`, used for opcodes disabled in the instruction table.
+pub fn not_activated<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionContext<'_, H, WIRE>) {
+    context.interpreter.halt_not_activated();
+}
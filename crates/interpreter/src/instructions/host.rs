@@ -1,9 +1,5 @@
 use crate::{
-    gas::{
-        self, selfdestruct_cold_beneficiary_cost, CALL_STIPEND,
-        COLD_ACCOUNT_ACCESS_COST_ADDITIONAL, COLD_SLOAD_COST_ADDITIONAL, ISTANBUL_SLOAD_GAS,
-        WARM_STORAGE_READ_COST,
-    },
+    gas,
     instructions::utility::{IntoAddress, IntoU256},
     interpreter_types::{InputsTr, InterpreterTypes, MemoryTr, RuntimeFlag, StackTr},
     Host, InstructionResult,
@@ -21,19 +17,11 @@ pub fn balance<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCon
     popn_top!([], top, context.interpreter);
     let address = top.into_address();
     let spec_id = context.interpreter.runtime_flag.spec_id();
+    gas!(context.interpreter, context.host.gas_params().balance);
     if spec_id.is_enabled_in(BERLIN) {
         let account = berlin_load_account!(context, address, false);
         *top = account.balance;
     } else {
-        let gas = if spec_id.is_enabled_in(ISTANBUL) {
-            // EIP-1884: Repricing for trie-size-dependent opcodes
-            700
-        } else if spec_id.is_enabled_in(TANGERINE) {
-            400
-        } else {
-            20
-        };
-        gas!(context.interpreter, gas);
         let Ok(account) = context
             .host
             .load_account_info_skip_cold_load(address, false, false)
@@ -69,17 +57,12 @@ pub fn extcodesize<WIRE: InterpreterTypes, H: Host + ?Sized>(
     popn_top!([], top, context.interpreter);
     let address = top.into_address();
     let spec_id = context.interpreter.runtime_flag.spec_id();
+    gas!(context.interpreter, context.host.gas_params().extcodesize);
     if spec_id.is_enabled_in(BERLIN) {
         let account = berlin_load_account!(context, address, true);
         // safe to unwrap because we are loading code
         *top = U256::from(account.code.as_ref().unwrap().len());
     } else {
-        let gas = if spec_id.is_enabled_in(TANGERINE) {
-            700
-        } else {
-            20
-        };
-        gas!(context.interpreter, gas);
         let Ok(account) = context
             .host
             .load_account_info_skip_cold_load(address, true, false)
@@ -100,15 +83,10 @@ pub fn extcodehash<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let address = top.into_address();
 
     let spec_id = context.interpreter.runtime_flag.spec_id();
+    gas!(context.interpreter, context.host.gas_params().extcodehash);
     let account = if spec_id.is_enabled_in(BERLIN) {
         berlin_load_account!(context, address, true)
     } else {
-        let gas = if spec_id.is_enabled_in(ISTANBUL) {
-            700
-        } else {
-            400
-        };
-        gas!(context.interpreter, gas);
         let Ok(account) = context
             .host
             .load_account_info_skip_cold_load(address, true, false)
@@ -154,17 +132,11 @@ pub fn extcodecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
         resize_memory!(context.interpreter, memory_offset_usize, len);
     }
 
+    gas!(context.interpreter, context.host.gas_params().extcodecopy);
     let code = if spec_id.is_enabled_in(BERLIN) {
         let account = berlin_load_account!(context, address, true);
         account.code.as_ref().unwrap().original_bytes()
     } else {
-        let gas = if spec_id.is_enabled_in(TANGERINE) {
-            700
-        } else {
-            20
-        };
-        gas!(context.interpreter, gas);
-
         let Some(code) = context.host.load_account_code(address) else {
             return context.interpreter.halt_fatal();
         };
@@ -224,26 +196,16 @@ pub fn sload<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionConte
     let spec_id = context.interpreter.runtime_flag.spec_id();
     let target = context.interpreter.input.target_address();
 
-    // `SLOAD` opcode cost calculation.
-    let gas = if spec_id.is_enabled_in(BERLIN) {
-        WARM_STORAGE_READ_COST
-    } else if spec_id.is_enabled_in(ISTANBUL) {
-        // EIP-1884: Repricing for trie-size-dependent opcodes
-        ISTANBUL_SLOAD_GAS
-    } else if spec_id.is_enabled_in(TANGERINE) {
-        // EIP-150: Gas cost changes for IO-heavy operations
-        200
-    } else {
-        50
-    };
-    gas!(context.interpreter, gas);
+    let params = context.host.gas_params();
+    let cold_additional = params.cold_sload_additional();
+    gas!(context.interpreter, params.sload);
     if spec_id.is_enabled_in(BERLIN) {
-        let skip_cold = context.interpreter.gas.remaining() < COLD_SLOAD_COST_ADDITIONAL;
+        let skip_cold = context.interpreter.gas.remaining() < cold_additional;
         let res = context.host.sload_skip_cold_load(target, *index, skip_cold);
         match res {
             Ok(storage) => {
                 if storage.is_cold {
-                    gas!(context.interpreter, COLD_SLOAD_COST_ADDITIONAL);
+                    gas!(context.interpreter, cold_additional);
                 }
 
                 *index = storage.data;
@@ -268,15 +230,11 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
 
     let target = context.interpreter.input.target_address();
     let spec_id = context.interpreter.runtime_flag.spec_id();
+    let is_istanbul = spec_id.is_enabled_in(ISTANBUL);
+    let params = *context.host.gas_params();
 
     // EIP-1706 Disable SSTORE with gasleft lower than call stipend
-    if context
-        .interpreter
-        .runtime_flag
-        .spec_id()
-        .is_enabled_in(ISTANBUL)
-        && context.interpreter.gas.remaining() <= CALL_STIPEND
-    {
+    if is_istanbul && context.interpreter.gas.remaining() <= params.call_stipend {
         context
             .interpreter
             .halt(InstructionResult::ReentrancySentryOOG);
@@ -284,13 +242,10 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
     }
 
     // static gas
-    gas!(
-        context.interpreter,
-        gas::static_sstore_cost(context.interpreter.runtime_flag.spec_id())
-    );
+    gas!(context.interpreter, params.sstore_static_cost(is_istanbul));
 
     let state_load = if spec_id.is_enabled_in(BERLIN) {
-        let skip_cold = context.interpreter.gas.remaining() < COLD_SLOAD_COST_ADDITIONAL;
+        let skip_cold = context.interpreter.gas.remaining() < params.cold_sload_additional();
         let res = context
             .host
             .sstore_skip_cold_load(target, index, value, skip_cold);
@@ -309,18 +264,14 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
     // dynamic gas
     gas!(
         context.interpreter,
-        gas::dyn_sstore_cost(
-            context.interpreter.runtime_flag.spec_id(),
-            &state_load.data,
-            state_load.is_cold
-        )
+        params.sstore_dynamic_cost(is_istanbul, &state_load.data, state_load.is_cold)
     );
 
     // refund
-    context.interpreter.gas.record_refund(gas::sstore_refund(
-        context.interpreter.runtime_flag.spec_id(),
-        &state_load.data,
-    ));
+    context
+        .interpreter
+        .gas
+        .record_refund(params.sstore_refund(is_istanbul, &state_load.data));
 }
 
 /// EIP-1153: Transient storage opcodes
@@ -392,11 +343,12 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
     popn!([target], context.interpreter);
     let target = target.into_address();
     let spec = context.interpreter.runtime_flag.spec_id();
+    let params = context.host.gas_params();
 
     // static gas
-    gas!(context.interpreter, gas::static_selfdestruct_cost(spec));
+    gas!(context.interpreter, params.selfdestruct);
 
-    let skip_cold = context.interpreter.gas.remaining() < selfdestruct_cold_beneficiary_cost(spec);
+    let skip_cold = context.interpreter.gas.remaining() < params.cold_account_access;
     let res = match context.host.selfdestruct(
         context.interpreter.input.target_address(),
         target,
@@ -407,20 +359,18 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
         Err(LoadError::DBError) => return context.interpreter.halt_fatal(),
     };
 
-    gas!(context.interpreter, gas::dyn_selfdestruct_cost(spec, &res));
+    let params = context.host.gas_params();
+    gas!(
+        context.interpreter,
+        params.selfdestruct_dynamic_cost(spec.is_enabled_in(SPURIOUS_DRAGON), &res)
+    );
 
     // EIP-3529: Reduction in refunds
-    if !context
-        .interpreter
-        .runtime_flag
-        .spec_id()
-        .is_enabled_in(LONDON)
-        && !res.previously_destroyed
-    {
+    if !res.previously_destroyed {
         context
             .interpreter
             .gas
-            .record_refund(gas::SELFDESTRUCT_REFUND);
+            .record_refund(params.selfdestruct_refund as i64);
     }
 
     context.interpreter.halt(InstructionResult::SelfDestruct);
@@ -73,6 +73,9 @@ macro_rules! gas {
 }
 
 /// Loads account and account berlin gas cost accounting.
+///
+/// Warm access cost is expected to be charged by the instruction, only the additional cold
+/// access cost from [`GasParams`](crate::gas::GasParams) is charged here.
 #[macro_export]
 #[collapse_debuginfo(yes)]
 macro_rules! berlin_load_account {
@@ -80,20 +83,15 @@ macro_rules! berlin_load_account {
         $crate::berlin_load_account!($context, $address, $load_code, ())
     };
     ($context:expr, $address:expr, $load_code:expr, $ret:expr) => {{
-        $crate::gas!($context.interpreter, WARM_STORAGE_READ_COST, $ret);
-        let skip_cold_load =
-            $context.interpreter.gas.remaining() < COLD_ACCOUNT_ACCESS_COST_ADDITIONAL;
+        let cold_additional = $context.host.gas_params().cold_account_access_additional();
+        let skip_cold_load = $context.interpreter.gas.remaining() < cold_additional;
         match $context
             .host
             .load_account_info_skip_cold_load($address, $load_code, skip_cold_load)
         {
             Ok(account) => {
                 if account.is_cold {
-                    $crate::gas!(
-                        $context.interpreter,
-                        COLD_ACCOUNT_ACCESS_COST_ADDITIONAL,
-                        $ret
-                    );
+                    $crate::gas!($context.interpreter, cold_additional, $ret);
                 }
                 account
             }
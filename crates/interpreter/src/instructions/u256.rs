@@ -0,0 +1,248 @@
+//! Fast paths for unsigned 256-bit arithmetic used by the arithmetic instructions.
+//!
+//! Operands seen in practice are mostly small or already reduced, so every function first checks
+//! for cheap special cases and falls back to the generic [`U256`](primitives::U256)
+//! implementation otherwise.
+//!
+//! Results are identical to the corresponding [`U256`](primitives::U256) methods, except that
+//! division and remainder by zero return zero as defined by the EVM.
+use primitives::U256;
+
+/// Returns the value if it fits in one limb.
+#[inline(always)]
+fn as_u64(x: &U256) -> Option<u64> {
+    let limbs = x.as_limbs();
+    (limbs[1] | limbs[2] | limbs[3] == 0).then_some(limbs[0])
+}
+
+/// Returns the value if it fits in two limbs.
+#[inline(always)]
+fn as_u128(x: &U256) -> Option<u128> {
+    let limbs = x.as_limbs();
+    (limbs[2] | limbs[3] == 0).then_some(limbs[0] as u128 | (limbs[1] as u128) << 64)
+}
+
+/// Multiplies by a single limb, wrapping on overflow.
+#[inline]
+fn mul_limb(a: &U256, b: u64) -> U256 {
+    let mut result = [0u64; 4];
+    let mut carry = 0u128;
+    for (r, &a) in result.iter_mut().zip(a.as_limbs()) {
+        let t = a as u128 * b as u128 + carry;
+        *r = t as u64;
+        carry = t >> 64;
+    }
+    U256::from_limbs(result)
+}
+
+/// Wrapping multiplication, same as [`U256::wrapping_mul`].
+#[inline]
+pub fn mul(a: U256, b: U256) -> U256 {
+    match (as_u64(&a), as_u64(&b)) {
+        (Some(a), Some(b)) => U256::from(a as u128 * b as u128),
+        (_, Some(b)) => mul_limb(&a, b),
+        (Some(a), _) => mul_limb(&b, a),
+        _ => a.wrapping_mul(b),
+    }
+}
+
+/// Division, zero if the divisor is zero.
+#[inline]
+pub fn div(a: U256, b: U256) -> U256 {
+    if let Some(a) = as_u128(&a) {
+        return match as_u128(&b) {
+            Some(0) | None => U256::ZERO,
+            Some(b) => match (u64::try_from(a), u64::try_from(b)) {
+                // Same operation on `u64`, native 64-bit division is much cheaper than the
+                // 128-bit one.
+                (Ok(a), Ok(b)) => U256::from(a / b),
+                _ => U256::from(a / b),
+            },
+        };
+    }
+    if b.is_zero() {
+        return U256::ZERO;
+    }
+    a.wrapping_div(b)
+}
+
+/// Remainder, zero if the divisor is zero.
+#[inline]
+pub fn rem(a: U256, b: U256) -> U256 {
+    if let Some(a) = as_u128(&a) {
+        return match as_u128(&b) {
+            Some(0) => U256::ZERO,
+            None => U256::from(a),
+            Some(b) => match (u64::try_from(a), u64::try_from(b)) {
+                // Same operation on `u64`, see `div`.
+                (Ok(a), Ok(b)) => U256::from(a % b),
+                _ => U256::from(a % b),
+            },
+        };
+    }
+    if b.is_zero() {
+        return U256::ZERO;
+    }
+    a.wrapping_rem(b)
+}
+
+/// Modular addition `(a + b) % n` without intermediate overflow, same as [`U256::add_mod`].
+#[inline]
+pub fn add_mod(a: U256, b: U256, n: U256) -> U256 {
+    if n.is_zero() {
+        return U256::ZERO;
+    }
+    if a < n && b < n {
+        // The sum is less than `2n`, so it is reduced with at most one subtraction.
+        let (sum, overflow) = a.overflowing_add(b);
+        return if overflow || sum >= n {
+            sum.wrapping_sub(n)
+        } else {
+            sum
+        };
+    }
+    a.add_mod(b, n)
+}
+
+/// Modular multiplication `(a * b) % n` without intermediate overflow, same as
+/// [`U256::mul_mod`].
+#[inline]
+pub fn mul_mod(a: U256, b: U256, n: U256) -> U256 {
+    if n.is_zero() {
+        return U256::ZERO;
+    }
+    if let Some(n) = as_u64(&n) {
+        let (a, b) = match (as_u64(&a), as_u64(&b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => (
+                as_u64(&rem(a, U256::from(n))).unwrap(),
+                as_u64(&rem(b, U256::from(n))).unwrap(),
+            ),
+        };
+        let product = a as u128 * b as u128;
+        // Native 64-bit division is much cheaper than the 128-bit one.
+        return match u64::try_from(product) {
+            Ok(product) => U256::from(product % n),
+            Err(_) => U256::from((product % n as u128) as u64),
+        };
+    }
+    if let (Some(a), Some(b)) = (as_u128(&a), as_u128(&b)) {
+        // The product of two 128-bit values fits in 256 bits.
+        return rem(U256::from(a).wrapping_mul(U256::from(b)), n);
+    }
+    a.mul_mod(b, n)
+}
+
+/// Exponentiation, wrapping on overflow, same as [`U256::pow`].
+#[inline]
+pub fn pow(base: U256, exp: U256) -> U256 {
+    if exp.is_zero() {
+        return U256::from(1);
+    }
+    if base <= U256::from(1) || exp == U256::from(1) {
+        return base;
+    }
+    if base.count_ones() == 1 {
+        // `2^(k * exp)` is zero once the shift reaches 256 bits.
+        let shift = match as_u64(&exp) {
+            Some(exp) if exp < 256 => base.trailing_zeros() as u64 * exp,
+            _ => return U256::ZERO,
+        };
+        return if shift < 256 {
+            U256::from(1) << shift as usize
+        } else {
+            U256::ZERO
+        };
+    }
+    let Some(mut exp) = as_u64(&exp) else {
+        return base.pow(exp);
+    };
+    // Square-and-multiply over a single limb, skipping the final squaring.
+    let mut result = U256::from(1);
+    let mut base = base;
+    loop {
+        if exp & 1 == 1 {
+            result = result.wrapping_mul(base);
+        }
+        exp >>= 1;
+        if exp == 0 {
+            return result;
+        }
+        base = base.wrapping_mul(base);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    /// Operands that hit both the fast paths and the fallbacks.
+    fn operand() -> impl Strategy<Value = U256> {
+        prop_oneof![
+            (0u64..4).prop_map(U256::from),
+            (0u64..300).prop_map(U256::from),
+            any::<u64>().prop_map(U256::from),
+            any::<u128>().prop_map(U256::from),
+            any::<[u64; 3]>().prop_map(|[a, b, c]| U256::from_limbs([a, b, c, 0])),
+            (0usize..256).prop_map(|shift| U256::from(1) << shift),
+            (0u64..4).prop_map(|x| U256::MAX - U256::from(x)),
+            (0usize..256).prop_map(|shift| U256::MAX >> shift),
+            any::<[u64; 4]>().prop_map(U256::from_limbs),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        /// Differential test against the generic implementation.
+        #[test]
+        fn matches_generic(a in operand(), b in operand(), n in operand()) {
+            prop_assert_eq!(mul(a, b), a.wrapping_mul(b));
+            let (q, r) = if b.is_zero() {
+                (U256::ZERO, U256::ZERO)
+            } else {
+                (a.wrapping_div(b), a.wrapping_rem(b))
+            };
+            prop_assert_eq!(div(a, b), q);
+            prop_assert_eq!(rem(a, b), r);
+            prop_assert_eq!(add_mod(a, b, n), a.add_mod(b, n));
+            prop_assert_eq!(mul_mod(a, b, n), a.mul_mod(b, n));
+            prop_assert_eq!(pow(a, b), a.pow(b));
+        }
+    }
+
+    #[test]
+    fn edge_cases() {
+        let values = [
+            U256::ZERO,
+            U256::from(1),
+            U256::from(2),
+            U256::from(3),
+            U256::from(u64::MAX),
+            U256::from(u64::MAX) + U256::from(1),
+            U256::from(u128::MAX),
+            U256::from(u128::MAX) + U256::from(1),
+            U256::from(1) << 255,
+            U256::MAX - U256::from(1),
+            U256::MAX,
+        ];
+        for a in values {
+            for b in values {
+                assert_eq!(mul(a, b), a.wrapping_mul(b));
+                if !b.is_zero() {
+                    assert_eq!(div(a, b), a.wrapping_div(b));
+                    assert_eq!(rem(a, b), a.wrapping_rem(b));
+                }
+                assert_eq!(pow(a, b), a.pow(b));
+                for n in values {
+                    assert_eq!(add_mod(a, b, n), a.add_mod(b, n));
+                    assert_eq!(mul_mod(a, b, n), a.mul_mod(b, n));
+                }
+            }
+        }
+        assert_eq!(div(U256::from(1), U256::ZERO), U256::ZERO);
+        assert_eq!(rem(U256::from(1), U256::ZERO), U256::ZERO);
+    }
+}
@@ -0,0 +1,120 @@
+//! Interned wrappers of instructions wrapped with [`Instruction::wrap`].
+//!
+//! A wrapped instruction points to a [`Node`] that holds the wrapper and the wrapped instruction.
+//! Nodes form a process-wide list that only grows, and equal pairs share a node, so wrapping the
+//! same instruction again, e.g. when a table is rebuilt, does not allocate.
+use super::{Instruction, InstructionWrapper};
+use crate::{Host, InstructionContext, InterpreterTypes};
+use core::ptr::NonNull;
+use primitives::OnceLock;
+use std::boxed::Box;
+
+/// Wrapper together with the instruction it wraps.
+///
+/// The layout does not depend on the interpreter types, which lets [`Node::is`] compare pairs of
+/// different types.
+#[repr(C)]
+#[derive(Debug)]
+struct Wrapped<W: InterpreterTypes, H: ?Sized> {
+    wrapper: InstructionWrapper<W, H>,
+    next: Instruction<W, H>,
+}
+
+/// Interned [`Wrapped`] pair of some interpreter types.
+#[derive(Debug)]
+pub(super) struct Node {
+    /// Leaked `Wrapped` pair, it is never mutated or freed.
+    wrapped: NonNull<()>,
+    /// Next node of the list.
+    next: OnceLock<Box<Node>>,
+}
+
+// SAFETY: `wrapped` points to an immutable allocation that lives for the rest of the program and
+// only contains function pointers, static gas and references to other nodes.
+unsafe impl Send for Node {}
+// SAFETY: See `Send` implementation.
+unsafe impl Sync for Node {}
+
+/// First node of the list of interned pairs.
+static HEAD: OnceLock<Box<Node>> = OnceLock::new();
+
+impl Node {
+    /// Executes the wrapper of the node with the wrapped instruction as `next`.
+    ///
+    /// # Safety
+    ///
+    /// The node must be returned by [`intern`] for the same interpreter types.
+    #[inline]
+    pub(super) unsafe fn execute<W: InterpreterTypes, H: Host + ?Sized>(
+        &self,
+        ctx: InstructionContext<'_, H, W>,
+    ) {
+        // SAFETY: Ensured by the caller.
+        let wrapped = unsafe { self.get::<W, H>() };
+        (wrapped.wrapper)(ctx, wrapped.next)
+    }
+
+    /// Returns the pair of the node.
+    ///
+    /// # Safety
+    ///
+    /// The node must hold a pair of the same interpreter types, or the functions of the pair must
+    /// only be compared and not called.
+    unsafe fn get<W: InterpreterTypes, H: ?Sized>(&self) -> &Wrapped<W, H> {
+        // SAFETY: Ensured by the caller.
+        unsafe { self.wrapped.cast::<Wrapped<W, H>>().as_ref() }
+    }
+
+    /// Returns `true` if the node holds the given pair.
+    fn is<W: InterpreterTypes, H: Host + ?Sized>(
+        &self,
+        wrapper: InstructionWrapper<W, H>,
+        next: &Instruction<W, H>,
+    ) -> bool {
+        // SAFETY: The node may hold a pair of other interpreter types. `Wrapped` and `Instruction`
+        // are `repr(C)` and function pointers of all signatures have the same layout, so the
+        // fields can be read and compared. The node is only used for these types if the fields
+        // are equal to the given pair, which makes them valid for these types.
+        let wrapped = unsafe { self.get::<W, H>() };
+        core::ptr::fn_addr_eq(wrapped.wrapper, wrapper) && wrapped.next.is_same(next)
+    }
+}
+
+/// Returns the node that holds the pair, adding it to the list if it is not there yet.
+pub(super) fn intern<W: InterpreterTypes, H: Host + ?Sized>(
+    wrapper: InstructionWrapper<W, H>,
+    next: Instruction<W, H>,
+) -> &'static Node {
+    let mut new: Option<Box<Node>> = None;
+    let mut cell = &HEAD;
+    loop {
+        if let Some(node) = cell.get() {
+            if node.is(wrapper, &next) {
+                if let Some(new) = new {
+                    // Another thread added the pair in the meantime.
+                    drop_new::<W, H>(&new);
+                }
+                return node;
+            }
+            cell = &node.next;
+            continue;
+        }
+        let node = new.take().unwrap_or_else(|| {
+            Box::new(Node {
+                wrapped: NonNull::from(Box::leak(Box::new(Wrapped { wrapper, next }))).cast(),
+                next: OnceLock::new(),
+            })
+        });
+        match cell.set(node) {
+            Ok(()) => return cell.get().expect("node is set"),
+            // Another thread added a node in the meantime, it is checked on the next iteration.
+            Err(node) => new = Some(node),
+        }
+    }
+}
+
+/// Frees the pair of a node that was not added to the list.
+fn drop_new<W: InterpreterTypes, H: ?Sized>(node: &Node) {
+    // SAFETY: The pair was leaked in `intern` with the same types and is not shared.
+    drop(unsafe { Box::from_raw(node.wrapped.cast::<Wrapped<W, H>>().as_ptr()) });
+}
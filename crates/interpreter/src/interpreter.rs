@@ -166,7 +166,7 @@ impl Default for Interpreter<EthInterpreter> {
 }
 
 /// Default types for Ethereum interpreter.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct EthInterpreter<EXT = (), MG = SharedMemory> {
     _phantom: core::marker::PhantomData<fn() -> (EXT, MG)>,
 }
@@ -283,6 +283,20 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
         &mut self,
         instruction_table: &InstructionTable<IW, H>,
         host: &mut H,
+    ) {
+        if let Err(limit) = self.bytecode.spend_budget(1) {
+            return self.halt(limit.into());
+        }
+        self.step_unbudgeted(instruction_table, host);
+    }
+
+    /// Executes the instruction at the current instruction pointer without spending the
+    /// execution budget.
+    #[inline]
+    fn step_unbudgeted<H: Host + ?Sized>(
+        &mut self,
+        instruction_table: &InstructionTable<IW, H>,
+        host: &mut H,
     ) {
         // Get current opcode.
         let opcode = self.bytecode.opcode();
@@ -321,8 +335,15 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
         instruction_table: &InstructionTable<IW, H>,
         host: &mut H,
     ) -> InterpreterAction {
-        while self.bytecode.is_not_end() {
-            self.step(instruction_table, host);
+        // The budget is only spent if it is limited, keeping the common loop free of it.
+        if self.bytecode.execution_budget().is_unlimited() {
+            while self.bytecode.is_not_end() {
+                self.step_unbudgeted(instruction_table, host);
+            }
+        } else {
+            while self.bytecode.is_not_end() {
+                self.step(instruction_table, host);
+            }
         }
         self.take_next_action()
     }
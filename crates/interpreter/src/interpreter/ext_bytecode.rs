@@ -1,6 +1,7 @@
 use super::{Immediates, Jumps, LegacyBytecode};
 use crate::{interpreter_types::LoopControl, InterpreterAction};
 use bytecode::{utils::read_u16, Bytecode};
+use context_interface::{ExecutionBudget, ExecutionLimit};
 use core::ops::Deref;
 use primitives::B256;
 
@@ -23,6 +24,8 @@ pub struct ExtBytecode {
     pub action: Option<InterpreterAction>,
     /// The base bytecode.
     base: Bytecode,
+    /// Remaining execution budget, loaded from the local context before the frame is run.
+    budget: ExecutionBudget,
 }
 
 impl Deref for ExtBytecode {
@@ -33,6 +36,22 @@ impl Deref for ExtBytecode {
     }
 }
 
+impl Clone for ExtBytecode {
+    fn clone(&self) -> Self {
+        let mut bytecode = Self {
+            base: self.base.clone(),
+            instruction_pointer: core::ptr::null(),
+            continue_execution: self.continue_execution,
+            bytecode_hash: self.bytecode_hash,
+            action: self.action.clone(),
+            budget: self.budget,
+        };
+        // Instruction pointer points into the cloned bytecode.
+        bytecode.absolute_jump(self.pc());
+        bytecode
+    }
+}
+
 impl Default for ExtBytecode {
     #[inline]
     fn default() -> Self {
@@ -65,6 +84,7 @@ impl ExtBytecode {
             bytecode_hash: hash,
             action: None,
             continue_execution: true,
+            budget: ExecutionBudget::UNLIMITED,
         }
     }
 
@@ -125,6 +145,26 @@ impl LoopControl for ExtBytecode {
     fn action(&mut self) -> &mut Option<InterpreterAction> {
         &mut self.action
     }
+
+    #[inline]
+    fn execution_budget(&self) -> ExecutionBudget {
+        self.budget
+    }
+
+    #[inline]
+    fn set_execution_budget(&mut self, budget: ExecutionBudget) {
+        self.budget = budget;
+    }
+
+    #[inline]
+    fn spend_budget(&mut self, steps: u64) -> Result<(), ExecutionLimit> {
+        self.budget.spend(steps)
+    }
+
+    #[inline]
+    fn refund_budget(&mut self, steps: u64) {
+        self.budget.refund(steps);
+    }
 }
 
 impl Jumps for ExtBytecode {
@@ -213,4 +253,15 @@ mod tests {
         let ext_bytecode = ExtBytecode::new_with_hash(bytecode.clone(), hash);
         assert_eq!(ext_bytecode.bytecode_hash, Some(hash));
     }
+
+    #[test]
+    fn test_clone_keeps_pc() {
+        let bytecode = Bytecode::new_raw(Bytes::from(&[0x60, 0x01, 0x60, 0x02, 0x01][..]));
+        let mut ext_bytecode = ExtBytecode::new(bytecode);
+        ext_bytecode.absolute_jump(2);
+        let cloned = ext_bytecode.clone();
+        drop(ext_bytecode);
+        assert_eq!(cloned.pc(), 2);
+        assert_eq!(cloned.opcode(), 0x60);
+    }
 }
@@ -206,6 +206,20 @@ impl SharedMemory {
         let _ = limit;
     }
 
+    /// Returns the shared buffer, `None` if the memory is invalid.
+    #[inline]
+    pub fn shared_buffer(&self) -> Option<&Rc<RefCell<Vec<u8>>>> {
+        self.buffer.as_ref()
+    }
+
+    /// Replaces the shared buffer, keeping the checkpoints of this context.
+    ///
+    /// Used to share one buffer between deserialized memories of nested calls.
+    #[inline]
+    pub fn set_shared_buffer(&mut self, buffer: Rc<RefCell<Vec<u8>>>) {
+        self.buffer = Some(buffer);
+    }
+
     #[inline]
     fn buffer(&self) -> &Rc<RefCell<Vec<u8>>> {
         debug_assert!(self.buffer.is_some(), "cannot use SharedMemory::empty");
@@ -1,4 +1,5 @@
 use crate::{CallInput, InstructionResult, InterpreterAction};
+use context_interface::{ExecutionBudget, ExecutionLimit};
 use core::{
     cell::Ref,
     ops::{Deref, Range},
@@ -277,6 +278,22 @@ pub trait LoopControl {
             .as_ref()
             .and_then(|action| action.instruction_result())
     }
+    /// Returns the remaining execution budget.
+    #[inline]
+    fn execution_budget(&self) -> ExecutionBudget {
+        ExecutionBudget::UNLIMITED
+    }
+    /// Sets the remaining execution budget.
+    #[inline]
+    fn set_execution_budget(&mut self, _budget: ExecutionBudget) {}
+    /// Spends `steps` from the execution budget, errors if a limit is reached.
+    #[inline]
+    fn spend_budget(&mut self, _steps: u64) -> Result<(), ExecutionLimit> {
+        Ok(())
+    }
+    /// Gives back `steps` that were spent but not executed.
+    #[inline]
+    fn refund_budget(&mut self, _steps: u64) {}
 }
 
 /// Runtime flags that control interpreter execution behavior.
@@ -24,6 +24,10 @@ pub mod interpreter;
 pub mod interpreter_action;
 /// Type traits and definitions for interpreter customization.
 pub mod interpreter_types;
+/// Mock host with an in-memory world state for running the interpreter standalone.
+pub mod mock_host;
+/// Threaded code execution backend for hot bytecode.
+pub mod threaded;
 
 // Reexport primary types.
 pub use context_interface::{
@@ -33,7 +37,10 @@ pub use context_interface::{
 pub use gas::{Gas, InitialAndFloorGas};
 pub use instruction_context::InstructionContext;
 pub use instruction_result::*;
-pub use instructions::{instruction_table, Instruction, InstructionTable};
+pub use instructions::{
+    builder::InstructionTableBuilder, instruction_table, Instruction, InstructionTable,
+    InstructionWrapper,
+};
 pub use interpreter::{
     num_words, InputsImpl, Interpreter, InterpreterResult, SharedMemory, Stack, STACK_LIMIT,
 };
@@ -42,3 +49,7 @@ pub use interpreter_action::{
     FrameInput, InterpreterAction,
 };
 pub use interpreter_types::InterpreterTypes;
+pub use mock_host::MockHost;
+#[cfg(feature = "std")]
+pub use threaded::ThreadedCodeCache;
+pub use threaded::{ThreadedBlock, ThreadedCode};
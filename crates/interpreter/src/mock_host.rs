@@ -0,0 +1,525 @@
+//! Mock [`Host`] with an in-memory world state.
+use crate::{
+    host::LoadError, FrameInput, Host, InstructionTable, Interpreter, InterpreterAction,
+    InterpreterTypes, SStoreResult, SelfDestructResult, StateLoad,
+};
+use context_interface::{cfg::GasParams, journaled_state::AccountInfoLoad};
+use primitives::{
+    eip3860, hardfork::SpecId, Address, HashMap, HashSet, Log, StorageKey, StorageValue, B256, U256,
+};
+use state::{AccountInfo, Bytecode};
+use std::{borrow::Cow, vec::Vec};
+
+/// Host with an in-memory world state, for running bytecode without the handler.
+///
+/// Useful for instruction-level tests and custom VMs that drive [`Interpreter`] directly.
+///
+/// Block, transaction and config values are public fields. Accounts, storage and block hashes
+/// can be set with the `with_*` builder methods or directly in the public maps.
+///
+/// Accounts and storage slots are cold on the first access and warm afterwards, as in a fresh
+/// transaction. Logs and selfdestructs are recorded, and [`MockHost::run`] records the sub-calls
+/// and creates requested by the interpreter.
+///
+/// Block hashes that are not set make `BLOCKHASH` halt with a fatal error, the same as a
+/// database error would.
+#[derive(Clone, Debug)]
+pub struct MockHost {
+    /* Block */
+    /// Block basefee.
+    pub basefee: U256,
+    /// Block blob gasprice.
+    pub blob_gasprice: U256,
+    /// Block gas limit.
+    pub gas_limit: U256,
+    /// Block difficulty.
+    pub difficulty: U256,
+    /// Block prevrandao, `None` before the merge.
+    pub prevrandao: Option<U256>,
+    /// Block number.
+    pub block_number: U256,
+    /// Block timestamp.
+    pub timestamp: U256,
+    /// Block beneficiary.
+    pub beneficiary: Address,
+    /// Chain id.
+    pub chain_id: U256,
+
+    /* Transaction */
+    /// Transaction effective gas price.
+    pub effective_gas_price: U256,
+    /// Transaction caller.
+    pub caller: Address,
+    /// Transaction blob versioned hashes.
+    pub blob_hashes: Vec<B256>,
+
+    /* Config */
+    /// Max initcode size.
+    pub max_initcode_size: usize,
+    /// Gas schedule used by instructions.
+    pub gas_params: GasParams,
+
+    /* State */
+    /// Accounts, missing accounts are empty.
+    pub accounts: HashMap<Address, AccountInfo>,
+    /// Present storage values, missing slots are zero.
+    pub storage: HashMap<(Address, StorageKey), StorageValue>,
+    /// Storage values from before the first write of the slot.
+    pub original_storage: HashMap<(Address, StorageKey), StorageValue>,
+    /// Transient storage values.
+    pub transient_storage: HashMap<(Address, StorageKey), StorageValue>,
+    /// Block hashes by block number.
+    pub block_hashes: HashMap<u64, B256>,
+    /// Warm accounts.
+    pub warm_accounts: HashSet<Address>,
+    /// Warm storage slots.
+    pub warm_slots: HashSet<(Address, StorageKey)>,
+
+    /* Records */
+    /// Emitted logs.
+    pub logs: Vec<Log>,
+    /// Selfdestructs as `(address, target)` pairs.
+    pub selfdestructs: Vec<(Address, Address)>,
+    /// Sub-calls and creates requested by the interpreter.
+    pub frame_inputs: Vec<FrameInput>,
+}
+
+impl Default for MockHost {
+    fn default() -> Self {
+        Self::new(SpecId::default())
+    }
+}
+
+impl MockHost {
+    /// Creates a new empty host with the mainnet gas schedule of the given spec.
+    pub fn new(spec: SpecId) -> Self {
+        Self {
+            basefee: U256::ZERO,
+            blob_gasprice: U256::ZERO,
+            gas_limit: U256::ZERO,
+            difficulty: U256::ZERO,
+            prevrandao: None,
+            block_number: U256::ZERO,
+            timestamp: U256::ZERO,
+            beneficiary: Address::ZERO,
+            chain_id: U256::from(1),
+            effective_gas_price: U256::ZERO,
+            caller: Address::ZERO,
+            blob_hashes: Vec::new(),
+            max_initcode_size: eip3860::MAX_INITCODE_SIZE,
+            gas_params: *GasParams::mainnet(spec),
+            accounts: HashMap::default(),
+            storage: HashMap::default(),
+            original_storage: HashMap::default(),
+            transient_storage: HashMap::default(),
+            block_hashes: HashMap::default(),
+            warm_accounts: HashSet::default(),
+            warm_slots: HashSet::default(),
+            logs: Vec::new(),
+            selfdestructs: Vec::new(),
+            frame_inputs: Vec::new(),
+        }
+    }
+
+    /// Consumes `self` and returns a new host with the given account.
+    pub fn with_account(mut self, address: Address, info: AccountInfo) -> Self {
+        self.accounts.insert(address, info);
+        self
+    }
+
+    /// Consumes `self` and returns a new host with the balance of the account set.
+    pub fn with_balance(mut self, address: Address, balance: U256) -> Self {
+        self.accounts.entry(address).or_default().balance = balance;
+        self
+    }
+
+    /// Consumes `self` and returns a new host with the code of the account set.
+    pub fn with_code(mut self, address: Address, code: Bytecode) -> Self {
+        self.accounts.entry(address).or_default().set_code(code);
+        self
+    }
+
+    /// Consumes `self` and returns a new host with the storage slot set.
+    pub fn with_storage(mut self, address: Address, key: StorageKey, value: StorageValue) -> Self {
+        self.storage.insert((address, key), value);
+        self
+    }
+
+    /// Consumes `self` and returns a new host with the block hash set.
+    pub fn with_block_hash(mut self, number: u64, hash: B256) -> Self {
+        self.block_hashes.insert(number, hash);
+        self
+    }
+
+    /// Consumes `self` and returns a new host with the account marked as warm.
+    pub fn with_warm_account(mut self, address: Address) -> Self {
+        self.warm_accounts.insert(address);
+        self
+    }
+
+    /// Returns the account, if it exists.
+    pub fn account(&self, address: Address) -> Option<&AccountInfo> {
+        self.accounts.get(&address)
+    }
+
+    /// Returns the present value of the storage slot.
+    pub fn storage_value(&self, address: Address, key: StorageKey) -> StorageValue {
+        self.storage
+            .get(&(address, key))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the value of the transient storage slot.
+    pub fn transient_value(&self, address: Address, key: StorageKey) -> StorageValue {
+        self.transient_storage
+            .get(&(address, key))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Runs the interpreter until it returns or stops, recording the requested sub-call or create.
+    pub fn run<IW: InterpreterTypes>(
+        &mut self,
+        interpreter: &mut Interpreter<IW>,
+        instruction_table: &InstructionTable<IW, Self>,
+    ) -> InterpreterAction {
+        let action = interpreter.run_plain(instruction_table, self);
+        if let InterpreterAction::NewFrame(frame_input) = &action {
+            self.frame_inputs.push(frame_input.clone());
+        }
+        action
+    }
+
+    /// Marks the account as warm, returns `Err` if it is cold and cold loads are skipped.
+    fn warm_account(&mut self, address: Address, skip_cold_load: bool) -> Result<bool, LoadError> {
+        let is_cold = !self.warm_accounts.contains(&address);
+        if is_cold {
+            if skip_cold_load {
+                return Err(LoadError::ColdLoadSkipped);
+            }
+            self.warm_accounts.insert(address);
+        }
+        Ok(is_cold)
+    }
+
+    /// Marks the storage slot as warm, returns `Err` if it is cold and cold loads are skipped.
+    fn warm_slot(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        skip_cold_load: bool,
+    ) -> Result<bool, LoadError> {
+        let is_cold = !self.warm_slots.contains(&(address, key));
+        if is_cold {
+            if skip_cold_load {
+                return Err(LoadError::ColdLoadSkipped);
+            }
+            self.warm_slots.insert((address, key));
+        }
+        Ok(is_cold)
+    }
+}
+
+impl Host for MockHost {
+    /* Block */
+
+    fn basefee(&self) -> U256 {
+        self.basefee
+    }
+
+    fn blob_gasprice(&self) -> U256 {
+        self.blob_gasprice
+    }
+
+    fn gas_limit(&self) -> U256 {
+        self.gas_limit
+    }
+
+    fn difficulty(&self) -> U256 {
+        self.difficulty
+    }
+
+    fn prevrandao(&self) -> Option<U256> {
+        self.prevrandao
+    }
+
+    fn block_number(&self) -> U256 {
+        self.block_number
+    }
+
+    fn timestamp(&self) -> U256 {
+        self.timestamp
+    }
+
+    fn beneficiary(&self) -> Address {
+        self.beneficiary
+    }
+
+    fn chain_id(&self) -> U256 {
+        self.chain_id
+    }
+
+    /* Transaction */
+
+    fn effective_gas_price(&self) -> U256 {
+        self.effective_gas_price
+    }
+
+    fn caller(&self) -> Address {
+        self.caller
+    }
+
+    fn blob_hash(&self, number: usize) -> Option<U256> {
+        self.blob_hashes
+            .get(number)
+            .map(|hash| U256::from_be_bytes(hash.0))
+    }
+
+    /* Config */
+
+    fn max_initcode_size(&self) -> usize {
+        self.max_initcode_size
+    }
+
+    fn gas_params(&self) -> &GasParams {
+        &self.gas_params
+    }
+
+    /* Database */
+
+    fn block_hash(&mut self, number: u64) -> Option<B256> {
+        self.block_hashes.get(&number).copied()
+    }
+
+    /* Journal */
+
+    fn selfdestruct(
+        &mut self,
+        address: Address,
+        target: Address,
+        skip_cold_load: bool,
+    ) -> Result<StateLoad<SelfDestructResult>, LoadError> {
+        let is_cold = self.warm_account(target, skip_cold_load)?;
+        let target_exists = self.accounts.get(&target).is_some_and(|a| !a.is_empty());
+        let previously_destroyed = self.selfdestructs.iter().any(|(a, _)| *a == address);
+
+        let balance = self
+            .accounts
+            .get(&address)
+            .map(|a| a.balance)
+            .unwrap_or_default();
+        if address != target && !balance.is_zero() {
+            self.accounts.entry(address).or_default().balance = U256::ZERO;
+            self.accounts.entry(target).or_default().balance += balance;
+        }
+        self.selfdestructs.push((address, target));
+
+        Ok(StateLoad::new(
+            SelfDestructResult {
+                had_value: !balance.is_zero(),
+                target_exists,
+                previously_destroyed,
+            },
+            is_cold,
+        ))
+    }
+
+    fn log(&mut self, log: Log) {
+        self.logs.push(log);
+    }
+
+    fn sstore_skip_cold_load(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+        skip_cold_load: bool,
+    ) -> Result<StateLoad<SStoreResult>, LoadError> {
+        let is_cold = self.warm_slot(address, key, skip_cold_load)?;
+        let present_value = self.storage_value(address, key);
+        let original_value = *self
+            .original_storage
+            .entry((address, key))
+            .or_insert(present_value);
+        self.storage.insert((address, key), value);
+
+        Ok(StateLoad::new(
+            SStoreResult {
+                original_value,
+                present_value,
+                new_value: value,
+            },
+            is_cold,
+        ))
+    }
+
+    fn sload_skip_cold_load(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        skip_cold_load: bool,
+    ) -> Result<StateLoad<StorageValue>, LoadError> {
+        let is_cold = self.warm_slot(address, key, skip_cold_load)?;
+        Ok(StateLoad::new(self.storage_value(address, key), is_cold))
+    }
+
+    fn tstore(&mut self, address: Address, key: StorageKey, value: StorageValue) {
+        self.transient_storage.insert((address, key), value);
+    }
+
+    fn tload(&mut self, address: Address, key: StorageKey) -> StorageValue {
+        self.transient_value(address, key)
+    }
+
+    fn load_account_info_skip_cold_load(
+        &mut self,
+        address: Address,
+        _load_code: bool,
+        skip_cold_load: bool,
+    ) -> Result<AccountInfoLoad<'_>, LoadError> {
+        let is_cold = self.warm_account(address, skip_cold_load)?;
+        let account = match self.accounts.get(&address) {
+            Some(account) => Cow::Borrowed(account),
+            None => Cow::Owned(AccountInfo::default()),
+        };
+        Ok(AccountInfoLoad {
+            is_empty: account.is_empty(),
+            account,
+            is_cold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        instruction_table,
+        interpreter::{EthInterpreter, ExtBytecode},
+        InputsImpl, InstructionResult, SharedMemory,
+    };
+    use bytecode::opcode;
+    use primitives::{address, Bytes};
+
+    const TARGET: Address = address!("0x1000000000000000000000000000000000000001");
+    const OTHER: Address = address!("0x2000000000000000000000000000000000000002");
+
+    fn run(host: &mut MockHost, code: &[u8]) -> InterpreterAction {
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            SharedMemory::new(),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(code))),
+            InputsImpl {
+                target_address: TARGET,
+                ..Default::default()
+            },
+            false,
+            SpecId::default(),
+            1_000_000,
+        );
+        let table = instruction_table::<EthInterpreter, MockHost>();
+        host.run(&mut interpreter, &table)
+    }
+
+    #[test]
+    fn storage_and_logs() {
+        let mut host = MockHost::default()
+            .with_storage(TARGET, U256::from(1), U256::from(7))
+            .with_balance(OTHER, U256::from(100));
+        host.timestamp = U256::from(1234);
+
+        // sstore(2, sload(1) + timestamp), tstore(3, balance(OTHER)), log0(0, 0), stop
+        let code = [
+            opcode::PUSH1,
+            1,
+            opcode::SLOAD,
+            opcode::TIMESTAMP,
+            opcode::ADD,
+            opcode::PUSH1,
+            2,
+            opcode::SSTORE,
+            opcode::PUSH20,
+        ]
+        .into_iter()
+        .chain(OTHER.0)
+        .chain([
+            opcode::BALANCE,
+            opcode::PUSH1,
+            3,
+            opcode::TSTORE,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::LOG0,
+            opcode::STOP,
+        ])
+        .collect::<Vec<_>>();
+        let action = run(&mut host, &code);
+
+        assert_eq!(action.instruction_result(), Some(InstructionResult::Stop));
+        assert_eq!(host.storage_value(TARGET, U256::from(2)), U256::from(1241));
+        assert_eq!(host.original_storage[&(TARGET, U256::from(2))], U256::ZERO);
+        assert_eq!(host.transient_value(TARGET, U256::from(3)), U256::from(100));
+        assert_eq!(host.logs.len(), 1);
+        assert_eq!(host.logs[0].address, TARGET);
+        assert!(host.warm_accounts.contains(&OTHER));
+        assert!(host.warm_slots.contains(&(TARGET, U256::from(1))));
+    }
+
+    #[test]
+    fn records_calls_and_selfdestructs() {
+        let mut host = MockHost::default().with_balance(TARGET, U256::from(5));
+
+        // call(gas, OTHER, 0, 0, 0, 0, 0)
+        let code = [
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH20,
+        ]
+        .into_iter()
+        .chain(OTHER.0)
+        .chain([opcode::GAS, opcode::CALL])
+        .collect::<Vec<_>>();
+        let action = run(&mut host, &code);
+        assert!(action.is_call());
+        assert_eq!(host.frame_inputs.len(), 1);
+        let FrameInput::Call(inputs) = &host.frame_inputs[0] else {
+            panic!("expected call");
+        };
+        assert_eq!(inputs.target_address, OTHER);
+        assert_eq!(inputs.caller, TARGET);
+
+        let code = [opcode::PUSH20]
+            .into_iter()
+            .chain(OTHER.0)
+            .chain([opcode::SELFDESTRUCT])
+            .collect::<Vec<_>>();
+        let action = run(&mut host, &code);
+        assert_eq!(
+            action.instruction_result(),
+            Some(InstructionResult::SelfDestruct)
+        );
+        assert_eq!(host.selfdestructs, [(TARGET, OTHER)]);
+        assert_eq!(host.account(OTHER).unwrap().balance, U256::from(5));
+        assert_eq!(host.account(TARGET).unwrap().balance, U256::ZERO);
+    }
+
+    #[test]
+    fn missing_block_hash_is_fatal() {
+        let mut host = MockHost::default().with_block_hash(9, B256::with_last_byte(1));
+        host.block_number = U256::from(10);
+
+        let code = [opcode::PUSH1, 9, opcode::BLOCKHASH, opcode::STOP];
+        let action = run(&mut host, &code);
+        assert_eq!(action.instruction_result(), Some(InstructionResult::Stop));
+
+        let code = [opcode::PUSH1, 8, opcode::BLOCKHASH, opcode::STOP];
+        let action = run(&mut host, &code);
+        assert_eq!(
+            action.instruction_result(),
+            Some(InstructionResult::FatalExternalError)
+        );
+    }
+}
@@ -0,0 +1,588 @@
+//! Threaded code execution backend.
+//!
+//! [`ThreadedCode`] is a pre-translated form of legacy analyzed bytecode. The code is split into
+//! blocks of instructions with resolved instruction functions, and every block records its
+//! static gas and the stack height it needs. When a block is entered, gas and stack are checked
+//! once for the whole block instead of on every instruction.
+//!
+//! Execution is bit-exact with [`Interpreter::run_plain`]:
+//! * A block ends on every instruction that can observe the remaining gas or consume gas
+//!   dynamically, so prepaid static gas is never visible to an instruction. Instructions that
+//!   differ from the mainnet table, e.g. wrapped or replaced ones, end the block as well.
+//! * Static gas of instructions that were not reached is returned if the block halts early.
+//! * If a block does not have enough gas or stack, instructions are stepped one by one.
+use crate::{
+    instruction_context::InstructionContext,
+    interpreter_types::{InterpreterTypes, Jumps, LoopControl, StackTr},
+    Host, Instruction, InstructionTable, Interpreter, InterpreterAction, STACK_LIMIT,
+};
+use bytecode::{opcode, Bytecode};
+use core::{fmt, ops::Range};
+use std::vec::Vec;
+
+#[cfg(feature = "std")]
+pub use cache::ThreadedCodeCache;
+
+/// Marks a bytecode offset that does not start a block.
+const NO_BLOCK: u32 = u32::MAX;
+
+/// Legacy bytecode translated to threaded code for a specific instruction table.
+///
+/// Threaded code contains instructions of the table it was translated with, it has to be
+/// executed with the same table.
+pub struct ThreadedCode<W: InterpreterTypes, H: ?Sized> {
+    /// Instructions of all blocks, ordered by offset.
+    ops: Vec<ThreadedOp<W, H>>,
+    /// Blocks of instructions.
+    blocks: Vec<ThreadedBlock>,
+    /// Block index for every bytecode offset that starts a block.
+    block_at: Vec<u32>,
+}
+
+/// Instruction of a block.
+struct ThreadedOp<W: InterpreterTypes, H: ?Sized> {
+    /// Resolved instruction.
+    instruction: Instruction<W, H>,
+    /// Static gas of the instructions that follow in the same block.
+    rest_gas: u64,
+}
+
+/// Range of instructions executed with a single gas and stack check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThreadedBlock {
+    /// Index of the first instruction.
+    start: u32,
+    /// Index after the last instruction.
+    end: u32,
+    /// Sum of the static gas of all instructions.
+    static_gas: u64,
+    /// Minimum stack height required at the start of the block.
+    stack_min: u16,
+    /// Maximum stack height growth during the block.
+    stack_max_growth: u16,
+}
+
+impl ThreadedBlock {
+    /// Returns the number of instructions in the block.
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    /// Returns `true` if the block has no instructions.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the sum of the static gas of all instructions.
+    #[inline]
+    pub fn static_gas(&self) -> u64 {
+        self.static_gas
+    }
+
+    /// Returns the minimum stack height required at the start of the block.
+    #[inline]
+    pub fn stack_min(&self) -> usize {
+        self.stack_min as usize
+    }
+
+    /// Returns the maximum stack height growth during the block.
+    #[inline]
+    pub fn stack_max_growth(&self) -> usize {
+        self.stack_max_growth as usize
+    }
+
+    /// Returns `true` if no instruction of the block can fail on stack underflow or overflow.
+    #[inline]
+    pub fn fits_stack(&self, stack_len: usize) -> bool {
+        stack_len >= self.stack_min() && stack_len + self.stack_max_growth() <= STACK_LIMIT
+    }
+
+    #[inline]
+    fn ops(&self) -> Range<usize> {
+        self.start as usize..self.end as usize
+    }
+}
+
+impl<W: InterpreterTypes, H: Host + ?Sized> ThreadedCode<W, H> {
+    /// Translates the bytecode with the given instruction table.
+    ///
+    /// Returns `None` if the bytecode is not legacy analyzed bytecode.
+    pub fn new(bytecode: &Bytecode, instruction_table: &InstructionTable<W, H>) -> Option<Self> {
+        let Bytecode::LegacyAnalyzed(analyzed) = bytecode else {
+            return None;
+        };
+        // Padded bytecode, it always ends with `STOP`.
+        let code = analyzed.bytecode();
+
+        let static_gas = core::array::from_fn(|op| instruction_table[op].static_gas());
+        // Custom instructions can charge dynamic gas or read the remaining gas.
+        let mainnet = crate::instruction_table::<W, H>();
+        let blocks = analyzed.basic_blocks_with_splits(&static_gas, |op| {
+            !is_block_interior(op)
+                || !instruction_table[op as usize].has_same_fn(&mainnet[op as usize])
+        });
+
+        let mut this = Self {
+            ops: Vec::with_capacity(code.len()),
+            blocks: Vec::with_capacity(blocks.len()),
+            block_at: std::vec![NO_BLOCK; code.len()],
+        };
+        for block in blocks.blocks() {
+            let start = this.ops.len();
+            let mut pc = block.start;
+            while pc < block.end {
+                let op = code[pc];
+                let Some(info) = opcode::OPCODE_INFO[op as usize] else {
+                    // Unknown opcodes end the block, their stack effect is unknown so they are
+                    // stepped.
+                    break;
+                };
+                this.ops.push(ThreadedOp {
+                    instruction: instruction_table[op as usize],
+                    rest_gas: 0,
+                });
+                pc += 1 + info.immediate_size() as usize;
+            }
+            if this.ops.len() == start {
+                continue;
+            }
+
+            let mut rest_gas = 0u64;
+            for op in this.ops[start..].iter_mut().rev() {
+                op.rest_gas = rest_gas;
+                rest_gas = rest_gas.saturating_add(op.instruction.static_gas());
+            }
+            this.block_at[block.start] = this.blocks.len() as u32;
+            this.blocks.push(ThreadedBlock {
+                start: start as u32,
+                end: this.ops.len() as u32,
+                static_gas: rest_gas,
+                stack_min: block.stack_min as u16,
+                stack_max_growth: block.stack_max_growth as u16,
+            });
+        }
+        Some(this)
+    }
+
+    /// Returns the block that starts at the given bytecode offset.
+    #[inline]
+    pub fn block_at(&self, pc: usize) -> Option<&ThreadedBlock> {
+        match self.block_at.get(pc) {
+            Some(&index) if index != NO_BLOCK => Some(&self.blocks[index as usize]),
+            _ => None,
+        }
+    }
+
+    /// Returns all blocks ordered by offset.
+    #[inline]
+    pub fn blocks(&self) -> &[ThreadedBlock] {
+        &self.blocks
+    }
+}
+
+impl<W: InterpreterTypes, H: ?Sized> fmt::Debug for ThreadedCode<W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadedCode")
+            .field("ops", &self.ops.len())
+            .field("blocks", &self.blocks)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if the mainnet instruction can be followed by other instructions of the same
+/// block.
+///
+/// These instructions only charge static gas, do not read the remaining gas and do not change
+/// the control flow, so prepaying the static gas of the following instructions is not
+/// observable. Everything else ends the block.
+const fn is_block_interior(op: u8) -> bool {
+    use opcode::*;
+    matches!(
+        op,
+        ADD | MUL
+            | SUB
+            | DIV
+            | SDIV
+            | MOD
+            | SMOD
+            | ADDMOD
+            | MULMOD
+            | SIGNEXTEND
+            | LT
+            | GT
+            | SLT
+            | SGT
+            | EQ
+            | ISZERO
+            | AND
+            | OR
+            | XOR
+            | NOT
+            | BYTE
+            | SHL
+            | SHR
+            | SAR
+            | CLZ
+            | ADDRESS
+            | ORIGIN
+            | CALLER
+            | CALLVALUE
+            | CALLDATALOAD
+            | CALLDATASIZE
+            | CODESIZE
+            | GASPRICE
+            | RETURNDATASIZE
+            | COINBASE
+            | TIMESTAMP
+            | NUMBER
+            | DIFFICULTY
+            | GASLIMIT
+            | CHAINID
+            | BASEFEE
+            | BLOBBASEFEE
+            | POP
+            | PC
+            | MSIZE
+            | JUMPDEST
+            | PUSH0..=PUSH32
+            | DUP1..=DUP16
+            | SWAP1..=SWAP16
+    )
+}
+
+impl<IW: InterpreterTypes> Interpreter<IW> {
+    /// Executes the interpreter with threaded code until it returns or stops.
+    ///
+    /// `code` must be translated from the executed bytecode with the given instruction table.
+    /// The result is the same as the result of [`Interpreter::run_plain`].
+    #[inline]
+    pub fn run_threaded<H: Host + ?Sized>(
+        &mut self,
+        code: &ThreadedCode<IW, H>,
+        instruction_table: &InstructionTable<IW, H>,
+        host: &mut H,
+    ) -> InterpreterAction {
+        while self.bytecode.is_not_end() {
+            match code.block_at(self.bytecode.pc()) {
+                Some(block)
+                    if block.fits_stack(self.stack.len())
+                        && self.gas.remaining() >= block.static_gas
+                        && self.bytecode.spend_budget(block.len() as u64).is_ok() =>
+                {
+                    let charged = self.gas.record_cost(block.static_gas);
+                    debug_assert!(charged);
+                    self.run_block(&code.ops[block.ops()], host)
+                }
+                // Not a block start, or the block would fail or exhaust the execution budget,
+                // step it instruction by instruction.
+                _ => self.step(instruction_table, host),
+            }
+        }
+        self.take_next_action()
+    }
+
+    /// Runs the instructions of a block whose static gas and execution budget are already charged.
+    #[inline]
+    fn run_block<H: Host + ?Sized>(&mut self, ops: &[ThreadedOp<IW, H>], host: &mut H) {
+        for (i, op) in ops.iter().enumerate() {
+            self.bytecode.relative_jump(1);
+            op.instruction.execute(InstructionContext {
+                interpreter: self,
+                host,
+            });
+            if self.bytecode.is_end() {
+                // Instructions that were not executed are not charged.
+                self.gas.erase_cost(op.rest_gas);
+                self.bytecode.refund_budget((ops.len() - i - 1) as u64);
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod cache {
+    use super::ThreadedCode;
+    use crate::{Host, InstructionTable, InterpreterTypes};
+    use bytecode::{Bytecode, CacheMetrics, LruCache};
+    use core::fmt;
+    use primitives::B256;
+    use std::sync::Arc;
+
+    /// Bounded LRU cache of threaded code keyed by code hash and instruction table identity.
+    ///
+    /// Threaded code contains the instructions of the table it was translated with, so every
+    /// entry is tagged with a table identifier chosen by the caller. Tables that differ in any
+    /// instruction must use different identifiers, which lets EVMs with different tables share
+    /// one cache.
+    pub struct ThreadedCodeCache<W: InterpreterTypes, H: ?Sized> {
+        codes: LruCache<(B256, u64), Arc<ThreadedCode<W, H>>>,
+    }
+
+    impl<W: InterpreterTypes, H: Host + ?Sized> ThreadedCodeCache<W, H> {
+        /// Capacity of [`Default`] caches.
+        pub const DEFAULT_CAPACITY: usize = 1024;
+
+        /// Creates an empty cache that holds at most `capacity` translated bytecodes.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                codes: LruCache::new(capacity),
+            }
+        }
+
+        /// Returns the threaded code of the bytecode, translating it on first use.
+        ///
+        /// `table_id` identifies `instruction_table`. Returns `None` if the bytecode can't be
+        /// translated.
+        pub fn get_or_translate(
+            &self,
+            code_hash: B256,
+            table_id: u64,
+            bytecode: &Bytecode,
+            instruction_table: &InstructionTable<W, H>,
+        ) -> Option<Arc<ThreadedCode<W, H>>> {
+            self.codes
+                .get_or_try_insert_with((code_hash, table_id), || {
+                    ThreadedCode::new(bytecode, instruction_table)
+                        .map(Arc::new)
+                        .ok_or(())
+                })
+                .ok()
+        }
+
+        /// Returns the maximum number of cached bytecodes.
+        pub fn capacity(&self) -> usize {
+            self.codes.capacity()
+        }
+
+        /// Returns the number of cached bytecodes.
+        pub fn len(&self) -> usize {
+            self.codes.len()
+        }
+
+        /// Returns `true` if the cache is empty.
+        pub fn is_empty(&self) -> bool {
+            self.codes.is_empty()
+        }
+
+        /// Removes all cached bytecodes.
+        pub fn clear(&self) {
+            self.codes.clear();
+        }
+
+        /// Returns a snapshot of the cache metrics.
+        pub fn metrics(&self) -> CacheMetrics {
+            self.codes.metrics()
+        }
+    }
+
+    impl<W: InterpreterTypes, H: Host + ?Sized> Default for ThreadedCodeCache<W, H> {
+        fn default() -> Self {
+            Self::new(Self::DEFAULT_CAPACITY)
+        }
+    }
+
+    impl<W: InterpreterTypes, H: ?Sized> fmt::Debug for ThreadedCodeCache<W, H> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ThreadedCodeCache")
+                .field("codes", &self.codes)
+                .finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{host::DummyHost, instruction_table, interpreter::ExtBytecode, InputsImpl};
+    use crate::{interpreter::EthInterpreter, SharedMemory};
+    use bytecode::opcode::*;
+    use primitives::{hardfork::SpecId, Bytes};
+
+    fn interpreter(code: &Bytecode, gas_limit: u64) -> Interpreter {
+        Interpreter::new(
+            SharedMemory::new(),
+            ExtBytecode::new(code.clone()),
+            InputsImpl::default(),
+            false,
+            SpecId::default(),
+            gas_limit,
+        )
+    }
+
+    /// Runs the bytecode with both backends and checks that the outcome is the same.
+    fn assert_same(code: &[u8], gas_limit: u64) -> InterpreterAction {
+        assert_same_with(&instruction_table(), code, gas_limit)
+    }
+
+    /// Same as [`assert_same`] with the given instruction table.
+    fn assert_same_with(
+        table: &InstructionTable<EthInterpreter, DummyHost>,
+        code: &[u8],
+        gas_limit: u64,
+    ) -> InterpreterAction {
+        let code = Bytecode::new_legacy(Bytes::copy_from_slice(code));
+        let threaded = ThreadedCode::new(&code, table).unwrap();
+
+        let mut plain = interpreter(&code, gas_limit);
+        let plain_action = plain.run_plain(table, &mut DummyHost);
+        let mut fast = interpreter(&code, gas_limit);
+        let fast_action = fast.run_threaded(&threaded, table, &mut DummyHost);
+
+        assert_eq!(fast_action, plain_action, "gas limit {gas_limit}");
+        assert_eq!(fast.gas, plain.gas, "gas limit {gas_limit}");
+        assert_eq!(
+            fast.stack.data(),
+            plain.stack.data(),
+            "gas limit {gas_limit}"
+        );
+        assert_eq!(
+            fast.bytecode.pc(),
+            plain.bytecode.pc(),
+            "gas limit {gas_limit}"
+        );
+        fast_action
+    }
+
+    #[test]
+    fn blocks() {
+        let code = Bytecode::new_legacy(Bytes::from_static(&[
+            PUSH1, 1, PUSH1, 6, JUMP, STOP, JUMPDEST, POP, GAS, STOP,
+        ]));
+        let table = instruction_table::<EthInterpreter, DummyHost>();
+        let threaded = ThreadedCode::new(&code, &table).unwrap();
+
+        let starts: Vec<_> = (0..16)
+            .filter(|pc| threaded.block_at(*pc).is_some())
+            .collect();
+        assert_eq!(starts, [0, 5, 6, 9]);
+
+        let first = threaded.block_at(0).unwrap();
+        assert_eq!(first.len(), 3);
+        assert_eq!(first.static_gas(), 3 + 3 + 8);
+        assert_eq!(first.stack_min(), 0);
+        assert_eq!(first.stack_max_growth(), 2);
+
+        // JUMPDEST POP GAS, GAS reads the remaining gas and ends the block.
+        let second = threaded.block_at(6).unwrap();
+        assert_eq!(second.len(), 3);
+        assert_eq!(second.stack_min(), 1);
+        assert_eq!(second.stack_max_growth(), 0);
+
+        assert!(ThreadedCode::new(&Bytecode::new(), &table).is_some());
+    }
+
+    #[test]
+    fn loop_matches_plain() {
+        // Counts down from 16, storing the counter in memory on every iteration.
+        let code = [
+            PUSH1, 16,       // counter
+            JUMPDEST, // 2
+            DUP1, PUSH1, 0, MSTORE, // memory[0] = counter
+            PUSH1, 1, SWAP1, SUB, // counter - 1
+            DUP1, PUSH1, 2, JUMPI, // loop while counter != 0
+            GAS, PUSH1, 0x20, PUSH1, 0, RETURN,
+        ];
+        let action = assert_same(&code, 100_000);
+        let result = action.into_result_return().unwrap();
+        assert_eq!(result.result, crate::InstructionResult::Return);
+        // Last stored counter is 1.
+        assert_eq!(result.output[31], 1);
+        for gas_limit in 0..800 {
+            assert_same(&code, gas_limit);
+        }
+    }
+
+    #[test]
+    fn custom_instruction_ends_block() {
+        // ADD that returns the remaining gas, which must not include prepaid gas.
+        fn add_remaining_gas(context: InstructionContext<'_, DummyHost, EthInterpreter>) {
+            let remaining = context.interpreter.gas.remaining();
+            let stack = &mut context.interpreter.stack;
+            let _ = stack.pop();
+            let _ = stack.pop();
+            assert!(stack.push(primitives::U256::from(remaining)));
+        }
+        let mut table = instruction_table::<EthInterpreter, DummyHost>();
+        table[ADD as usize] = Instruction::new(add_remaining_gas, 3);
+
+        let code = [PUSH1, 1, PUSH1, 2, ADD, PUSH1, 3, POP, STOP];
+        let threaded =
+            ThreadedCode::new(&Bytecode::new_legacy(Bytes::copy_from_slice(&code)), &table)
+                .unwrap();
+        assert_eq!(threaded.block_at(0).unwrap().len(), 3);
+        assert_eq!(threaded.block_at(5).unwrap().len(), 3);
+        for gas_limit in [0, 9, 10, 100_000] {
+            assert_same_with(&table, &code, gas_limit);
+        }
+    }
+
+    #[test]
+    fn early_halt_returns_prepaid_gas() {
+        // Stack underflow in the middle of the block, PUSH0 before Shanghai, invalid opcode.
+        for code in [
+            &[PUSH1, 1, ADD, PUSH1, 2, PUSH1, 3, STOP][..],
+            &[PUSH1, 1, PUSH1, 2, 0x0c, PUSH1, 3, STOP][..],
+            &[PUSH1, 1, SWAP2, PUSH1, 3, REVERT][..],
+            &[CALLER, CALLVALUE, PUSH1, 0, SSTORE, STOP][..],
+        ] {
+            for gas_limit in [0, 5, 10, 50, 100_000] {
+                assert_same(code, gas_limit);
+            }
+        }
+    }
+
+    #[test]
+    fn random_code_matches_plain() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        let ops = [
+            PUSH1,
+            PUSH2,
+            PUSH0,
+            ADD,
+            MUL,
+            SUB,
+            DIV,
+            EXP,
+            LT,
+            ISZERO,
+            DUP1,
+            DUP2,
+            SWAP1,
+            POP,
+            MSTORE,
+            MLOAD,
+            JUMPDEST,
+            JUMPI,
+            JUMP,
+            GAS,
+            PC,
+            MSIZE,
+            KECCAK256,
+            CALLDATALOAD,
+        ];
+        for _ in 0..300 {
+            let len = next() % 64;
+            let mut code = Vec::new();
+            for _ in 0..len {
+                let op = ops[(next() % ops.len() as u64) as usize];
+                code.push(op);
+                // Small immediates so jumps sometimes hit a JUMPDEST.
+                for _ in 0..opcode::OPCODE_INFO[op as usize].unwrap().immediate_size() {
+                    code.push((next() % 32) as u8);
+                }
+            }
+            for gas_limit in [0, 10, 30, 100, 1_000, 100_000] {
+                assert_same(&code, gas_limit);
+            }
+        }
+    }
+}
@@ -1,14 +1,11 @@
 //! Implementation of the [`ExecuteEvm`] trait for the [`OpEvm`].
 use crate::{
-    evm::OpEvm, handler::OpHandler, transaction::OpTxTr, L1BlockInfo, OpHaltReason, OpSpecId,
+    evm::OpEvm, handler::OpHandler, transaction::OpTxTr, L1BlockInfo, OpExecutionResult, OpSpecId,
     OpTransactionError,
 };
 use revm::{
     context::{result::ExecResultAndState, ContextSetters},
-    context_interface::{
-        result::{EVMError, ExecutionResult},
-        Cfg, ContextTr, Database, JournalTr,
-    },
+    context_interface::{result::EVMError, Cfg, ContextTr, Database, JournalTr},
     handler::{
         instructions::EthInstructions, system_call::SystemCallEvm, EthFrame, Handler,
         PrecompileProvider, SystemCallTx,
@@ -56,7 +53,7 @@ where
     type Block = <CTX as ContextTr>::Block;
     type State = EvmState;
     type Error = OpError<CTX>;
-    type ExecutionResult = ExecutionResult<OpHaltReason>;
+    type ExecutionResult = OpExecutionResult;
 
     fn set_block(&mut self, block: Self::Block) {
         self.0.ctx.set_block(block);
@@ -64,8 +61,9 @@ where
 
     fn transact_one(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
         self.0.ctx.set_tx(tx);
-        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::new();
+        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::with_config(self.1.clone());
         h.run(self)
+            .map(|result| OpExecutionResult::new(result, h.deposit_receipt()))
     }
 
     fn finalize(&mut self) -> Self::State {
@@ -75,10 +73,10 @@ where
     fn replay(
         &mut self,
     ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
-        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::new();
+        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::with_config(self.1.clone());
         h.run(self).map(|result| {
             let state = self.finalize();
-            ExecResultAndState::new(result, state)
+            ExecResultAndState::new(OpExecutionResult::new(result, h.deposit_receipt()), state)
         })
     }
 }
@@ -109,8 +107,9 @@ where
 
     fn inspect_one_tx(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
         self.0.ctx.set_tx(tx);
-        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::new();
+        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::with_config(self.1.clone());
         h.inspect_run(self)
+            .map(|result| OpExecutionResult::new(result, h.deposit_receipt()))
     }
 }
 
@@ -140,8 +139,8 @@ where
             system_contract_address,
             data,
         ));
-        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::new();
-        h.run_system_call(self)
+        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::with_config(self.1.clone());
+        h.run_system_call(self).map(Into::into)
     }
 }
 
@@ -163,7 +162,7 @@ where
             system_contract_address,
             data,
         ));
-        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::new();
-        h.inspect_run_system_call(self)
+        let mut h = OpHandler::<_, _, EthFrame<EthInterpreter>>::with_config(self.1.clone());
+        h.inspect_run_system_call(self).map(Into::into)
     }
 }
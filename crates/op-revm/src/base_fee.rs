@@ -0,0 +1,358 @@
+//! Next block base fee of OP Stack chains.
+//!
+//! OP Stack chains use the EIP-1559 update rule with their own parameters, which change in
+//! Canyon. From Holocene, the parameters are set in the `SystemConfig` contract on L1 and encoded
+//! in the extra data of every block, and from Jovian the extra data also encodes a minimum base
+//! fee.
+//!
+//! The base fee of a block is derived from its parent, so the spec, gas values and extra data
+//! passed to the functions of this module are the ones of the parent block.
+use crate::OpSpecId;
+use core::fmt::{self, Display};
+use revm::{context_interface::block::BaseFeeParams, primitives::Bytes};
+use std::vec::Vec;
+
+/// Version byte of the Holocene extra data.
+pub const HOLOCENE_EXTRA_DATA_VERSION: u8 = 0;
+
+/// Version byte of the Jovian extra data.
+pub const JOVIAN_EXTRA_DATA_VERSION: u8 = 1;
+
+/// Length of the Holocene extra data: version, denominator and elasticity.
+pub const HOLOCENE_EXTRA_DATA_LEN: usize = 1 + 4 + 4;
+
+/// Length of the Jovian extra data: Holocene extra data and minimum base fee.
+pub const JOVIAN_EXTRA_DATA_LEN: usize = HOLOCENE_EXTRA_DATA_LEN + 8;
+
+/// Base fee parameters of an OP Stack chain before Holocene.
+///
+/// From Holocene they are the default used when the extra data does not set the parameters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpBaseFeeConfig {
+    /// Parameters before Canyon.
+    pub base_fee_params: BaseFeeParams,
+    /// Parameters from Canyon.
+    pub canyon_base_fee_params: BaseFeeParams,
+}
+
+impl OpBaseFeeConfig {
+    /// Parameters of OP Mainnet.
+    pub const OPTIMISM: Self = Self {
+        base_fee_params: BaseFeeParams::new(50, 6),
+        canyon_base_fee_params: BaseFeeParams::new(250, 6),
+    };
+
+    /// Returns the parameters of the given spec, ignoring the extra data.
+    pub fn base_fee_params(&self, spec_id: OpSpecId) -> BaseFeeParams {
+        if spec_id.is_enabled_in(OpSpecId::CANYON) {
+            self.canyon_base_fee_params
+        } else {
+            self.base_fee_params
+        }
+    }
+
+    /// Calculates the base fee of the block following the parent with the given spec, gas used,
+    /// gas limit, base fee and extra data.
+    ///
+    /// The extra data is only decoded from Holocene. From Jovian, `gas_used` is the larger of the
+    /// gas used and the DA footprint of the parent, see
+    /// [`DaFootprintAccumulator::base_fee_gas_used`](crate::da_footprint::DaFootprintAccumulator::base_fee_gas_used).
+    ///
+    /// Returns [`ExtraDataError::ZeroGasTarget`] if the elasticity is zero or larger than the gas
+    /// limit.
+    pub fn next_block_base_fee(
+        &self,
+        spec_id: OpSpecId,
+        gas_used: u64,
+        gas_limit: u64,
+        base_fee: u64,
+        extra_data: &[u8],
+    ) -> Result<u64, ExtraDataError> {
+        let (params, min_base_fee) = if spec_id.is_enabled_in(OpSpecId::HOLOCENE) {
+            let extra_data = ExtraDataParams::decode(extra_data, spec_id)?;
+            let params = extra_data
+                .base_fee_params()
+                .unwrap_or_else(|| self.base_fee_params(spec_id));
+            (params, extra_data.min_base_fee)
+        } else {
+            (self.base_fee_params(spec_id), 0)
+        };
+
+        // The gas target is a divisor of the base fee change.
+        if gas_limit
+            .checked_div(params.elasticity_multiplier)
+            .unwrap_or_default()
+            == 0
+        {
+            return Err(ExtraDataError::ZeroGasTarget);
+        }
+        let next_base_fee = params.next_block_base_fee(gas_used, gas_limit, base_fee);
+        Ok(next_base_fee.max(min_base_fee))
+    }
+}
+
+impl Default for OpBaseFeeConfig {
+    fn default() -> Self {
+        Self::OPTIMISM
+    }
+}
+
+/// Base fee parameters encoded in the extra data of blocks from Holocene.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtraDataParams {
+    /// Max change denominator, zero to use the default parameters.
+    pub max_change_denominator: u32,
+    /// Elasticity multiplier, zero to use the default parameters.
+    pub elasticity_multiplier: u32,
+    /// Minimum base fee. Zero before Jovian.
+    pub min_base_fee: u64,
+}
+
+impl ExtraDataParams {
+    /// Decodes the extra data of a block with the given spec, which must be Holocene or later.
+    ///
+    /// Before Jovian the minimum base fee is not encoded and is zero.
+    pub fn decode(extra_data: &[u8], spec_id: OpSpecId) -> Result<Self, ExtraDataError> {
+        let (version, len) = Self::format(spec_id);
+        if extra_data.len() != len {
+            return Err(ExtraDataError::InvalidLength {
+                expected: len,
+                actual: extra_data.len(),
+            });
+        }
+        if extra_data[0] != version {
+            return Err(ExtraDataError::UnsupportedVersion(extra_data[0]));
+        }
+
+        let params = Self {
+            max_change_denominator: u32::from_be_bytes(extra_data[1..5].try_into().unwrap()),
+            elasticity_multiplier: u32::from_be_bytes(extra_data[5..9].try_into().unwrap()),
+            min_base_fee: if len == JOVIAN_EXTRA_DATA_LEN {
+                u64::from_be_bytes(extra_data[9..].try_into().unwrap())
+            } else {
+                0
+            },
+        };
+        // Either both parameters are set or both use the default.
+        if (params.max_change_denominator == 0) != (params.elasticity_multiplier == 0) {
+            return Err(ExtraDataError::InvalidParams);
+        }
+        Ok(params)
+    }
+
+    /// Encodes the parameters as the extra data of a block with the given spec, which must be
+    /// Holocene or later.
+    pub fn encode(&self, spec_id: OpSpecId) -> Bytes {
+        let (version, len) = Self::format(spec_id);
+        let mut out = Vec::with_capacity(len);
+        out.push(version);
+        out.extend_from_slice(&self.max_change_denominator.to_be_bytes());
+        out.extend_from_slice(&self.elasticity_multiplier.to_be_bytes());
+        if len == JOVIAN_EXTRA_DATA_LEN {
+            out.extend_from_slice(&self.min_base_fee.to_be_bytes());
+        }
+        out.into()
+    }
+
+    /// Returns the parameters, or `None` if the default ones are used.
+    pub fn base_fee_params(&self) -> Option<BaseFeeParams> {
+        (self.max_change_denominator != 0).then(|| {
+            BaseFeeParams::new(
+                self.max_change_denominator as u64,
+                self.elasticity_multiplier as u64,
+            )
+        })
+    }
+
+    /// Returns the version byte and the length of the extra data of the spec.
+    fn format(spec_id: OpSpecId) -> (u8, usize) {
+        if spec_id.is_enabled_in(OpSpecId::JOVIAN) {
+            (JOVIAN_EXTRA_DATA_VERSION, JOVIAN_EXTRA_DATA_LEN)
+        } else {
+            (HOLOCENE_EXTRA_DATA_VERSION, HOLOCENE_EXTRA_DATA_LEN)
+        }
+    }
+}
+
+/// Error returned when decoding the base fee parameters from the extra data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtraDataError {
+    /// The extra data length does not match the spec.
+    InvalidLength {
+        /// Expected length.
+        expected: usize,
+        /// Actual length.
+        actual: usize,
+    },
+    /// The version byte does not match the spec.
+    UnsupportedVersion(u8),
+    /// Only one of the denominator and the elasticity is zero.
+    InvalidParams,
+    /// The elasticity is zero or larger than the gas limit, so the gas target is zero.
+    ZeroGasTarget,
+}
+
+impl Display for ExtraDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "invalid extra data length: expected {expected} bytes, got {actual}"
+            ),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported extra data version {version}")
+            }
+            Self::InvalidParams => f.write_str("only one of denominator and elasticity is zero"),
+            Self::ZeroGasTarget => f.write_str("elasticity is zero or larger than the gas limit"),
+        }
+    }
+}
+
+impl core::error::Error for ExtraDataError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::hex;
+
+    const GAS_LIMIT: u64 = 30_000_000;
+    const BASE_FEE: u64 = 1_000_000_000;
+
+    fn next_base_fee(spec_id: OpSpecId, gas_used: u64, extra_data: &[u8]) -> u64 {
+        OpBaseFeeConfig::OPTIMISM
+            .next_block_base_fee(spec_id, gas_used, GAS_LIMIT, BASE_FEE, extra_data)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_canyon_denominator() {
+        // The gas target is 5M with an elasticity of 6.
+        assert_eq!(
+            next_base_fee(OpSpecId::BEDROCK, GAS_LIMIT, &[]),
+            1_100_000_000
+        );
+        assert_eq!(
+            next_base_fee(OpSpecId::CANYON, GAS_LIMIT, &[]),
+            1_020_000_000
+        );
+        assert_eq!(next_base_fee(OpSpecId::CANYON, 5_000_000, &[]), BASE_FEE);
+        assert_eq!(next_base_fee(OpSpecId::GRANITE, 0, &[]), 996_000_000);
+        // Extra data is ignored before Holocene.
+        assert_eq!(next_base_fee(OpSpecId::GRANITE, 0, &[0xff]), 996_000_000);
+    }
+
+    #[test]
+    fn test_holocene_extra_data() {
+        // Default parameters.
+        let extra_data = hex!("000000000000000000");
+        assert_eq!(
+            next_base_fee(OpSpecId::HOLOCENE, GAS_LIMIT, &extra_data),
+            1_020_000_000
+        );
+
+        // Denominator 8 and elasticity 2, the gas target is 15M.
+        let extra_data = hex!("000000000800000002");
+        assert_eq!(
+            ExtraDataParams::decode(&extra_data, OpSpecId::HOLOCENE),
+            Ok(ExtraDataParams {
+                max_change_denominator: 8,
+                elasticity_multiplier: 2,
+                min_base_fee: 0,
+            })
+        );
+        assert_eq!(
+            next_base_fee(OpSpecId::HOLOCENE, GAS_LIMIT, &extra_data),
+            1_125_000_000
+        );
+        assert_eq!(
+            next_base_fee(OpSpecId::ISTHMUS, 15_000_000, &extra_data),
+            BASE_FEE
+        );
+
+        let config = OpBaseFeeConfig::OPTIMISM;
+        for (extra_data, error) in [
+            (
+                &hex!("0000000008")[..],
+                ExtraDataError::InvalidLength {
+                    expected: 9,
+                    actual: 5,
+                },
+            ),
+            (
+                &hex!("010000000800000002")[..],
+                ExtraDataError::UnsupportedVersion(1),
+            ),
+            (
+                &hex!("000000000800000000")[..],
+                ExtraDataError::InvalidParams,
+            ),
+        ] {
+            assert_eq!(
+                config.next_block_base_fee(OpSpecId::HOLOCENE, 0, GAS_LIMIT, BASE_FEE, extra_data),
+                Err(error)
+            );
+        }
+    }
+
+    #[test]
+    fn test_jovian_min_base_fee() {
+        let params = ExtraDataParams {
+            max_change_denominator: 250,
+            elasticity_multiplier: 6,
+            min_base_fee: 1_000_000_000,
+        };
+        let extra_data = params.encode(OpSpecId::JOVIAN);
+        assert_eq!(extra_data[..], hex!("01000000fa00000006000000003b9aca00"));
+        assert_eq!(
+            ExtraDataParams::decode(&extra_data, OpSpecId::JOVIAN),
+            Ok(params)
+        );
+
+        // The base fee does not go below the minimum.
+        assert_eq!(next_base_fee(OpSpecId::JOVIAN, 0, &extra_data), BASE_FEE);
+        assert_eq!(
+            next_base_fee(OpSpecId::JOVIAN, GAS_LIMIT, &extra_data),
+            1_020_000_000
+        );
+
+        // The Holocene format does not encode the minimum base fee.
+        let holocene = params.encode(OpSpecId::HOLOCENE);
+        assert_eq!(holocene[..], hex!("00000000fa00000006"));
+        assert_eq!(
+            ExtraDataParams::decode(&holocene, OpSpecId::JOVIAN),
+            Err(ExtraDataError::InvalidLength {
+                expected: 17,
+                actual: 9,
+            })
+        );
+    }
+
+    #[test]
+    fn test_zero_gas_target() {
+        // The elasticity is larger than the gas limit.
+        let extra_data = hex!("000000000800000002");
+        assert_eq!(
+            OpBaseFeeConfig::OPTIMISM.next_block_base_fee(
+                OpSpecId::HOLOCENE,
+                0,
+                1,
+                BASE_FEE,
+                &extra_data
+            ),
+            Err(ExtraDataError::ZeroGasTarget)
+        );
+
+        // Zero elasticity in the configured parameters.
+        let config = OpBaseFeeConfig {
+            base_fee_params: BaseFeeParams::new(50, 0),
+            ..OpBaseFeeConfig::OPTIMISM
+        };
+        assert_eq!(
+            config.next_block_base_fee(OpSpecId::BEDROCK, 0, GAS_LIMIT, BASE_FEE, &[]),
+            Err(ExtraDataError::ZeroGasTarget)
+        );
+    }
+}
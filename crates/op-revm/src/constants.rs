@@ -22,6 +22,9 @@ pub const OPERATOR_FEE_CONSTANT_OFFSET: usize = 24;
 /// the storage slot of the 16-byte daFootprintGasScalar attribute.
 pub const DA_FOOTPRINT_GAS_SCALAR_OFFSET: usize = 18;
 
+/// The DA footprint gas scalar used from Jovian when the one set in the L1Block predeploy is zero.
+pub const DEFAULT_DA_FOOTPRINT_GAS_SCALAR: u16 = 400;
+
 /// The fixed point decimal scaling factor associated with the operator fee scalar.
 ///
 /// Allows users to use 6 decimal points of precision when specifying the operator_fee_scalar.
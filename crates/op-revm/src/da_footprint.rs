@@ -0,0 +1,207 @@
+//! Jovian block-level DA footprint accounting.
+//!
+//! From Jovian, every non-deposit transaction has a DA footprint: its estimated compressed size
+//! multiplied by the DA footprint gas scalar of the L1Block predeploy. The DA footprint of all
+//! transactions in a block can not exceed the block gas limit, is stored in the `blob_gas_used`
+//! field of the block header and is used instead of the gas used for the base fee update when
+//! it is larger.
+use crate::{
+    constants::DEFAULT_DA_FOOTPRINT_GAS_SCALAR, estimate_tx_compressed_size, transaction::OpTxTr,
+    L1BlockInfo, OpTransactionError,
+};
+
+/// Returns the DA footprint of a non-deposit transaction with the given enveloped bytes.
+///
+/// A zero scalar is replaced by [`DEFAULT_DA_FOOTPRINT_GAS_SCALAR`].
+pub fn tx_da_footprint(enveloped_tx: &[u8], da_footprint_gas_scalar: u16) -> u64 {
+    let scalar = effective_scalar(da_footprint_gas_scalar);
+    // The estimated size is scaled by 1e6.
+    (estimate_tx_compressed_size(enveloped_tx) / 1_000_000).saturating_mul(scalar as u64)
+}
+
+/// Replaces a zero scalar by the default one.
+const fn effective_scalar(da_footprint_gas_scalar: u16) -> u16 {
+    match da_footprint_gas_scalar {
+        0 => DEFAULT_DA_FOOTPRINT_GAS_SCALAR,
+        scalar => scalar,
+    }
+}
+
+/// Accumulates the DA footprint of the transactions of a block.
+///
+/// Transactions are added in block order with [`add_transaction`](Self::add_transaction), which
+/// rejects the ones that would exceed the limit without adding them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaFootprintAccumulator {
+    /// DA footprint gas scalar of the block, never zero.
+    da_footprint_gas_scalar: u16,
+    /// Maximum DA footprint of the block, equal to the block gas limit.
+    limit: u64,
+    /// DA footprint of the transactions added so far.
+    used: u64,
+}
+
+impl DaFootprintAccumulator {
+    /// Creates an empty accumulator for a block with the given gas limit.
+    ///
+    /// A zero scalar is replaced by [`DEFAULT_DA_FOOTPRINT_GAS_SCALAR`].
+    pub fn new(block_gas_limit: u64, da_footprint_gas_scalar: u16) -> Self {
+        Self {
+            da_footprint_gas_scalar: effective_scalar(da_footprint_gas_scalar),
+            limit: block_gas_limit,
+            used: 0,
+        }
+    }
+
+    /// Creates an empty accumulator with the scalar of the L1 block info.
+    ///
+    /// Returns `None` before Jovian, when the L1 block info has no DA footprint gas scalar.
+    pub fn from_l1_block_info(l1_block_info: &L1BlockInfo, block_gas_limit: u64) -> Option<Self> {
+        l1_block_info
+            .da_footprint_gas_scalar
+            .map(|scalar| Self::new(block_gas_limit, scalar))
+    }
+
+    /// Returns the DA footprint gas scalar.
+    pub fn da_footprint_gas_scalar(&self) -> u16 {
+        self.da_footprint_gas_scalar
+    }
+
+    /// Returns the maximum DA footprint of the block.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns the DA footprint of the transactions added so far.
+    ///
+    /// This is the `blob_gas_used` field of the block header.
+    pub fn blob_gas_used(&self) -> u64 {
+        self.used
+    }
+
+    /// Returns the DA footprint still available in the block.
+    pub fn available(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    /// Returns the gas used by the base fee update of the next block, which is the larger of the
+    /// given block gas used and the DA footprint.
+    pub fn base_fee_gas_used(&self, gas_used: u64) -> u64 {
+        gas_used.max(self.used)
+    }
+
+    /// Returns the DA footprint of the transaction. Deposits have none.
+    pub fn tx_da_footprint(&self, tx: impl OpTxTr) -> Result<u64, OpTransactionError> {
+        if tx.is_deposit() {
+            return Ok(0);
+        }
+        let enveloped_tx = tx
+            .enveloped_tx()
+            .ok_or(OpTransactionError::MissingEnvelopedTx)?;
+        Ok(tx_da_footprint(enveloped_tx, self.da_footprint_gas_scalar))
+    }
+
+    /// Returns the DA footprint of the transaction if it fits in the block.
+    pub fn check_transaction(&self, tx: impl OpTxTr) -> Result<u64, OpTransactionError> {
+        let tx_da_footprint = self.tx_da_footprint(tx)?;
+        let available = self.available();
+        if tx_da_footprint > available {
+            return Err(OpTransactionError::BlockDaFootprintLimitExceeded {
+                tx_da_footprint,
+                available,
+            });
+        }
+        Ok(tx_da_footprint)
+    }
+
+    /// Adds the transaction to the block and returns its DA footprint.
+    ///
+    /// The accumulator is unchanged if the transaction does not fit in the block.
+    pub fn add_transaction(&mut self, tx: impl OpTxTr) -> Result<u64, OpTransactionError> {
+        let tx_da_footprint = self.check_transaction(tx)?;
+        self.used += tx_da_footprint;
+        Ok(tx_da_footprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::OpTransaction;
+    use revm::{
+        context::TxEnv,
+        primitives::{Bytes, B256},
+    };
+    use std::vec;
+
+    fn tx(enveloped_tx: Bytes) -> OpTransaction<TxEnv> {
+        OpTransaction::builder()
+            .enveloped_tx(Some(enveloped_tx))
+            .build_fill()
+    }
+
+    #[test]
+    fn test_tx_da_footprint() {
+        // Small transactions are counted with the minimum size of 100 bytes.
+        assert_eq!(tx_da_footprint(&[0x02; 10], 7), 700);
+        assert_eq!(
+            tx_da_footprint(&[], 0),
+            100 * DEFAULT_DA_FOOTPRINT_GAS_SCALAR as u64
+        );
+
+        let input: Vec<u8> = (0..2_000u32).map(|i| (i * 31 % 251) as u8).collect();
+        let size = estimate_tx_compressed_size(&input) / 1_000_000;
+        assert!(size > 100);
+        assert_eq!(tx_da_footprint(&input, 10), size * 10);
+    }
+
+    #[test]
+    fn test_accumulate_until_limit() {
+        let mut block = DaFootprintAccumulator::new(100_000, 0);
+        assert_eq!(
+            block.da_footprint_gas_scalar(),
+            DEFAULT_DA_FOOTPRINT_GAS_SCALAR
+        );
+
+        let small = tx(Bytes::from(vec![0x02; 10]));
+        let deposit = OpTransaction::builder()
+            .source_hash(B256::from([1u8; 32]))
+            .build_fill();
+        assert_eq!(block.add_transaction(&small), Ok(40_000));
+        assert_eq!(block.add_transaction(&deposit), Ok(0));
+        assert_eq!(block.add_transaction(&small), Ok(40_000));
+        assert_eq!(
+            block.add_transaction(&small),
+            Err(OpTransactionError::BlockDaFootprintLimitExceeded {
+                tx_da_footprint: 40_000,
+                available: 20_000,
+            })
+        );
+        assert_eq!(block.blob_gas_used(), 80_000);
+        assert_eq!(block.available(), 20_000);
+        assert_eq!(block.base_fee_gas_used(50_000), 80_000);
+        assert_eq!(block.base_fee_gas_used(90_000), 90_000);
+
+        let mut missing = small;
+        missing.enveloped_tx = None;
+        assert_eq!(
+            block.check_transaction(&missing),
+            Err(OpTransactionError::MissingEnvelopedTx)
+        );
+    }
+
+    #[test]
+    fn test_from_l1_block_info() {
+        let mut l1_block_info = L1BlockInfo::default();
+        assert_eq!(
+            DaFootprintAccumulator::from_l1_block_info(&l1_block_info, 30_000_000),
+            None
+        );
+
+        l1_block_info.da_footprint_gas_scalar = Some(7);
+        let block = DaFootprintAccumulator::from_l1_block_info(&l1_block_info, 30_000_000).unwrap();
+        assert_eq!(block.da_footprint_gas_scalar(), 7);
+        assert_eq!(block.limit(), 30_000_000);
+    }
+}
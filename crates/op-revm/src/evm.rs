@@ -1,5 +1,8 @@
 //! Contains the `[OpEvm]` type and its implementation of the execution EVM traits.
-use crate::precompiles::OpPrecompiles;
+use crate::{
+    fee_vaults::FeeVaultConfig, handler::OpHandlerConfig, interop::MessageOracle,
+    precompiles::OpPrecompiles,
+};
 use revm::{
     context::{ContextError, ContextSetters, Evm, FrameStack},
     context_interface::ContextTr,
@@ -12,6 +15,7 @@ use revm::{
     interpreter::{interpreter::EthInterpreter, InterpreterResult},
     Database, Inspector,
 };
+use std::sync::Arc;
 
 /// Optimism EVM extends the [`Evm`] type with Optimism specific types and logic.
 #[derive(Debug, Clone)]
@@ -24,30 +28,47 @@ pub struct OpEvm<
 >(
     /// Inner EVM type.
     pub Evm<CTX, INSP, I, P, F>,
+    /// Configuration passed to the [`OpHandler`](crate::handler::OpHandler) of every transaction.
+    pub OpHandlerConfig,
 );
 
 impl<CTX: ContextTr, INSP> OpEvm<CTX, INSP, EthInstructions<EthInterpreter, CTX>, OpPrecompiles> {
     /// Create a new Optimism EVM.
     pub fn new(ctx: CTX, inspector: INSP) -> Self {
-        Self(Evm {
-            ctx,
-            inspector,
-            instruction: EthInstructions::new_mainnet(),
-            precompiles: OpPrecompiles::default(),
-            frame_stack: FrameStack::new_prealloc(8),
-        })
+        Self(
+            Evm {
+                ctx,
+                inspector,
+                instruction: EthInstructions::new_mainnet(),
+                precompiles: OpPrecompiles::default(),
+                frame_stack: FrameStack::new_prealloc(8),
+            },
+            OpHandlerConfig::default(),
+        )
     }
 }
 
 impl<CTX, INSP, I, P> OpEvm<CTX, INSP, I, P> {
     /// Consumed self and returns a new Evm type with given Inspector.
     pub fn with_inspector<OINSP>(self, inspector: OINSP) -> OpEvm<CTX, OINSP, I, P> {
-        OpEvm(self.0.with_inspector(inspector))
+        OpEvm(self.0.with_inspector(inspector), self.1)
     }
 
     /// Consumes self and returns a new Evm type with given Precompiles.
     pub fn with_precompiles<OP>(self, precompiles: OP) -> OpEvm<CTX, INSP, I, OP> {
-        OpEvm(self.0.with_precompiles(precompiles))
+        OpEvm(self.0.with_precompiles(precompiles), self.1)
+    }
+
+    /// Sets the oracle used to validate executing messages from the Interop hardfork.
+    pub fn with_message_oracle(mut self, oracle: Arc<dyn MessageOracle>) -> Self {
+        self.1 = self.1.with_message_oracle(oracle);
+        self
+    }
+
+    /// Sets the recipients of the base, L1 and operator fees.
+    pub fn with_fee_vaults(mut self, fee_vaults: FeeVaultConfig) -> Self {
+        self.1 = self.1.with_fee_vaults(fee_vaults);
+        self
     }
 
     /// Consumes self and returns the inner Inspector.
@@ -0,0 +1,485 @@
+//! L1 fee and operator fee estimation for unsigned transactions.
+//!
+//! The L1 cost of a transaction depends on its signed EIP-2718 envelope, which is not known
+//! before the transaction is signed. The estimation encodes the transaction with a fixed
+//! [dummy signature](DUMMY_SIGNATURE_R) of the same size as a real one and returns the values of
+//! the `GasPriceOracle` predeploy getters for that envelope.
+use crate::{
+    constants::NON_ZERO_BYTE_COST, estimate_tx_compressed_size,
+    transaction::deposit::DEPOSIT_TRANSACTION_TYPE, L1BlockInfo, OpSpecId,
+};
+use alloy_rlp::{Encodable, Header};
+use revm::{
+    context_interface::{
+        transaction::{AccessListItemTr, AuthorizationTr, TransactionType},
+        Transaction,
+    },
+    primitives::{uint, Bytes, B256, U256},
+};
+use std::vec::Vec;
+
+/// `r` of the dummy signature, the x coordinate of the secp256k1 generator point.
+///
+/// A dummy signature needs to be as incompressible as a real one.
+pub const DUMMY_SIGNATURE_R: U256 =
+    uint!(0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798_U256);
+
+/// `s` of the dummy signature, the y coordinate of the secp256k1 generator point.
+pub const DUMMY_SIGNATURE_S: U256 =
+    uint!(0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8_U256);
+
+/// Y parity of the dummy signature.
+pub const DUMMY_SIGNATURE_Y_PARITY: bool = true;
+
+/// Breakdown of the fees charged by OP Stack chains on top of the execution gas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpFeeEstimate {
+    /// Length of the enveloped transaction.
+    pub tx_size: u64,
+    /// Estimated compressed size of the enveloped transaction, scaled by 1e6.
+    ///
+    /// Zero before Fjord.
+    pub estimated_compressed_size: u64,
+    /// L1 gas used, as returned by `GasPriceOracle.getL1GasUsed`.
+    pub l1_gas_used: U256,
+    /// L1 data fee, as returned by `GasPriceOracle.getL1Fee`.
+    pub l1_fee: U256,
+    /// Operator fee for the whole gas limit, as returned by `GasPriceOracle.getOperatorFee`.
+    ///
+    /// Zero before Isthmus.
+    pub operator_fee: U256,
+}
+
+impl OpFeeEstimate {
+    /// Returns the sum of the L1 fee and the operator fee.
+    pub fn total_fee(&self) -> U256 {
+        self.l1_fee.saturating_add(self.operator_fee)
+    }
+}
+
+impl L1BlockInfo {
+    /// Estimates the L1 fee and operator fee of the transaction, which is encoded with the
+    /// dummy signature.
+    ///
+    /// Deposits have no fees. Returns `None` for custom transaction types.
+    pub fn estimate_tx_fees(
+        &self,
+        tx: impl Transaction,
+        spec_id: OpSpecId,
+    ) -> Option<OpFeeEstimate> {
+        if tx.tx_type() == DEPOSIT_TRANSACTION_TYPE {
+            return Some(OpFeeEstimate::default());
+        }
+        let enveloped_tx = encode_with_dummy_signature(&tx)?;
+        Some(self.estimate_enveloped_tx_fees(&enveloped_tx, tx.gas_limit(), spec_id))
+    }
+
+    /// Returns the fee breakdown of an enveloped transaction with the given gas limit.
+    pub fn estimate_enveloped_tx_fees(
+        &self,
+        enveloped_tx: &[u8],
+        gas_limit: u64,
+        spec_id: OpSpecId,
+    ) -> OpFeeEstimate {
+        // Do not use or overwrite the cached cost of the current transaction.
+        let mut l1_block_info = self.clone();
+        l1_block_info.clear_tx_l1_cost();
+
+        let mut estimate = OpFeeEstimate {
+            tx_size: enveloped_tx.len() as u64,
+            l1_fee: l1_block_info.calculate_tx_l1_cost(enveloped_tx, spec_id),
+            ..Default::default()
+        };
+
+        estimate.l1_gas_used = if spec_id.is_enabled_in(OpSpecId::FJORD) {
+            estimate.estimated_compressed_size = estimate_tx_compressed_size(enveloped_tx);
+            U256::from(estimate.estimated_compressed_size)
+                .saturating_mul(U256::from(NON_ZERO_BYTE_COST))
+                / U256::from(1_000_000)
+        } else {
+            let data_gas = self.data_gas(enveloped_tx, spec_id);
+            // The overhead is part of the L1 gas used as long as the Bedrock cost function is used.
+            if !spec_id.is_enabled_in(OpSpecId::ECOTONE) || self.empty_ecotone_scalars {
+                data_gas.saturating_add(self.l1_fee_overhead.unwrap_or_default())
+            } else {
+                data_gas
+            }
+        };
+
+        if spec_id.is_enabled_in(OpSpecId::ISTHMUS) {
+            estimate.operator_fee =
+                self.operator_fee_charge(enveloped_tx, U256::from(gas_limit), spec_id);
+        }
+
+        estimate
+    }
+}
+
+/// Encodes the transaction as an EIP-2718 envelope signed with the dummy signature.
+///
+/// Signatures of EIP-7702 authorizations are replaced by the dummy signature as well. Returns
+/// `None` for deposits and custom transaction types.
+pub fn encode_with_dummy_signature(tx: &impl Transaction) -> Option<Bytes> {
+    let tx_type = TransactionType::from(tx.tx_type());
+    if tx_type == TransactionType::Custom {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+    if tx_type != TransactionType::Legacy {
+        tx.chain_id().unwrap_or_default().encode(&mut fields);
+    }
+    tx.nonce().encode(&mut fields);
+    if matches!(tx_type, TransactionType::Legacy | TransactionType::Eip2930) {
+        tx.gas_price().encode(&mut fields);
+    } else {
+        tx.max_priority_fee_per_gas()
+            .unwrap_or_default()
+            .encode(&mut fields);
+        tx.max_fee_per_gas().encode(&mut fields);
+    }
+    tx.gas_limit().encode(&mut fields);
+    tx.kind().encode(&mut fields);
+    tx.value().encode(&mut fields);
+    tx.input().encode(&mut fields);
+
+    if tx_type != TransactionType::Legacy {
+        let mut access_list = Vec::new();
+        for item in tx.access_list().into_iter().flatten() {
+            let mut entry = Vec::new();
+            item.address().encode(&mut entry);
+            let keys: Vec<_> = item.storage_slots().collect();
+            alloy_rlp::encode_list::<_, B256>(&keys, &mut entry);
+            encode_list(&entry, &mut access_list);
+        }
+        encode_list(&access_list, &mut fields);
+    }
+
+    match tx_type {
+        TransactionType::Eip4844 => {
+            tx.max_fee_per_blob_gas().encode(&mut fields);
+            alloy_rlp::encode_list::<_, B256>(tx.blob_versioned_hashes(), &mut fields);
+        }
+        TransactionType::Eip7702 => {
+            let mut authorization_list = Vec::new();
+            for authorization in tx.authorization_list() {
+                let mut entry = Vec::new();
+                authorization.chain_id().encode(&mut entry);
+                authorization.address().encode(&mut entry);
+                authorization.nonce().encode(&mut entry);
+                encode_dummy_signature(DUMMY_SIGNATURE_Y_PARITY as u64, &mut entry);
+                encode_list(&entry, &mut authorization_list);
+            }
+            encode_list(&authorization_list, &mut fields);
+        }
+        _ => {}
+    }
+
+    let mut out = Vec::new();
+    if tx_type == TransactionType::Legacy {
+        // EIP-155 `v` if the chain ID is set.
+        let v = match tx.chain_id() {
+            Some(chain_id) => chain_id as u128 * 2 + 35,
+            None => 27,
+        } + DUMMY_SIGNATURE_Y_PARITY as u128;
+        encode_dummy_signature(v, &mut fields);
+    } else {
+        encode_dummy_signature(DUMMY_SIGNATURE_Y_PARITY as u64, &mut fields);
+        out.push(tx_type as u8);
+    }
+    encode_list(&fields, &mut out);
+    Some(out.into())
+}
+
+/// Encodes the dummy signature with the given `v` or y parity.
+fn encode_dummy_signature(v: impl Encodable, out: &mut Vec<u8>) {
+    v.encode(out);
+    DUMMY_SIGNATURE_R.encode(out);
+    DUMMY_SIGNATURE_S.encode(out);
+}
+
+/// Encodes the already encoded payload as a list.
+fn encode_list(payload: &[u8], out: &mut Vec<u8>) {
+    Header {
+        list: true,
+        payload_length: payload.len(),
+    }
+    .encode(out);
+    out.extend_from_slice(payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::OpTransaction;
+    use alloy_consensus::{
+        SignableTransaction, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxEnvelope, TxLegacy,
+    };
+    use alloy_eips::{
+        eip2718::Encodable2718,
+        eip2930::{AccessList, AccessListItem},
+        eip7702::{Authorization, SignedAuthorization},
+    };
+    use alloy_primitives::Signature;
+    use revm::{
+        context::TxEnv,
+        context_interface::either::Either,
+        primitives::{address, b256, bytes, TxKind},
+    };
+    use std::vec;
+
+    const B256_ONE: B256 =
+        b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+
+    fn signature() -> Signature {
+        Signature::new(
+            DUMMY_SIGNATURE_R,
+            DUMMY_SIGNATURE_S,
+            DUMMY_SIGNATURE_Y_PARITY,
+        )
+    }
+
+    fn access_list() -> AccessList {
+        AccessList(vec![AccessListItem {
+            address: address!("0x4200000000000000000000000000000000000015"),
+            storage_keys: vec![
+                B256_ONE,
+                b256!("0x00000000000000000000000000000000000000000000000000000000000000ff"),
+            ],
+        }])
+    }
+
+    fn tx_env(tx_type: TransactionType) -> TxEnv {
+        let mut tx = TxEnv::builder()
+            .tx_type(Some(tx_type as u8))
+            .chain_id(Some(10))
+            .nonce(7)
+            .gas_price(1_000_000_000)
+            .gas_priority_fee(Some(1_000))
+            .gas_limit(100_000)
+            .kind(TxKind::Call(address!(
+                "0xd8da6bf26964af9d7eed9e10c0aa5a75a7e5a3f8"
+            )))
+            .value(U256::from(123_456_789u64))
+            .data(bytes!(
+                "a9059cbb000000000000000000000000d8da6bf26964af9d7eed9e10c0aa5a75a7e5a3f8"
+            ))
+            .build_fill();
+        if tx_type != TransactionType::Legacy {
+            tx.access_list = access_list();
+        }
+        tx
+    }
+
+    #[test]
+    fn test_encoding_matches_signed_envelope() {
+        let input = tx_env(TransactionType::Legacy).data;
+        let to = address!("0xd8da6bf26964af9d7eed9e10c0aa5a75a7e5a3f8");
+        let value = U256::from(123_456_789u64);
+
+        let legacy = TxLegacy {
+            chain_id: Some(10),
+            nonce: 7,
+            gas_price: 1_000_000_000,
+            gas_limit: 100_000,
+            to: TxKind::Call(to),
+            value,
+            input: input.clone(),
+        };
+        let eip2930 = TxEip2930 {
+            chain_id: 10,
+            nonce: 7,
+            gas_price: 1_000_000_000,
+            gas_limit: 100_000,
+            to: TxKind::Call(to),
+            value,
+            access_list: access_list(),
+            input: input.clone(),
+        };
+        let eip1559 = TxEip1559 {
+            chain_id: 10,
+            nonce: 7,
+            gas_limit: 100_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000,
+            to: TxKind::Call(to),
+            value,
+            access_list: access_list(),
+            input: input.clone(),
+        };
+        let eip4844 = TxEip4844 {
+            chain_id: 10,
+            nonce: 7,
+            gas_limit: 100_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000,
+            to,
+            value,
+            access_list: access_list(),
+            blob_versioned_hashes: vec![B256_ONE],
+            max_fee_per_blob_gas: 5,
+            input: input.clone(),
+        };
+        let authorization = Authorization {
+            chain_id: U256::from(10),
+            address: to,
+            nonce: 3,
+        };
+        let eip7702 = TxEip7702 {
+            chain_id: 10,
+            nonce: 7,
+            gas_limit: 100_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000,
+            to,
+            value,
+            access_list: access_list(),
+            authorization_list: vec![SignedAuthorization::new_unchecked(
+                authorization.clone(),
+                DUMMY_SIGNATURE_Y_PARITY as u8,
+                DUMMY_SIGNATURE_R,
+                DUMMY_SIGNATURE_S,
+            )],
+            input,
+        };
+
+        let mut blob_tx = tx_env(TransactionType::Eip4844);
+        blob_tx.blob_hashes = vec![B256_ONE];
+        blob_tx.max_fee_per_blob_gas = 5;
+        let mut set_code_tx = tx_env(TransactionType::Eip7702);
+        set_code_tx.authorization_list = vec![Either::Left(SignedAuthorization::new_unchecked(
+            authorization,
+            0,
+            U256::ZERO,
+            U256::ZERO,
+        ))];
+
+        let cases: [(TxEnv, TxEnvelope); 5] = [
+            (
+                tx_env(TransactionType::Legacy),
+                legacy.into_signed(signature()).into(),
+            ),
+            (
+                tx_env(TransactionType::Eip2930),
+                eip2930.into_signed(signature()).into(),
+            ),
+            (
+                tx_env(TransactionType::Eip1559),
+                eip1559.into_signed(signature()).into(),
+            ),
+            (blob_tx, eip4844.into_signed(signature()).into()),
+            (set_code_tx, eip7702.into_signed(signature()).into()),
+        ];
+        for (tx, envelope) in cases {
+            assert_eq!(
+                encode_with_dummy_signature(&tx).unwrap(),
+                Bytes::from(envelope.encoded_2718()),
+                "type {}",
+                tx.tx_type
+            );
+        }
+
+        let mut custom = tx_env(TransactionType::Eip1559);
+        custom.tx_type = 0x42;
+        assert_eq!(encode_with_dummy_signature(&custom), None);
+    }
+
+    fn l1_block_info() -> L1BlockInfo {
+        L1BlockInfo {
+            l1_base_fee: U256::from(1_000_000_000),
+            l1_fee_overhead: Some(U256::from(188)),
+            l1_base_fee_scalar: U256::from(684_000),
+            ..Default::default()
+        }
+    }
+
+    fn ecotone_l1_block_info() -> L1BlockInfo {
+        L1BlockInfo {
+            l1_base_fee: U256::from(1_000_000_000),
+            l1_base_fee_scalar: U256::from(1_368),
+            l1_blob_base_fee: Some(U256::from(1_000_000)),
+            l1_blob_base_fee_scalar: Some(U256::from(810_949)),
+            operator_fee_scalar: Some(U256::from(2_000)),
+            operator_fee_constant: Some(U256::from(30)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_gas_price_oracle_parity() {
+        let tx = tx_env(TransactionType::Eip1559);
+        let enveloped_tx = encode_with_dummy_signature(&tx).unwrap();
+        let zero_bytes = enveloped_tx.iter().filter(|b| **b == 0).count() as u64;
+        let calldata_gas =
+            U256::from(zero_bytes * 4 + (enveloped_tx.len() as u64 - zero_bytes) * 16);
+
+        // Bedrock: `(calldataGas + overhead) * l1BaseFee * scalar / 1e6`.
+        let info = l1_block_info();
+        let estimate = info.estimate_tx_fees(&tx, OpSpecId::REGOLITH).unwrap();
+        let l1_gas_used = calldata_gas + U256::from(188);
+        assert_eq!(estimate.tx_size, enveloped_tx.len() as u64);
+        assert_eq!(estimate.l1_gas_used, l1_gas_used);
+        assert_eq!(
+            estimate.l1_fee,
+            l1_gas_used * U256::from(1_000_000_000) * U256::from(684_000) / U256::from(1_000_000)
+        );
+        assert_eq!(estimate.operator_fee, U256::ZERO);
+
+        // Ecotone: `calldataGas * (16 * l1BaseFee * baseFeeScalar + blobBaseFee * blobBaseFeeScalar) / 16e6`.
+        let info = ecotone_l1_block_info();
+        let fee_scaled = U256::from(16 * 1_000_000_000u64 * 1_368 + 1_000_000 * 810_949);
+        let estimate = info.estimate_tx_fees(&tx, OpSpecId::ECOTONE).unwrap();
+        assert_eq!(estimate.l1_gas_used, calldata_gas);
+        assert_eq!(
+            estimate.l1_fee,
+            calldata_gas * fee_scaled / U256::from(16_000_000)
+        );
+
+        // Fjord: `estimatedSize * feeScaled / 1e12` and `l1GasUsed = estimatedSize * 16 / 1e6`.
+        let estimate = info.estimate_tx_fees(&tx, OpSpecId::FJORD).unwrap();
+        let size = estimate_tx_compressed_size(&enveloped_tx);
+        assert_eq!(estimate.estimated_compressed_size, size);
+        assert_eq!(estimate.l1_gas_used, U256::from(size * 16 / 1_000_000));
+        assert_eq!(
+            estimate.l1_fee,
+            U256::from(size) * fee_scaled / U256::from(1_000_000_000_000u64)
+        );
+
+        // Isthmus: `gasUsed * operatorFeeScalar / 1e6 + operatorFeeConstant`.
+        let estimate = info.estimate_tx_fees(&tx, OpSpecId::ISTHMUS).unwrap();
+        assert_eq!(
+            estimate.operator_fee,
+            U256::from(100_000 * 2_000 / 1_000_000 + 30)
+        );
+        // Jovian: `gasUsed * operatorFeeScalar * 100 + operatorFeeConstant`.
+        let estimate = info.estimate_tx_fees(&tx, OpSpecId::JOVIAN).unwrap();
+        assert_eq!(
+            estimate.operator_fee,
+            U256::from(100_000 * 2_000 * 100 + 30u64)
+        );
+        assert_eq!(
+            estimate.total_fee(),
+            estimate.l1_fee + estimate.operator_fee
+        );
+    }
+
+    #[test]
+    fn test_estimate_ignores_cached_cost() {
+        let mut info = ecotone_l1_block_info();
+        info.tx_l1_cost = Some(U256::from(1));
+        let tx = tx_env(TransactionType::Eip1559);
+        let estimate = info.estimate_tx_fees(&tx, OpSpecId::ISTHMUS).unwrap();
+        assert_ne!(estimate.l1_fee, U256::from(1));
+        assert_eq!(info.tx_l1_cost, Some(U256::from(1)));
+
+        let deposit = OpTransaction::builder()
+            .base(TxEnv::builder().gas_limit(100_000))
+            .source_hash(B256_ONE)
+            .build_fill();
+        assert_eq!(
+            info.estimate_tx_fees(&deposit, OpSpecId::ISTHMUS),
+            Some(OpFeeEstimate::default())
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Routing of the base, L1 and operator fees to fee vaults.
+//!
+//! OP Stack chains credit fees to the vault predeploys of [`constants`](crate::constants).
+//! Derivative chains can redirect them or split a fee between recipients with a
+//! [`FeeVaultConfig`] set on the [`OpEvm`](crate::OpEvm).
+use crate::{
+    constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT},
+    OpSpecId,
+};
+use core::fmt::{self, Display};
+use revm::primitives::{Address, U256};
+use std::{collections::BTreeMap, sync::Arc, vec, vec::Vec};
+
+/// Basis points of a whole fee.
+pub const TOTAL_BPS: u16 = 10_000;
+
+/// Share of a fee credited to a recipient.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeShare {
+    /// Recipient of the share.
+    pub recipient: Address,
+    /// Share of the fee in basis points.
+    pub bps: u16,
+}
+
+/// Recipients of a fee.
+///
+/// Shares sum up to [`TOTAL_BPS`]. Amounts are rounded down and the remainder is credited to the
+/// last recipient, so that the whole fee is always credited.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "Vec<FeeShare>", into = "Vec<FeeShare>")
+)]
+pub struct FeeSplit {
+    shares: Vec<FeeShare>,
+}
+
+impl FeeSplit {
+    /// Creates a split that credits the whole fee to one recipient.
+    pub fn single(recipient: Address) -> Self {
+        Self {
+            shares: vec![FeeShare {
+                recipient,
+                bps: TOTAL_BPS,
+            }],
+        }
+    }
+
+    /// Creates a split from shares that sum up to [`TOTAL_BPS`].
+    pub fn new(shares: Vec<FeeShare>) -> Result<Self, FeeSplitError> {
+        if shares.is_empty() {
+            return Err(FeeSplitError::NoRecipients);
+        }
+        let total = shares.iter().map(|share| share.bps as u32).sum::<u32>();
+        if total != TOTAL_BPS as u32 {
+            return Err(FeeSplitError::InvalidTotal(total));
+        }
+        Ok(Self { shares })
+    }
+
+    /// Returns the shares of the split.
+    pub fn shares(&self) -> &[FeeShare] {
+        &self.shares
+    }
+
+    /// Splits the amount between the recipients.
+    pub fn split(&self, amount: U256) -> impl Iterator<Item = (Address, U256)> + '_ {
+        let last = self.shares.len() - 1;
+        let mut remaining = amount;
+        self.shares.iter().enumerate().map(move |(i, share)| {
+            let value = if i == last {
+                remaining
+            } else {
+                let (bps, total) = (U256::from(share.bps), U256::from(TOTAL_BPS));
+                // Split the division to not overflow.
+                amount / total * bps + amount % total * bps / total
+            };
+            remaining -= value;
+            (share.recipient, value)
+        })
+    }
+}
+
+impl TryFrom<Vec<FeeShare>> for FeeSplit {
+    type Error = FeeSplitError;
+
+    fn try_from(shares: Vec<FeeShare>) -> Result<Self, Self::Error> {
+        Self::new(shares)
+    }
+}
+
+impl From<FeeSplit> for Vec<FeeShare> {
+    fn from(split: FeeSplit) -> Self {
+        split.shares
+    }
+}
+
+/// Invalid [`FeeSplit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeeSplitError {
+    /// Split has no recipients.
+    NoRecipients,
+    /// Shares do not sum up to [`TOTAL_BPS`].
+    InvalidTotal(u32),
+}
+
+impl Display for FeeSplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoRecipients => f.write_str("fee split has no recipients"),
+            Self::InvalidTotal(total) => {
+                write!(f, "fee shares sum up to {total} bps instead of {TOTAL_BPS}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FeeSplitError {}
+
+/// Recipients of the fees of non-deposit transactions.
+///
+/// The base fee is credited here, [`CfgEnv::base_fee_recipient`] is ignored on OP chains.
+///
+/// [`CfgEnv::base_fee_recipient`]: revm::context::CfgEnv::base_fee_recipient
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeVaults {
+    /// Recipients of the base fee.
+    pub base_fee: FeeSplit,
+    /// Recipients of the L1 data fee.
+    pub l1_fee: FeeSplit,
+    /// Recipients of the operator fee, from Isthmus.
+    pub operator_fee: FeeSplit,
+}
+
+impl Default for FeeVaults {
+    /// Returns the vault predeploys of OP Stack chains.
+    fn default() -> Self {
+        Self {
+            base_fee: FeeSplit::single(BASE_FEE_RECIPIENT),
+            l1_fee: FeeSplit::single(L1_FEE_RECIPIENT),
+            operator_fee: FeeSplit::single(OPERATOR_FEE_RECIPIENT),
+        }
+    }
+}
+
+/// Fee vaults of a chain per spec.
+///
+/// Serialized as a map from the spec to the vaults used from it. Specs before the first entry use
+/// the vault predeploys.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "BTreeMap<OpSpecId, FeeVaults>",
+        into = "BTreeMap<OpSpecId, FeeVaults>"
+    )
+)]
+pub struct FeeVaultConfig {
+    /// Vaults by the spec they are used from, always set from Bedrock.
+    ///
+    /// Shared so that the config is cheap to clone into the handler of every transaction.
+    schedule: Arc<BTreeMap<OpSpecId, FeeVaults>>,
+}
+
+impl FeeVaultConfig {
+    /// Creates a config that uses the same vaults on all specs.
+    pub fn new(vaults: FeeVaults) -> Self {
+        Self {
+            schedule: Arc::new(BTreeMap::from([(OpSpecId::BEDROCK, vaults)])),
+        }
+    }
+
+    /// Uses the vaults from the given spec, until vaults of a later spec are set.
+    pub fn with_vaults_from(mut self, spec: OpSpecId, vaults: FeeVaults) -> Self {
+        Arc::make_mut(&mut self.schedule).insert(spec, vaults);
+        self
+    }
+
+    /// Returns the vaults used on the given spec.
+    pub fn vaults(&self, spec: OpSpecId) -> &FeeVaults {
+        self.schedule
+            .range(..=spec)
+            .next_back()
+            .map(|(_, vaults)| vaults)
+            .expect("vaults are set from Bedrock")
+    }
+}
+
+impl Default for FeeVaultConfig {
+    fn default() -> Self {
+        Self::new(FeeVaults::default())
+    }
+}
+
+impl From<BTreeMap<OpSpecId, FeeVaults>> for FeeVaultConfig {
+    fn from(schedule: BTreeMap<OpSpecId, FeeVaults>) -> Self {
+        schedule
+            .into_iter()
+            .fold(Self::default(), |config, (spec, vaults)| {
+                config.with_vaults_from(spec, vaults)
+            })
+    }
+}
+
+impl From<FeeVaultConfig> for BTreeMap<OpSpecId, FeeVaults> {
+    fn from(config: FeeVaultConfig) -> Self {
+        Arc::unwrap_or_clone(config.schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Address = Address::with_last_byte(0xa);
+    const B: Address = Address::with_last_byte(0xb);
+    const C: Address = Address::with_last_byte(0xc);
+
+    #[test]
+    fn split_credits_remainder_to_last_recipient() {
+        let split = FeeSplit::new(vec![
+            FeeShare {
+                recipient: A,
+                bps: 3_333,
+            },
+            FeeShare {
+                recipient: B,
+                bps: 3_333,
+            },
+            FeeShare {
+                recipient: C,
+                bps: 3_334,
+            },
+        ])
+        .unwrap();
+
+        let shares = split.split(U256::from(100)).collect::<Vec<_>>();
+        assert_eq!(
+            shares,
+            vec![
+                (A, U256::from(33)),
+                (B, U256::from(33)),
+                (C, U256::from(34))
+            ]
+        );
+
+        let shares = split.split(U256::ZERO).collect::<Vec<_>>();
+        assert!(shares.iter().all(|(_, value)| value.is_zero()));
+
+        let total = split
+            .split(U256::MAX)
+            .fold(U256::ZERO, |total, (_, value)| total + value);
+        assert_eq!(total, U256::MAX);
+
+        let single = FeeSplit::single(A).split(U256::MAX).collect::<Vec<_>>();
+        assert_eq!(single, vec![(A, U256::MAX)]);
+    }
+
+    #[test]
+    fn invalid_split() {
+        assert_eq!(FeeSplit::new(vec![]), Err(FeeSplitError::NoRecipients));
+        assert_eq!(
+            FeeSplit::new(vec![FeeShare {
+                recipient: A,
+                bps: 9_999
+            }]),
+            Err(FeeSplitError::InvalidTotal(9_999))
+        );
+    }
+
+    #[test]
+    fn vaults_per_spec() {
+        let isthmus = FeeVaults {
+            base_fee: FeeSplit::single(A),
+            ..Default::default()
+        };
+        let config = FeeVaultConfig::default().with_vaults_from(OpSpecId::ISTHMUS, isthmus.clone());
+
+        assert_eq!(config.vaults(OpSpecId::BEDROCK), &FeeVaults::default());
+        assert_eq!(config.vaults(OpSpecId::HOLOCENE), &FeeVaults::default());
+        assert_eq!(config.vaults(OpSpecId::ISTHMUS), &isthmus);
+        assert_eq!(config.vaults(OpSpecId::JOVIAN), &isthmus);
+
+        let config = config.with_vaults_from(OpSpecId::BEDROCK, isthmus.clone());
+        assert_eq!(config.vaults(OpSpecId::BEDROCK), &isthmus);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_vaults() {
+        let vaults: FeeVaults = serde_json::from_str(
+            r#"{
+                "base_fee": [
+                    { "recipient": "0x000000000000000000000000000000000000000a", "bps": 7000 },
+                    { "recipient": "0x000000000000000000000000000000000000000b", "bps": 3000 }
+                ],
+                "l1_fee": [{ "recipient": "0x000000000000000000000000000000000000000c", "bps": 10000 }],
+                "operator_fee": [{ "recipient": "0x000000000000000000000000000000000000000c", "bps": 10000 }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(vaults.base_fee.shares()[0].bps, 7_000);
+        assert_eq!(vaults.l1_fee, FeeSplit::single(C));
+
+        assert!(serde_json::from_str::<FeeSplit>(
+            r#"[{ "recipient": "0x000000000000000000000000000000000000000a", "bps": 1 }]"#
+        )
+        .is_err());
+
+        // Specs before the first entry use the vault predeploys.
+        let config: FeeVaultConfig =
+            serde_json::from_value(serde_json::json!({ "ISTHMUS": vaults.clone() })).unwrap();
+        assert_eq!(config.vaults(OpSpecId::HOLOCENE), &FeeVaults::default());
+        assert_eq!(config.vaults(OpSpecId::JOVIAN), &vaults);
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            serde_json::from_value::<FeeVaultConfig>(json).unwrap(),
+            config
+        );
+    }
+}
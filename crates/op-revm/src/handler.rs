@@ -1,10 +1,12 @@
 //!Handler related to Optimism chain
 use crate::{
     api::exec::OpContextTr,
-    constants::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT, OPERATOR_FEE_RECIPIENT},
+    fee_vaults::FeeVaultConfig,
+    interop::{declared_messages, InteropError, MessageOracle},
     transaction::{deposit::DEPOSIT_TRANSACTION_TYPE, OpTransactionError, OpTxTr},
-    L1BlockInfo, OpHaltReason, OpSpecId,
+    DepositReceipt, L1BlockInfo, OpHaltReason, OpSpecId,
 };
+use core::cell::Cell;
 use revm::{
     context::{journaled_state::JournalCheckpoint, result::InvalidTransaction, LocalContextTr},
     context_interface::{
@@ -23,7 +25,7 @@ use revm::{
     interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, Gas},
     primitives::{hardfork::SpecId, U256},
 };
-use std::boxed::Box;
+use std::{boxed::Box, sync::Arc};
 
 /// Optimism handler extends the [`Handler`] with Optimism specific logic.
 #[derive(Debug, Clone)]
@@ -31,15 +33,38 @@ pub struct OpHandler<EVM, ERROR, FRAME> {
     /// Mainnet handler allows us to use functions from the mainnet handler inside optimism handler.
     /// So we dont duplicate the logic
     pub mainnet: MainnetHandler<EVM, ERROR, FRAME>,
+    /// Optimism specific configuration, see [`OpHandlerConfig`].
+    config: OpHandlerConfig,
+    /// Receipt fields of the executed deposit, set while the transaction is run.
+    deposit_receipt: Cell<Option<DepositReceipt>>,
 }
 
 impl<EVM, ERROR, FRAME> OpHandler<EVM, ERROR, FRAME> {
     /// Create a new Optimism handler.
     pub fn new() -> Self {
+        Self::with_config(OpHandlerConfig::default())
+    }
+
+    /// Create a new Optimism handler with the given configuration.
+    pub fn with_config(config: OpHandlerConfig) -> Self {
         Self {
             mainnet: MainnetHandler::default(),
+            config,
+            deposit_receipt: Cell::new(None),
         }
     }
+
+    /// Returns the Optimism specific configuration.
+    pub fn config(&self) -> &OpHandlerConfig {
+        &self.config
+    }
+
+    /// Returns the receipt fields of the deposit run by this handler, including failed deposits.
+    ///
+    /// `None` if the transaction is not a deposit or was rejected before execution.
+    pub fn deposit_receipt(&self) -> Option<DepositReceipt> {
+        self.deposit_receipt.get()
+    }
 }
 
 impl<EVM, ERROR, FRAME> Default for OpHandler<EVM, ERROR, FRAME> {
@@ -48,6 +73,38 @@ impl<EVM, ERROR, FRAME> Default for OpHandler<EVM, ERROR, FRAME> {
     }
 }
 
+/// Optimism specific configuration of the [`OpHandler`].
+///
+/// It is set on the [`OpEvm`](crate::OpEvm) and passed to the handler of every transaction.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct OpHandlerConfig {
+    /// Oracle used to validate executing messages from the Interop hardfork.
+    ///
+    /// Without an oracle, transactions that declare executing messages are rejected.
+    pub message_oracle: Option<Arc<dyn MessageOracle>>,
+    /// Recipients of the base, L1 and operator fees.
+    ///
+    /// Defaults to the fee vault predeploys. [`CfgEnv::base_fee_recipient`] is not used.
+    ///
+    /// [`CfgEnv::base_fee_recipient`]: revm::context::CfgEnv::base_fee_recipient
+    pub fee_vaults: FeeVaultConfig,
+}
+
+impl OpHandlerConfig {
+    /// Sets the oracle used to validate executing messages.
+    pub fn with_message_oracle(mut self, oracle: Arc<dyn MessageOracle>) -> Self {
+        self.message_oracle = Some(oracle);
+        self
+    }
+
+    /// Sets the recipients of the base, L1 and operator fees.
+    pub fn with_fee_vaults(mut self, fee_vaults: FeeVaultConfig) -> Self {
+        self.fee_vaults = fee_vaults;
+        self
+    }
+}
+
 /// Trait to check if the error is a transaction error.
 ///
 /// Used in cache_error handler to catch deposit transaction that was halted.
@@ -75,6 +132,8 @@ where
     type HaltReason = OpHaltReason;
 
     fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        // Clear the receipt of a previous transaction run by this handler.
+        self.deposit_receipt.set(None);
         // Do not perform any extra validation for deposit transactions, they are pre-verified on L1.
         let ctx = evm.ctx();
         let tx = ctx.tx();
@@ -94,6 +153,25 @@ where
             return Err(OpTransactionError::MissingEnvelopedTx.into());
         }
 
+        // Validate executing messages declared in the CrossL2Inbox access list.
+        if ctx.cfg().spec().is_enabled_in(OpSpecId::INTEROP) {
+            let messages = declared_messages(tx.access_list().into_iter().flatten())
+                .map_err(OpTransactionError::from)?;
+            if !messages.is_empty() {
+                let oracle = self
+                    .config
+                    .message_oracle
+                    .as_deref()
+                    .ok_or(OpTransactionError::from(InteropError::MissingMessageOracle))?;
+                let timestamp = ctx.block().timestamp().saturating_to();
+                for message in messages {
+                    message
+                        .validate(oracle, timestamp)
+                        .map_err(OpTransactionError::from)?;
+                }
+            }
+        }
+
         self.mainnet.validate_env(evm)
     }
 
@@ -128,6 +206,9 @@ where
                 new_balance = new_balance.max(tx.value());
             }
 
+            self.deposit_receipt
+                .set(Some(DepositReceipt::new(spec, caller.nonce())));
+
             // set the new balance and bump the nonce if it is a call
             caller.set_balance(new_balance);
             if tx.kind().is_call() {
@@ -300,7 +381,8 @@ where
             return Ok(());
         }
 
-        self.mainnet.reward_beneficiary(evm, frame_result)?;
+        // The base fee goes to the base fee vault, `Cfg::base_fee_recipient` is ignored.
+        post_execution::reward_beneficiary(evm.ctx(), frame_result.gas())?;
         let basefee = evm.ctx().block().basefee() as u128;
 
         // If the transaction is not a deposit transaction, fees are paid out
@@ -329,12 +411,15 @@ where
         let base_fee_amount = U256::from(basefee.saturating_mul(frame_result.gas().used() as u128));
 
         // Send fees to their respective recipients
-        for (recipient, amount) in [
-            (L1_FEE_RECIPIENT, l1_cost),
-            (BASE_FEE_RECIPIENT, base_fee_amount),
-            (OPERATOR_FEE_RECIPIENT, operator_fee_cost),
+        let vaults = self.config.fee_vaults.vaults(spec);
+        for (split, amount) in [
+            (&vaults.l1_fee, l1_cost),
+            (&vaults.base_fee, base_fee_amount),
+            (&vaults.operator_fee, operator_fee_cost),
         ] {
-            ctx.journal_mut().balance_incr(recipient, amount)?;
+            for (recipient, amount) in split.split(amount) {
+                ctx.journal_mut().balance_incr(recipient, amount)?;
+            }
         }
 
         Ok(())
@@ -401,11 +486,14 @@ where
             // Increment sender nonce and account balance for the mint amount. Deposits
             // always persist the mint amount, even if the transaction fails.
             let mut acc = journal.load_account_mut(caller)?;
+            let nonce = acc.nonce();
             acc.bump_nonce();
             acc.incr_balance(U256::from(mint.unwrap_or_default()));
 
             // We can now commit the changes.
             journal.commit_tx();
+            self.deposit_receipt
+                .set(Some(DepositReceipt::new(spec, nonce)));
 
             // The gas used of a failed deposit post-regolith is the gas
             // limit of the transaction. pre-regolith, it is the gas limit
@@ -451,21 +539,25 @@ mod tests {
     use crate::{
         api::default_ctx::OpContext,
         constants::{
-            BASE_FEE_SCALAR_OFFSET, ECOTONE_L1_BLOB_BASE_FEE_SLOT, ECOTONE_L1_FEE_SCALARS_SLOT,
-            L1_BASE_FEE_SLOT, L1_BLOCK_CONTRACT, OPERATOR_FEE_SCALARS_SLOT,
+            BASE_FEE_RECIPIENT, BASE_FEE_SCALAR_OFFSET, ECOTONE_L1_BLOB_BASE_FEE_SLOT,
+            ECOTONE_L1_FEE_SCALARS_SLOT, L1_BASE_FEE_SLOT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT,
+            OPERATOR_FEE_SCALARS_SLOT,
         },
+        fee_vaults::{FeeShare, FeeSplit, FeeVaults},
         DefaultOp, OpBuilder, OpTransaction,
     };
     use alloy_primitives::uint;
     use revm::{
-        context::{BlockEnv, Context, TxEnv},
+        bytecode::{opcode, Bytecode},
+        context::{BlockEnv, Context, ContextTr, TxEnv},
         context_interface::result::InvalidTransaction,
         database::InMemoryDB,
         database_interface::EmptyDB,
         handler::EthFrame,
         interpreter::{CallOutcome, InstructionResult, InterpreterResult},
-        primitives::{bytes, Address, Bytes, B256},
+        primitives::{bytes, Address, Bytes, TxKind, B256},
         state::AccountInfo,
+        ExecuteEvm,
     };
     use rstest::rstest;
     use std::boxed::Box;
@@ -744,7 +836,7 @@ mod tests {
                 operator_fee_scalar: Some(U256::from(OPERATOR_FEE_SCALAR)),
                 operator_fee_constant: Some(U256::from(OPERATOR_FEE_CONST)),
                 tx_l1_cost: Some(U256::ZERO),
-                da_footprint_gas_scalar: None
+                da_footprint_gas_scalar: None,
             }
         );
     }
@@ -1164,6 +1256,126 @@ mod tests {
         assert_eq!(account.info.balance, U256::from(1));
     }
 
+    fn reward_fee_vaults(spec: OpSpecId, fee_vaults: FeeVaultConfig) -> Vec<U256> {
+        let caller = Address::with_last_byte(0xc0);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo::default().with_balance(U256::from(10).pow(U256::from(18))),
+        );
+        let mut evm = Context::op()
+            .with_db(db)
+            .with_chain(L1BlockInfo {
+                l1_base_fee: U256::from(1_000),
+                l1_fee_overhead: Some(U256::from(1_000)),
+                l1_base_fee_scalar: U256::from(1_000),
+                l2_block: Some(U256::from(0)),
+                ..Default::default()
+            })
+            .modify_block_chained(|block| block.basefee = 100)
+            .modify_cfg_chained(|cfg| cfg.spec = spec)
+            .build_op()
+            .with_fee_vaults(fee_vaults);
+        let tx = OpTransaction::builder()
+            .base(
+                TxEnv::builder()
+                    .caller(caller)
+                    .kind(TxKind::Call(Address::with_last_byte(0xc1)))
+                    .gas_price(100),
+            )
+            .enveloped_tx(Some(bytes!("FACADE")))
+            .build_fill();
+
+        let output = evm.transact(tx).unwrap();
+        assert!(output.result.result.is_success());
+
+        [
+            BASE_FEE_RECIPIENT,
+            L1_FEE_RECIPIENT,
+            Address::with_last_byte(0xa),
+            Address::with_last_byte(0xb),
+        ]
+        .into_iter()
+        .map(|address| {
+            output
+                .state
+                .get(&address)
+                .map(|account| account.info.balance)
+                .unwrap_or_default()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_base_fee_recipient_is_ignored() {
+        let caller = Address::with_last_byte(0xc0);
+        let recipient = Address::with_last_byte(0xfe);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo::default().with_balance(U256::from(10).pow(U256::from(18))),
+        );
+        let mut evm = Context::op()
+            .with_db(db)
+            .modify_block_chained(|block| block.basefee = 100)
+            .modify_cfg_chained(|cfg| cfg.base_fee_recipient = Some(recipient))
+            .build_op();
+        let tx = OpTransaction::builder()
+            .base(
+                TxEnv::builder()
+                    .caller(caller)
+                    .kind(TxKind::Call(Address::with_last_byte(0xc1)))
+                    .gas_price(100),
+            )
+            .build_fill();
+
+        let output = evm.transact(tx).unwrap();
+        assert!(output.result.result.is_success());
+        // The base fee is credited once, to the base fee vault.
+        assert!(!output.state.contains_key(&recipient));
+        assert_eq!(
+            output.state[&BASE_FEE_RECIPIENT].info.balance,
+            U256::from(100 * 21_000)
+        );
+    }
+
+    #[test]
+    fn test_reward_fee_vaults() {
+        // Base fee is 100 * 21_000. Before Regolith l1block cost includes 68 bytes of signature.
+        let base_fee = U256::from(2_100_000);
+        let custom = FeeVaults {
+            base_fee: FeeSplit::new(vec![
+                FeeShare {
+                    recipient: Address::with_last_byte(0xa),
+                    bps: 7_000,
+                },
+                FeeShare {
+                    recipient: Address::with_last_byte(0xb),
+                    bps: 3_000,
+                },
+            ])
+            .unwrap(),
+            l1_fee: FeeSplit::single(Address::with_last_byte(0xb)),
+            ..Default::default()
+        };
+        let config = FeeVaultConfig::default().with_vaults_from(OpSpecId::REGOLITH, custom);
+
+        // Default vaults before Regolith.
+        assert_eq!(
+            reward_fee_vaults(OpSpecId::BEDROCK, config.clone()),
+            vec![base_fee, U256::from(2136), U256::ZERO, U256::ZERO]
+        );
+        assert_eq!(
+            reward_fee_vaults(OpSpecId::REGOLITH, config),
+            vec![
+                U256::ZERO,
+                U256::ZERO,
+                U256::from(1_470_000),
+                U256::from(630_000 + 1048)
+            ]
+        );
+    }
+
     #[test]
     fn test_remove_l1_cost_lack_of_funds() {
         let caller = Address::ZERO;
@@ -1298,6 +1510,79 @@ mod tests {
         )
     }
 
+    fn deposit_receipt_ctx(spec: OpSpecId, code: Bytecode) -> OpContext<InMemoryDB> {
+        let caller = Address::with_last_byte(0xc0);
+        let target = Address::with_last_byte(0xc1);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::default().with_nonce(5));
+        db.insert_account_info(target, AccountInfo::default().with_code(code));
+
+        Context::op()
+            .with_db(db)
+            .modify_tx_chained(|tx| {
+                tx.base.caller = caller;
+                tx.base.kind = TxKind::Call(target);
+                tx.base.gas_limit = 100_000;
+                tx.deposit.source_hash = B256::from([1u8; 32]);
+                tx.deposit.mint = Some(10);
+            })
+            .modify_cfg_chained(|cfg| cfg.spec = spec)
+    }
+
+    #[rstest]
+    #[case::bedrock(OpSpecId::BEDROCK, None, None)]
+    #[case::regolith(OpSpecId::REGOLITH, Some(5), None)]
+    #[case::canyon(OpSpecId::CANYON, Some(5), Some(1))]
+    fn test_deposit_receipt(
+        #[case] spec: OpSpecId,
+        #[case] deposit_nonce: Option<u64>,
+        #[case] deposit_receipt_version: Option<u64>,
+    ) {
+        let ctx = deposit_receipt_ctx(spec, Bytecode::new_legacy([opcode::STOP].into()));
+        let tx = ctx.tx.clone();
+        let mut evm = ctx.build_op();
+
+        let output = evm.transact(tx).unwrap();
+        assert!(output.result.result.is_success());
+        assert_eq!(output.state[&Address::with_last_byte(0xc0)].info.nonce, 6);
+        assert_eq!(
+            output.result.deposit_receipt,
+            Some(DepositReceipt {
+                deposit_nonce,
+                deposit_receipt_version,
+            })
+        );
+    }
+
+    #[test]
+    fn test_failed_deposit_receipt() {
+        // POP on an empty stack halts, which fails the deposit post-Regolith.
+        let ctx = deposit_receipt_ctx(OpSpecId::CANYON, Bytecode::new_legacy([opcode::POP].into()));
+        let tx = ctx.tx.clone();
+        let mut evm = ctx.build_op();
+
+        let output = evm.transact(tx).unwrap();
+        assert_eq!(
+            output.result.result,
+            ExecutionResult::Halt {
+                reason: OpHaltReason::FailedDeposit,
+                gas_used: 100_000,
+            }
+        );
+
+        // Mint is kept and the nonce is bumped.
+        let caller = &output.state[&Address::with_last_byte(0xc0)].info;
+        assert_eq!(caller.nonce, 6);
+        assert_eq!(caller.balance, U256::from(10));
+        assert_eq!(
+            output.result.deposit_receipt,
+            Some(DepositReceipt {
+                deposit_nonce: Some(5),
+                deposit_receipt_version: Some(1),
+            })
+        );
+    }
+
     #[test]
     fn test_tx_zero_value_touch_caller() {
         let ctx = Context::op();
@@ -1457,4 +1742,122 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_validate_executing_messages() {
+        use crate::interop::{InMemoryMessageOracle, MessageIdentifier, CROSS_L2_INBOX_ADDRESS};
+        use revm::context_interface::transaction::{AccessList, AccessListItem};
+
+        let identifier = MessageIdentifier {
+            origin: Address::with_last_byte(1),
+            block_number: 10,
+            log_index: 0,
+            timestamp: 100,
+            chain_id: U256::from(901),
+        };
+        let payload_hash = B256::with_last_byte(2);
+        let oracle = Arc::new(InMemoryMessageOracle::default().with_log(identifier, payload_hash));
+
+        let tx_with_keys = |storage_keys| {
+            OpTransaction::builder()
+                .base(
+                    TxEnv::builder()
+                        .tx_type(Some(1))
+                        .access_list(AccessList(vec![AccessListItem {
+                            address: CROSS_L2_INBOX_ADDRESS,
+                            storage_keys,
+                        }])),
+                )
+                .build_fill()
+        };
+        let validate = |handler: &OpHandler<_, _, _>, spec, storage_keys| {
+            let mut evm = Context::op()
+                .with_tx(tx_with_keys(storage_keys))
+                .modify_block_chained(|block| block.timestamp = U256::from(200))
+                .modify_cfg_chained(|cfg| cfg.spec = spec)
+                .build_op();
+            handler.validate_env(&mut evm)
+        };
+
+        let valid = identifier.declare(payload_hash).access_list_keys();
+        let invalid = identifier.declare(B256::ZERO).access_list_keys();
+        let handler =
+            OpHandler::<_, EVMError<_, OpTransactionError>, EthFrame<EthInterpreter>>::new();
+        let with_oracle =
+            OpHandler::with_config(OpHandlerConfig::default().with_message_oracle(oracle));
+
+        assert_eq!(
+            validate(&with_oracle, OpSpecId::INTEROP, valid.clone()),
+            Ok(())
+        );
+        assert_eq!(
+            validate(&with_oracle, OpSpecId::INTEROP, invalid.clone()),
+            Err(EVMError::Transaction(
+                OpTransactionError::InvalidExecutingMessage(InteropError::ChecksumMismatch)
+            ))
+        );
+        assert_eq!(
+            validate(&with_oracle, OpSpecId::INTEROP, valid[..1].to_vec()),
+            Err(EVMError::Transaction(
+                OpTransactionError::InvalidExecutingMessage(InteropError::MalformedAccessList)
+            ))
+        );
+        assert_eq!(
+            validate(&handler, OpSpecId::INTEROP, valid.clone()),
+            Err(EVMError::Transaction(
+                OpTransactionError::InvalidExecutingMessage(InteropError::MissingMessageOracle)
+            ))
+        );
+        // Transactions without executing messages do not need an oracle.
+        assert_eq!(validate(&handler, OpSpecId::INTEROP, vec![]), Ok(()));
+        // Executing messages are not validated before Interop.
+        assert_eq!(validate(&handler, OpSpecId::JOVIAN, invalid), Ok(()));
+    }
+
+    #[test]
+    fn test_transact_with_message_oracle() {
+        use crate::interop::{InMemoryMessageOracle, MessageIdentifier, CROSS_L2_INBOX_ADDRESS};
+        use revm::context_interface::transaction::{AccessList, AccessListItem};
+
+        let identifier = MessageIdentifier {
+            origin: Address::with_last_byte(1),
+            block_number: 10,
+            log_index: 0,
+            timestamp: 100,
+            chain_id: U256::from(901),
+        };
+        let payload_hash = B256::with_last_byte(2);
+        let oracle = Arc::new(InMemoryMessageOracle::default().with_log(identifier, payload_hash));
+
+        let tx = |payload_hash| {
+            OpTransaction::builder()
+                .base(
+                    TxEnv::builder()
+                        .tx_type(Some(1))
+                        .access_list(AccessList(vec![AccessListItem {
+                            address: CROSS_L2_INBOX_ADDRESS,
+                            storage_keys: identifier.declare(payload_hash).access_list_keys(),
+                        }])),
+                )
+                .build_fill()
+        };
+        let mut evm = Context::op()
+            .modify_block_chained(|block| block.timestamp = U256::from(200))
+            .modify_cfg_chained(|cfg| cfg.spec = OpSpecId::INTEROP)
+            .build_op()
+            .with_message_oracle(oracle);
+
+        assert!(evm
+            .transact(tx(payload_hash))
+            .unwrap()
+            .result
+            .result
+            .is_success());
+        assert_eq!(
+            evm.transact(tx(B256::ZERO)).unwrap_err(),
+            EVMError::Transaction(OpTransactionError::InvalidExecutingMessage(
+                InteropError::ChecksumMismatch
+            ))
+        );
+    }
 }
@@ -0,0 +1,86 @@
+//! Fork activation schedules of OP Stack chains.
+use crate::OpSpecId;
+use revm::context::{ChainHardforks, ForkCondition};
+use std::vec;
+
+/// OP Mainnet chain ID.
+pub const OP_MAINNET_CHAIN_ID: u64 = 10;
+/// Base chain ID.
+pub const BASE_MAINNET_CHAIN_ID: u64 = 8453;
+
+/// Returns the schedule of a known OP Stack chain.
+pub fn from_chain_id(chain_id: u64) -> Option<ChainHardforks<OpSpecId>> {
+    match chain_id {
+        OP_MAINNET_CHAIN_ID => Some(op_mainnet()),
+        BASE_MAINNET_CHAIN_ID => Some(base_mainnet()),
+        _ => None,
+    }
+}
+
+/// OP Mainnet schedule.
+///
+/// Blocks before the Bedrock migration were executed by the legacy client and have no spec.
+pub fn op_mainnet() -> ChainHardforks<OpSpecId> {
+    superchain(105_235_063)
+}
+
+/// Base schedule.
+pub fn base_mainnet() -> ChainHardforks<OpSpecId> {
+    superchain(0)
+}
+
+/// Schedule of a Superchain member starting at Bedrock at the given block, with Regolith active
+/// at Bedrock and the following forks at their Superchain timestamps.
+fn superchain(bedrock_block: u64) -> ChainHardforks<OpSpecId> {
+    use ForkCondition::{Block, Timestamp};
+    use OpSpecId::*;
+    ChainHardforks::new(vec![
+        (BEDROCK, Block(bedrock_block)),
+        (REGOLITH, Block(bedrock_block)),
+        (CANYON, Timestamp(1_704_992_401)),
+        (ECOTONE, Timestamp(1_710_374_401)),
+        (FJORD, Timestamp(1_720_627_201)),
+        (GRANITE, Timestamp(1_726_070_401)),
+        (HOLOCENE, Timestamp(1_736_445_601)),
+        (ISTHMUS, Timestamp(1_746_806_401)),
+        (JOVIAN, Timestamp(1_764_691_201)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn op_mainnet_schedule() {
+        let hardforks = op_mainnet();
+        assert_eq!(hardforks.spec_at(105_235_062, 1_686_068_903), None);
+        assert_eq!(
+            hardforks.spec_at(105_235_063, 1_686_068_905),
+            Some(OpSpecId::REGOLITH)
+        );
+        assert_eq!(
+            hardforks.spec_at(130_000_000, 1_736_445_601),
+            Some(OpSpecId::HOLOCENE)
+        );
+        assert_eq!(
+            hardforks.spec_at(u64::MAX, u64::MAX),
+            Some(OpSpecId::JOVIAN)
+        );
+        assert!(hardforks.forks().windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[test]
+    fn base_schedule() {
+        let hardforks = from_chain_id(BASE_MAINNET_CHAIN_ID).unwrap();
+        assert_eq!(
+            hardforks.spec_at(0, 1_686_789_347),
+            Some(OpSpecId::REGOLITH)
+        );
+        assert_eq!(
+            hardforks.spec_at(30_000_000, 1_746_806_401),
+            Some(OpSpecId::ISTHMUS)
+        );
+        assert_eq!(from_chain_id(1), None);
+    }
+}
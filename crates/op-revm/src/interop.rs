@@ -0,0 +1,427 @@
+//! OP Stack interop executing-message validation.
+//!
+//! Transactions that execute cross-chain messages declare them in the access list of the
+//! [`CROSS_L2_INBOX_ADDRESS`] predeploy. Every declared message is a lookup entry that identifies
+//! the initiating log on the remote chain, an optional chain ID extension entry and a checksum
+//! entry that commits to the full [`MessageIdentifier`] and the message payload.
+//!
+//! Declared messages are resolved with a [`MessageOracle`] and a transaction that declares an
+//! invalid message is rejected before execution.
+use core::fmt::{self, Debug, Display};
+use revm::{
+    context_interface::transaction::AccessListItemTr,
+    primitives::{address, keccak256, Address, HashMap, Log, B256, U256},
+};
+use std::vec::Vec;
+
+/// Address of the `CrossL2Inbox` predeploy.
+pub const CROSS_L2_INBOX_ADDRESS: Address = address!("4200000000000000000000000000000000000022");
+
+/// Messages initiated more than this many seconds before the executing block are expired.
+pub const MESSAGE_EXPIRY_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Type byte of the access list entry with the lookup fields of a message identifier.
+pub const LOOKUP_ENTRY_TYPE: u8 = 0x01;
+
+/// Type byte of the access list entry with the upper 24 bytes of a chain ID that does not fit
+/// in the lookup entry.
+pub const CHAIN_ID_EXTENSION_ENTRY_TYPE: u8 = 0x02;
+
+/// Type byte of the access list entry with the message checksum.
+pub const CHECKSUM_ENTRY_TYPE: u8 = 0x03;
+
+/// Identifier of a log emitted on a remote chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageIdentifier {
+    /// Address that emitted the log.
+    pub origin: Address,
+    /// Number of the block that contains the log.
+    pub block_number: u64,
+    /// Index of the log in the block.
+    pub log_index: u32,
+    /// Timestamp of the block that contains the log.
+    pub timestamp: u64,
+    /// Chain ID of the remote chain.
+    pub chain_id: U256,
+}
+
+impl MessageIdentifier {
+    /// Returns the checksum of the message with the given payload hash, as declared in the
+    /// access list.
+    pub fn checksum(&self, payload_hash: B256) -> B256 {
+        let mut buf = [0u8; 64];
+
+        buf[..20].copy_from_slice(self.origin.as_slice());
+        buf[20..52].copy_from_slice(payload_hash.as_slice());
+        let log_hash = keccak256(&buf[..52]);
+
+        buf[..32].copy_from_slice(log_hash.as_slice());
+        buf[32..44].fill(0);
+        buf[44..52].copy_from_slice(&self.block_number.to_be_bytes());
+        buf[52..60].copy_from_slice(&self.timestamp.to_be_bytes());
+        buf[60..64].copy_from_slice(&self.log_index.to_be_bytes());
+        let id_log_hash = keccak256(buf);
+
+        buf[..32].copy_from_slice(id_log_hash.as_slice());
+        buf[32..].copy_from_slice(&self.chain_id.to_be_bytes::<32>());
+        let mut checksum = keccak256(buf);
+        checksum[0] = CHECKSUM_ENTRY_TYPE;
+        checksum
+    }
+
+    /// Returns the message declaration of this identifier with the given payload hash.
+    pub fn declare(&self, payload_hash: B256) -> DeclaredMessage {
+        DeclaredMessage {
+            chain_id: self.chain_id,
+            block_number: self.block_number,
+            timestamp: self.timestamp,
+            log_index: self.log_index,
+            checksum: self.checksum(payload_hash),
+        }
+    }
+}
+
+/// Returns the payload hash of a log, the hash of its topics followed by its data.
+pub fn message_payload_hash(log: &Log) -> B256 {
+    let topics = log.topics();
+    let mut payload = Vec::with_capacity(topics.len() * 32 + log.data.data.len());
+    for topic in topics {
+        payload.extend_from_slice(topic.as_slice());
+    }
+    payload.extend_from_slice(&log.data.data);
+    keccak256(payload)
+}
+
+/// Executing message declared in the access list of the [`CROSS_L2_INBOX_ADDRESS`].
+///
+/// The origin of the message is not declared, it is part of the checksum.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeclaredMessage {
+    /// Chain ID of the remote chain.
+    pub chain_id: U256,
+    /// Number of the block that contains the log.
+    pub block_number: u64,
+    /// Timestamp of the block that contains the log.
+    pub timestamp: u64,
+    /// Index of the log in the block.
+    pub log_index: u32,
+    /// Checksum of the message identifier and payload.
+    pub checksum: B256,
+}
+
+impl DeclaredMessage {
+    /// Encodes the message as access list storage keys.
+    pub fn access_list_keys(&self) -> Vec<B256> {
+        let chain_id = self.chain_id.to_be_bytes::<32>();
+
+        let mut lookup = B256::ZERO;
+        lookup[0] = LOOKUP_ENTRY_TYPE;
+        lookup[4..12].copy_from_slice(&chain_id[24..]);
+        lookup[12..20].copy_from_slice(&self.block_number.to_be_bytes());
+        lookup[20..28].copy_from_slice(&self.timestamp.to_be_bytes());
+        lookup[28..].copy_from_slice(&self.log_index.to_be_bytes());
+
+        let mut keys = Vec::with_capacity(3);
+        keys.push(lookup);
+        if self.chain_id > U256::from(u64::MAX) {
+            let mut extension = B256::ZERO;
+            extension[0] = CHAIN_ID_EXTENSION_ENTRY_TYPE;
+            extension[8..].copy_from_slice(&chain_id[..24]);
+            keys.push(extension);
+        }
+        keys.push(self.checksum);
+        keys
+    }
+
+    /// Validates the message against the oracle for a block with the given timestamp.
+    pub fn validate(
+        &self,
+        oracle: &dyn MessageOracle,
+        block_timestamp: u64,
+    ) -> Result<(), InteropError> {
+        if self.timestamp > block_timestamp {
+            return Err(InteropError::FutureMessage);
+        }
+        if self.timestamp.saturating_add(MESSAGE_EXPIRY_WINDOW) < block_timestamp {
+            return Err(InteropError::ExpiredMessage);
+        }
+        let log = oracle
+            .log(self.chain_id, self.block_number, self.log_index)
+            .ok_or(InteropError::UnknownMessage)?;
+        if log.timestamp != self.timestamp {
+            return Err(InteropError::TimestampMismatch);
+        }
+        let identifier = MessageIdentifier {
+            origin: log.origin,
+            block_number: self.block_number,
+            log_index: self.log_index,
+            timestamp: self.timestamp,
+            chain_id: self.chain_id,
+        };
+        if identifier.checksum(log.payload_hash) != self.checksum {
+            return Err(InteropError::ChecksumMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the messages declared in the storage keys of a [`CROSS_L2_INBOX_ADDRESS`] access list
+/// item.
+pub fn decode_access_list_keys<'a>(
+    keys: impl IntoIterator<Item = &'a B256>,
+) -> Result<Vec<DeclaredMessage>, InteropError> {
+    let mut keys = keys.into_iter();
+    let mut messages = Vec::new();
+    while let Some(lookup) = keys.next() {
+        if lookup[0] != LOOKUP_ENTRY_TYPE || lookup[1..4] != [0; 3] {
+            return Err(InteropError::MalformedAccessList);
+        }
+        let mut chain_id = [0u8; 32];
+        chain_id[24..].copy_from_slice(&lookup[4..12]);
+
+        let mut next = keys.next().ok_or(InteropError::MalformedAccessList)?;
+        if next[0] == CHAIN_ID_EXTENSION_ENTRY_TYPE {
+            if next[1..8] != [0; 7] {
+                return Err(InteropError::MalformedAccessList);
+            }
+            chain_id[..24].copy_from_slice(&next[8..]);
+            next = keys.next().ok_or(InteropError::MalformedAccessList)?;
+        }
+        if next[0] != CHECKSUM_ENTRY_TYPE {
+            return Err(InteropError::MalformedAccessList);
+        }
+
+        messages.push(DeclaredMessage {
+            chain_id: U256::from_be_bytes(chain_id),
+            block_number: u64::from_be_bytes(lookup[12..20].try_into().unwrap()),
+            timestamp: u64::from_be_bytes(lookup[20..28].try_into().unwrap()),
+            log_index: u32::from_be_bytes(lookup[28..].try_into().unwrap()),
+            checksum: *next,
+        });
+    }
+    Ok(messages)
+}
+
+/// Decodes the messages declared in all [`CROSS_L2_INBOX_ADDRESS`] items of an access list.
+pub fn declared_messages<I: AccessListItemTr>(
+    access_list: impl IntoIterator<Item = I>,
+) -> Result<Vec<DeclaredMessage>, InteropError> {
+    let mut messages = Vec::new();
+    for item in access_list {
+        if *item.address() == CROSS_L2_INBOX_ADDRESS {
+            messages.extend(decode_access_list_keys(item.storage_slots())?);
+        }
+    }
+    Ok(messages)
+}
+
+/// Log emitted on a remote chain, as returned by a [`MessageOracle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoteLog {
+    /// Address that emitted the log.
+    pub origin: Address,
+    /// Payload hash of the log, see [`message_payload_hash`].
+    pub payload_hash: B256,
+    /// Timestamp of the block that contains the log.
+    pub timestamp: u64,
+}
+
+/// Source of the logs of remote chains, used to validate executing messages.
+///
+/// Usually backed by the supervisor or by nodes of the chains in the dependency set.
+pub trait MessageOracle: Debug + Send + Sync {
+    /// Returns the log with the given index in the block of the remote chain, or `None` if the
+    /// chain is not in the dependency set or the log does not exist.
+    fn log(&self, chain_id: U256, block_number: u64, log_index: u32) -> Option<RemoteLog>;
+}
+
+/// [`MessageOracle`] that holds the remote logs in memory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InMemoryMessageOracle {
+    /// Logs keyed by chain ID, block number and log index.
+    pub logs: HashMap<(U256, u64, u32), RemoteLog>,
+}
+
+impl InMemoryMessageOracle {
+    /// Inserts the log with the given identifier and payload hash.
+    pub fn insert(&mut self, identifier: MessageIdentifier, payload_hash: B256) {
+        self.logs.insert(
+            (
+                identifier.chain_id,
+                identifier.block_number,
+                identifier.log_index,
+            ),
+            RemoteLog {
+                origin: identifier.origin,
+                payload_hash,
+                timestamp: identifier.timestamp,
+            },
+        );
+    }
+
+    /// Inserts the log with the given identifier and payload hash.
+    pub fn with_log(mut self, identifier: MessageIdentifier, payload_hash: B256) -> Self {
+        self.insert(identifier, payload_hash);
+        self
+    }
+}
+
+impl MessageOracle for InMemoryMessageOracle {
+    fn log(&self, chain_id: U256, block_number: u64, log_index: u32) -> Option<RemoteLog> {
+        self.logs.get(&(chain_id, block_number, log_index)).copied()
+    }
+}
+
+/// Invalid executing message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InteropError {
+    /// Access list of the `CrossL2Inbox` is not a sequence of lookup, optional chain ID
+    /// extension and checksum entries.
+    MalformedAccessList,
+    /// Transaction declares executing messages but no [`MessageOracle`] is configured.
+    MissingMessageOracle,
+    /// Message is initiated after the executing block.
+    FutureMessage,
+    /// Message is older than the [`MESSAGE_EXPIRY_WINDOW`].
+    ExpiredMessage,
+    /// Initiating log does not exist on the remote chain.
+    UnknownMessage,
+    /// Declared timestamp differs from the timestamp of the block with the initiating log.
+    TimestampMismatch,
+    /// Declared checksum does not match the initiating log.
+    ChecksumMismatch,
+}
+
+impl Display for InteropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::MalformedAccessList => "malformed CrossL2Inbox access list",
+            Self::MissingMessageOracle => "no message oracle to validate executing messages",
+            Self::FutureMessage => "message is initiated after the executing block",
+            Self::ExpiredMessage => "message is expired",
+            Self::UnknownMessage => "initiating message not found",
+            Self::TimestampMismatch => "message timestamp mismatch",
+            Self::ChecksumMismatch => "message checksum mismatch",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for InteropError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{b256, bytes, LogData};
+    use std::vec;
+
+    fn identifier(chain_id: U256) -> MessageIdentifier {
+        MessageIdentifier {
+            origin: address!("0x5fbdb2315678afecb367f032d93f642f64180aa3"),
+            block_number: 42,
+            log_index: 3,
+            timestamp: 1_000,
+            chain_id,
+        }
+    }
+
+    fn payload_hash() -> B256 {
+        message_payload_hash(&Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(
+                vec![b256!(
+                    "0x382409ac69001e11931a28435afef442cbfd20d9891907e8fa373ba7d351f320"
+                )],
+                bytes!("0xdeadbeef"),
+            ),
+        })
+    }
+
+    #[test]
+    fn access_list_round_trip() {
+        for chain_id in [U256::from(10), U256::MAX] {
+            let message = identifier(chain_id).declare(payload_hash());
+            let keys = message.access_list_keys();
+            assert_eq!(keys.len(), if chain_id == U256::MAX { 3 } else { 2 });
+            assert_eq!(keys.last().unwrap()[0], CHECKSUM_ENTRY_TYPE);
+            assert_eq!(decode_access_list_keys(&keys), Ok(vec![message]));
+        }
+    }
+
+    #[test]
+    fn malformed_access_list() {
+        let keys = identifier(U256::from(10))
+            .declare(payload_hash())
+            .access_list_keys();
+        let malformed = [
+            vec![keys[0]],
+            vec![keys[1]],
+            vec![keys[0], keys[0]],
+            vec![keys[0], keys[1], keys[1]],
+        ];
+        for keys in malformed {
+            assert_eq!(
+                decode_access_list_keys(&keys),
+                Err(InteropError::MalformedAccessList)
+            );
+        }
+        assert_eq!(decode_access_list_keys(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn validate_against_oracle() {
+        let identifier = identifier(U256::from(10));
+        let oracle = InMemoryMessageOracle::default().with_log(identifier, payload_hash());
+        let message = identifier.declare(payload_hash());
+
+        assert_eq!(message.validate(&oracle, 1_000), Ok(()));
+        assert_eq!(
+            message.validate(&oracle, 1_000 + MESSAGE_EXPIRY_WINDOW),
+            Ok(())
+        );
+        assert_eq!(
+            message.validate(&oracle, 999),
+            Err(InteropError::FutureMessage)
+        );
+        assert_eq!(
+            message.validate(&oracle, 1_001 + MESSAGE_EXPIRY_WINDOW),
+            Err(InteropError::ExpiredMessage)
+        );
+
+        let invalid = [
+            (
+                identifier.declare(B256::ZERO),
+                InteropError::ChecksumMismatch,
+            ),
+            (
+                DeclaredMessage {
+                    log_index: 4,
+                    ..message
+                },
+                InteropError::UnknownMessage,
+            ),
+            (
+                DeclaredMessage {
+                    timestamp: 999,
+                    ..message
+                },
+                InteropError::TimestampMismatch,
+            ),
+            (
+                MessageIdentifier {
+                    origin: Address::ZERO,
+                    ..identifier
+                }
+                .declare(payload_hash()),
+                InteropError::ChecksumMismatch,
+            ),
+        ];
+        for (message, error) in invalid {
+            assert_eq!(message.validate(&oracle, 1_000), Err(error));
+        }
+    }
+}
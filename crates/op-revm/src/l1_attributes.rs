@@ -0,0 +1,560 @@
+//! Encoding and decoding of the L1 attributes deposit transaction calldata.
+//!
+//! The first transaction of every L2 block is a deposit that calls the `L1Block` predeploy with
+//! the attributes of the L1 origin block. Decoding its calldata gives the same [`L1BlockInfo`]
+//! that [`L1BlockInfo::try_fetch`] reads from the predeploy storage once the transaction has been
+//! executed, without a database.
+//!
+//! Bedrock uses the ABI encoded `setL1BlockValues` call, while Ecotone, Isthmus and Jovian use
+//! tightly packed calls without arguments that append new fields at the end.
+use crate::{L1BlockInfo, OpSpecId};
+use core::fmt::{self, Display};
+use revm::primitives::{Bytes, B256, U256};
+use std::vec::Vec;
+
+/// Selector of `setL1BlockValues(uint64,uint64,uint256,bytes32,uint64,bytes32,uint256,uint256)`.
+pub const BEDROCK_L1_ATTRIBUTES_SELECTOR: [u8; 4] = [0x01, 0x5d, 0x8e, 0xb9];
+
+/// Selector of `setL1BlockValuesEcotone()`.
+pub const ECOTONE_L1_ATTRIBUTES_SELECTOR: [u8; 4] = [0x44, 0x0a, 0x5e, 0x20];
+
+/// Selector of `setL1BlockValuesIsthmus()`.
+pub const ISTHMUS_L1_ATTRIBUTES_SELECTOR: [u8; 4] = [0x09, 0x89, 0x99, 0xbe];
+
+/// Selector of `setL1BlockValuesJovian()`.
+pub const JOVIAN_L1_ATTRIBUTES_SELECTOR: [u8; 4] = [0x3d, 0xb6, 0xbe, 0x2b];
+
+/// Calldata format of the L1 attributes deposit transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum L1AttributesFormat {
+    /// ABI encoded `setL1BlockValues`, used before Ecotone.
+    Bedrock,
+    /// Packed `setL1BlockValuesEcotone`, used from Ecotone.
+    Ecotone,
+    /// Packed `setL1BlockValuesIsthmus`, used from Isthmus.
+    Isthmus,
+    /// Packed `setL1BlockValuesJovian`, used from Jovian.
+    Jovian,
+}
+
+impl L1AttributesFormat {
+    /// Returns the format used by blocks of the given spec.
+    ///
+    /// The first block of a hardfork still uses the format of the previous one, as the
+    /// `L1Block` predeploy is upgraded by the deposits that follow the L1 attributes transaction.
+    pub const fn for_spec(spec_id: OpSpecId) -> Self {
+        if spec_id.is_enabled_in(OpSpecId::JOVIAN) {
+            Self::Jovian
+        } else if spec_id.is_enabled_in(OpSpecId::ISTHMUS) {
+            Self::Isthmus
+        } else if spec_id.is_enabled_in(OpSpecId::ECOTONE) {
+            Self::Ecotone
+        } else {
+            Self::Bedrock
+        }
+    }
+
+    /// Returns the format with the given function selector.
+    pub const fn from_selector(selector: [u8; 4]) -> Option<Self> {
+        match selector {
+            BEDROCK_L1_ATTRIBUTES_SELECTOR => Some(Self::Bedrock),
+            ECOTONE_L1_ATTRIBUTES_SELECTOR => Some(Self::Ecotone),
+            ISTHMUS_L1_ATTRIBUTES_SELECTOR => Some(Self::Isthmus),
+            JOVIAN_L1_ATTRIBUTES_SELECTOR => Some(Self::Jovian),
+            _ => None,
+        }
+    }
+
+    /// Returns the function selector.
+    pub const fn selector(self) -> [u8; 4] {
+        match self {
+            Self::Bedrock => BEDROCK_L1_ATTRIBUTES_SELECTOR,
+            Self::Ecotone => ECOTONE_L1_ATTRIBUTES_SELECTOR,
+            Self::Isthmus => ISTHMUS_L1_ATTRIBUTES_SELECTOR,
+            Self::Jovian => JOVIAN_L1_ATTRIBUTES_SELECTOR,
+        }
+    }
+
+    /// Returns the calldata length, including the selector.
+    pub const fn calldata_len(self) -> usize {
+        match self {
+            // Selector and eight 32 byte words.
+            Self::Bedrock => 4 + 8 * 32,
+            // Selector, two u32 scalars, three u64 and four 32 byte words.
+            Self::Ecotone => 4 + 2 * 4 + 3 * 8 + 4 * 32,
+            // Operator fee scalar and constant.
+            Self::Isthmus => Self::Ecotone.calldata_len() + 4 + 8,
+            // DA footprint gas scalar.
+            Self::Jovian => Self::Isthmus.calldata_len() + 2,
+        }
+    }
+
+    /// Returns the first spec that uses this format.
+    pub const fn spec_id(self) -> OpSpecId {
+        match self {
+            Self::Bedrock => OpSpecId::BEDROCK,
+            Self::Ecotone => OpSpecId::ECOTONE,
+            Self::Isthmus => OpSpecId::ISTHMUS,
+            Self::Jovian => OpSpecId::JOVIAN,
+        }
+    }
+}
+
+/// Attributes of the L1 origin block set by the L1 attributes deposit transaction.
+///
+/// Fields that are not part of a format are zero after decoding it and ignored when encoding it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct L1Attributes {
+    /// Number of the L1 origin block.
+    pub number: u64,
+    /// Timestamp of the L1 origin block.
+    pub timestamp: u64,
+    /// Base fee of the L1 origin block.
+    pub base_fee: U256,
+    /// Hash of the L1 origin block.
+    pub hash: B256,
+    /// Number of L2 blocks since the start of the epoch.
+    pub sequence_number: u64,
+    /// Versioned hash of the batcher address.
+    pub batcher_hash: B256,
+    /// L1 fee overhead. Bedrock only.
+    pub l1_fee_overhead: U256,
+    /// L1 fee scalar. Bedrock only.
+    pub l1_fee_scalar: U256,
+    /// Blob base fee of the L1 origin block. Since Ecotone.
+    pub blob_base_fee: U256,
+    /// L1 base fee scalar. Since Ecotone.
+    pub base_fee_scalar: u32,
+    /// L1 blob base fee scalar. Since Ecotone.
+    pub blob_base_fee_scalar: u32,
+    /// Operator fee scalar. Since Isthmus.
+    pub operator_fee_scalar: u32,
+    /// Operator fee constant. Since Isthmus.
+    pub operator_fee_constant: u64,
+    /// DA footprint gas scalar. Since Jovian.
+    pub da_footprint_gas_scalar: u16,
+}
+
+impl L1Attributes {
+    /// Decodes the calldata of the L1 attributes deposit transaction.
+    ///
+    /// The format is selected by the function selector.
+    pub fn decode(calldata: &[u8]) -> Result<(L1AttributesFormat, Self), L1AttributesError> {
+        let selector: [u8; 4] = calldata
+            .get(..4)
+            .and_then(|selector| selector.try_into().ok())
+            .ok_or(L1AttributesError::InvalidLength {
+                expected: 4,
+                actual: calldata.len(),
+            })?;
+        let format = L1AttributesFormat::from_selector(selector)
+            .ok_or(L1AttributesError::UnknownSelector(selector))?;
+        if calldata.len() != format.calldata_len() {
+            return Err(L1AttributesError::InvalidLength {
+                expected: format.calldata_len(),
+                actual: calldata.len(),
+            });
+        }
+
+        let mut reader = Reader(&calldata[4..]);
+        let mut attributes = Self::default();
+        if format == L1AttributesFormat::Bedrock {
+            attributes.number = reader.abi_u64()?;
+            attributes.timestamp = reader.abi_u64()?;
+            attributes.base_fee = reader.u256();
+            attributes.hash = reader.b256();
+            attributes.sequence_number = reader.abi_u64()?;
+            attributes.batcher_hash = reader.b256();
+            attributes.l1_fee_overhead = reader.u256();
+            attributes.l1_fee_scalar = reader.u256();
+            return Ok((format, attributes));
+        }
+
+        attributes.base_fee_scalar = u32::from_be_bytes(reader.take());
+        attributes.blob_base_fee_scalar = u32::from_be_bytes(reader.take());
+        attributes.sequence_number = u64::from_be_bytes(reader.take());
+        attributes.timestamp = u64::from_be_bytes(reader.take());
+        attributes.number = u64::from_be_bytes(reader.take());
+        attributes.base_fee = reader.u256();
+        attributes.blob_base_fee = reader.u256();
+        attributes.hash = reader.b256();
+        attributes.batcher_hash = reader.b256();
+        if format == L1AttributesFormat::Ecotone {
+            return Ok((format, attributes));
+        }
+
+        attributes.operator_fee_scalar = u32::from_be_bytes(reader.take());
+        attributes.operator_fee_constant = u64::from_be_bytes(reader.take());
+        if format == L1AttributesFormat::Isthmus {
+            return Ok((format, attributes));
+        }
+
+        attributes.da_footprint_gas_scalar = u16::from_be_bytes(reader.take());
+        Ok((format, attributes))
+    }
+
+    /// Encodes the attributes as the calldata of the L1 attributes deposit transaction.
+    pub fn encode(&self, format: L1AttributesFormat) -> Bytes {
+        let mut out = Vec::with_capacity(format.calldata_len());
+        out.extend_from_slice(&format.selector());
+
+        if format == L1AttributesFormat::Bedrock {
+            out.extend_from_slice(&U256::from(self.number).to_be_bytes::<32>());
+            out.extend_from_slice(&U256::from(self.timestamp).to_be_bytes::<32>());
+            out.extend_from_slice(&self.base_fee.to_be_bytes::<32>());
+            out.extend_from_slice(self.hash.as_slice());
+            out.extend_from_slice(&U256::from(self.sequence_number).to_be_bytes::<32>());
+            out.extend_from_slice(self.batcher_hash.as_slice());
+            out.extend_from_slice(&self.l1_fee_overhead.to_be_bytes::<32>());
+            out.extend_from_slice(&self.l1_fee_scalar.to_be_bytes::<32>());
+            return out.into();
+        }
+
+        out.extend_from_slice(&self.base_fee_scalar.to_be_bytes());
+        out.extend_from_slice(&self.blob_base_fee_scalar.to_be_bytes());
+        out.extend_from_slice(&self.sequence_number.to_be_bytes());
+        out.extend_from_slice(&self.timestamp.to_be_bytes());
+        out.extend_from_slice(&self.number.to_be_bytes());
+        out.extend_from_slice(&self.base_fee.to_be_bytes::<32>());
+        out.extend_from_slice(&self.blob_base_fee.to_be_bytes::<32>());
+        out.extend_from_slice(self.hash.as_slice());
+        out.extend_from_slice(self.batcher_hash.as_slice());
+        if format == L1AttributesFormat::Ecotone {
+            return out.into();
+        }
+
+        out.extend_from_slice(&self.operator_fee_scalar.to_be_bytes());
+        out.extend_from_slice(&self.operator_fee_constant.to_be_bytes());
+        if format == L1AttributesFormat::Isthmus {
+            return out.into();
+        }
+
+        out.extend_from_slice(&self.da_footprint_gas_scalar.to_be_bytes());
+        out.into()
+    }
+
+    /// Returns the L1 block info of an L2 block of the given spec whose L1 attributes were
+    /// decoded from the given format.
+    ///
+    /// Ecotone scalars are empty if the Ecotone formats set both of them to zero, or if the
+    /// Bedrock format is used in the first Ecotone block. In the latter case the Bedrock overhead
+    /// and scalar are kept for the Bedrock cost function, while the overhead is not part of the
+    /// Ecotone formats and has to be set by the caller if needed.
+    pub fn into_l1_block_info(
+        self,
+        format: L1AttributesFormat,
+        l2_block: U256,
+        spec_id: OpSpecId,
+    ) -> Result<L1BlockInfo, L1AttributesError> {
+        if !spec_id.is_enabled_in(format.spec_id()) {
+            return Err(L1AttributesError::UnsupportedFormat { format, spec_id });
+        }
+
+        let mut out = L1BlockInfo {
+            l2_block: Some(l2_block),
+            l1_base_fee: self.base_fee,
+            ..Default::default()
+        };
+
+        if format == L1AttributesFormat::Bedrock {
+            out.l1_base_fee_scalar = self.l1_fee_scalar;
+            out.l1_fee_overhead = Some(self.l1_fee_overhead);
+            if spec_id.is_enabled_in(OpSpecId::ECOTONE) {
+                out.l1_blob_base_fee = Some(U256::ZERO);
+                out.l1_blob_base_fee_scalar = Some(U256::ZERO);
+                out.empty_ecotone_scalars = true;
+            }
+        } else {
+            out.l1_base_fee_scalar = U256::from(self.base_fee_scalar);
+            out.l1_blob_base_fee = Some(self.blob_base_fee);
+            out.l1_blob_base_fee_scalar = Some(U256::from(self.blob_base_fee_scalar));
+            out.empty_ecotone_scalars = self.base_fee_scalar == 0 && self.blob_base_fee_scalar == 0;
+        }
+
+        if spec_id.is_enabled_in(OpSpecId::ISTHMUS) {
+            out.operator_fee_scalar = Some(U256::from(self.operator_fee_scalar));
+            out.operator_fee_constant = Some(U256::from(self.operator_fee_constant));
+        }
+
+        if spec_id.is_enabled_in(OpSpecId::JOVIAN) {
+            out.da_footprint_gas_scalar = Some(self.da_footprint_gas_scalar);
+        }
+
+        Ok(out)
+    }
+}
+
+impl L1BlockInfo {
+    /// Decodes the L1 block info from the calldata of the L1 attributes deposit transaction of an
+    /// L2 block.
+    ///
+    /// See [`L1Attributes::into_l1_block_info`] for the fields that can not be decoded.
+    pub fn try_from_l1_attributes(
+        calldata: &[u8],
+        l2_block: U256,
+        spec_id: OpSpecId,
+    ) -> Result<L1BlockInfo, L1AttributesError> {
+        let (format, attributes) = L1Attributes::decode(calldata)?;
+        attributes.into_l1_block_info(format, l2_block, spec_id)
+    }
+}
+
+/// Error returned when decoding the L1 attributes deposit transaction calldata.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum L1AttributesError {
+    /// The function selector does not match any format.
+    UnknownSelector([u8; 4]),
+    /// The calldata length does not match the format.
+    InvalidLength {
+        /// Expected length.
+        expected: usize,
+        /// Actual length.
+        actual: usize,
+    },
+    /// An ABI encoded `uint64` argument does not fit in 64 bits.
+    InvalidUint64,
+    /// The format is not used by the spec.
+    UnsupportedFormat {
+        /// Format of the calldata.
+        format: L1AttributesFormat,
+        /// Spec of the L2 block.
+        spec_id: OpSpecId,
+    },
+}
+
+impl Display for L1AttributesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSelector(selector) => {
+                write!(f, "unknown L1 attributes selector 0x")?;
+                selector.iter().try_for_each(|b| write!(f, "{b:02x}"))
+            }
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "invalid L1 attributes length: expected {expected} bytes, got {actual}"
+            ),
+            Self::InvalidUint64 => f.write_str("L1 attributes uint64 argument out of range"),
+            Self::UnsupportedFormat { format, spec_id } => {
+                write!(
+                    f,
+                    "{format:?} L1 attributes are not supported in {spec_id:?}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for L1AttributesError {}
+
+/// Reads fields from calldata whose length has already been checked.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, tail) = self.0.split_at(N);
+        self.0 = tail;
+        head.try_into().unwrap()
+    }
+
+    fn b256(&mut self) -> B256 {
+        B256::new(self.take())
+    }
+
+    fn u256(&mut self) -> U256 {
+        U256::from_be_bytes(self.take::<32>())
+    }
+
+    fn abi_u64(&mut self) -> Result<u64, L1AttributesError> {
+        self.u256()
+            .try_into()
+            .map_err(|_| L1AttributesError::InvalidUint64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{b256, keccak256};
+
+    const SPECS: [OpSpecId; 11] = [
+        OpSpecId::BEDROCK,
+        OpSpecId::REGOLITH,
+        OpSpecId::CANYON,
+        OpSpecId::ECOTONE,
+        OpSpecId::FJORD,
+        OpSpecId::GRANITE,
+        OpSpecId::HOLOCENE,
+        OpSpecId::ISTHMUS,
+        OpSpecId::JOVIAN,
+        OpSpecId::INTEROP,
+        OpSpecId::OSAKA,
+    ];
+
+    /// Attributes with only the fields of the given format set.
+    fn sample(format: L1AttributesFormat) -> L1Attributes {
+        let mut attributes = L1Attributes {
+            number: 21_000_000,
+            timestamp: 1_733_000_000,
+            base_fee: U256::from(7_000_000_000u64),
+            hash: b256!("0x8a1f5e3c9b2d4f6a7e8c0b1d2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a"),
+            sequence_number: 3,
+            batcher_hash: b256!(
+                "0x0000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f32985"
+            ),
+            ..Default::default()
+        };
+        if format == L1AttributesFormat::Bedrock {
+            attributes.l1_fee_overhead = U256::from(188);
+            attributes.l1_fee_scalar = U256::from(684_000);
+            return attributes;
+        }
+        attributes.blob_base_fee = U256::from(1);
+        attributes.base_fee_scalar = 5_227;
+        attributes.blob_base_fee_scalar = 1_014_213;
+        if format == L1AttributesFormat::Ecotone {
+            return attributes;
+        }
+        attributes.operator_fee_scalar = 1_500;
+        attributes.operator_fee_constant = 2_000;
+        if format == L1AttributesFormat::Isthmus {
+            return attributes;
+        }
+        attributes.da_footprint_gas_scalar = 400;
+        attributes
+    }
+
+    #[test]
+    fn test_selectors() {
+        let signatures = [
+            (
+                L1AttributesFormat::Bedrock,
+                "setL1BlockValues(uint64,uint64,uint256,bytes32,uint64,bytes32,uint256,uint256)",
+            ),
+            (L1AttributesFormat::Ecotone, "setL1BlockValuesEcotone()"),
+            (L1AttributesFormat::Isthmus, "setL1BlockValuesIsthmus()"),
+            (L1AttributesFormat::Jovian, "setL1BlockValuesJovian()"),
+        ];
+        for (format, signature) in signatures {
+            assert_eq!(format.selector(), keccak256(signature)[..4], "{format:?}");
+            assert_eq!(
+                L1AttributesFormat::from_selector(format.selector()),
+                Some(format)
+            );
+        }
+        assert_eq!(L1AttributesFormat::Bedrock.calldata_len(), 260);
+        assert_eq!(L1AttributesFormat::Ecotone.calldata_len(), 164);
+        assert_eq!(L1AttributesFormat::Isthmus.calldata_len(), 176);
+        assert_eq!(L1AttributesFormat::Jovian.calldata_len(), 178);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for spec_id in SPECS {
+            let format = L1AttributesFormat::for_spec(spec_id);
+            let attributes = sample(format);
+            let calldata = attributes.encode(format);
+            assert_eq!(calldata.len(), format.calldata_len(), "{spec_id:?}");
+            assert_eq!(
+                L1Attributes::decode(&calldata),
+                Ok((format, attributes)),
+                "{spec_id:?}"
+            );
+
+            let info =
+                L1BlockInfo::try_from_l1_attributes(&calldata, U256::from(42), spec_id).unwrap();
+            assert_eq!(info.l2_block, Some(U256::from(42)));
+            assert_eq!(info.l1_base_fee, attributes.base_fee);
+            assert!(!info.empty_ecotone_scalars);
+            if format == L1AttributesFormat::Bedrock {
+                assert_eq!(info.l1_base_fee_scalar, attributes.l1_fee_scalar);
+                assert_eq!(info.l1_fee_overhead, Some(attributes.l1_fee_overhead));
+                assert_eq!(info.l1_blob_base_fee, None);
+            } else {
+                assert_eq!(info.l1_base_fee_scalar, U256::from(5_227));
+                assert_eq!(info.l1_fee_overhead, None);
+                assert_eq!(info.l1_blob_base_fee, Some(U256::from(1)));
+                assert_eq!(info.l1_blob_base_fee_scalar, Some(U256::from(1_014_213)));
+            }
+            let isthmus = spec_id.is_enabled_in(OpSpecId::ISTHMUS);
+            assert_eq!(
+                info.operator_fee_scalar,
+                isthmus.then_some(U256::from(1_500))
+            );
+            assert_eq!(
+                info.operator_fee_constant,
+                isthmus.then_some(U256::from(2_000))
+            );
+            assert_eq!(
+                info.da_footprint_gas_scalar,
+                spec_id.is_enabled_in(OpSpecId::JOVIAN).then_some(400)
+            );
+        }
+    }
+
+    #[test]
+    fn test_activation_block() {
+        // The first Ecotone block still uses the Bedrock format.
+        let attributes = sample(L1AttributesFormat::Bedrock);
+        let calldata = attributes.encode(L1AttributesFormat::Bedrock);
+        let info =
+            L1BlockInfo::try_from_l1_attributes(&calldata, U256::ZERO, OpSpecId::ECOTONE).unwrap();
+        assert!(info.empty_ecotone_scalars);
+        assert_eq!(info.l1_fee_overhead, Some(U256::from(188)));
+        assert_eq!(info.l1_base_fee_scalar, U256::from(684_000));
+
+        // The first Isthmus block still uses the Ecotone format.
+        let calldata = sample(L1AttributesFormat::Ecotone).encode(L1AttributesFormat::Ecotone);
+        let info =
+            L1BlockInfo::try_from_l1_attributes(&calldata, U256::ZERO, OpSpecId::ISTHMUS).unwrap();
+        assert_eq!(info.operator_fee_scalar, Some(U256::ZERO));
+        assert_eq!(info.operator_fee_constant, Some(U256::ZERO));
+
+        // Newer formats are not valid before their hardfork.
+        let calldata = sample(L1AttributesFormat::Jovian).encode(L1AttributesFormat::Jovian);
+        assert_eq!(
+            L1BlockInfo::try_from_l1_attributes(&calldata, U256::ZERO, OpSpecId::ISTHMUS),
+            Err(L1AttributesError::UnsupportedFormat {
+                format: L1AttributesFormat::Jovian,
+                spec_id: OpSpecId::ISTHMUS,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let calldata = sample(L1AttributesFormat::Isthmus).encode(L1AttributesFormat::Isthmus);
+        assert_eq!(
+            L1Attributes::decode(&calldata[..100]),
+            Err(L1AttributesError::InvalidLength {
+                expected: 176,
+                actual: 100,
+            })
+        );
+        assert_eq!(
+            L1Attributes::decode(&calldata[..2]),
+            Err(L1AttributesError::InvalidLength {
+                expected: 4,
+                actual: 2,
+            })
+        );
+        let err = L1Attributes::decode(&[0xde, 0xad, 0xbe, 0xef]).unwrap_err();
+        assert_eq!(
+            err,
+            L1AttributesError::UnknownSelector([0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(err.to_string(), "unknown L1 attributes selector 0xdeadbeef");
+
+        let mut calldata = sample(L1AttributesFormat::Bedrock)
+            .encode(L1AttributesFormat::Bedrock)
+            .to_vec();
+        // Set a bit above the 64 bits of the block number.
+        calldata[4 + 23] = 1;
+        assert_eq!(
+            L1Attributes::decode(&calldata),
+            Err(L1AttributesError::InvalidUint64)
+        );
+    }
+}
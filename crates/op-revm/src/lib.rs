@@ -6,10 +6,17 @@
 extern crate alloc as std;
 
 pub mod api;
+pub mod base_fee;
 pub mod constants;
+pub mod da_footprint;
 pub mod evm;
 pub mod fast_lz;
+pub mod fee_estimate;
+pub mod fee_vaults;
 pub mod handler;
+pub mod hardforks;
+pub mod interop;
+pub mod l1_attributes;
 pub mod l1block;
 pub mod precompiles;
 pub mod result;
@@ -22,6 +29,6 @@ pub use api::{
 };
 pub use evm::OpEvm;
 pub use l1block::L1BlockInfo;
-pub use result::OpHaltReason;
+pub use result::{DepositReceipt, OpExecutionResult, OpHaltReason};
 pub use spec::*;
 pub use transaction::{error::OpTransactionError, estimate_tx_compressed_size, OpTransaction};
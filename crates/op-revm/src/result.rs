@@ -1,5 +1,6 @@
-//! Contains the `[OpHaltReason]` type.
-use revm::context_interface::result::HaltReason;
+//! Contains the `[OpHaltReason]`, `[OpExecutionResult]` and `[DepositReceipt]` types.
+use crate::OpSpecId;
+use revm::context_interface::result::{ExecutionResult, HaltReason};
 
 /// Optimism halt reason.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -11,6 +12,69 @@ pub enum OpHaltReason {
     FailedDeposit,
 }
 
+/// Result of a transaction executed by the [`OpEvm`](crate::OpEvm).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpExecutionResult {
+    /// Result of the execution.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub result: ExecutionResult<OpHaltReason>,
+    /// Deposit specific receipt fields. Set for deposit transactions, including failed deposits.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub deposit_receipt: Option<DepositReceipt>,
+}
+
+impl OpExecutionResult {
+    /// Creates a new result from the execution result and the deposit receipt fields.
+    pub const fn new(
+        result: ExecutionResult<OpHaltReason>,
+        deposit_receipt: Option<DepositReceipt>,
+    ) -> Self {
+        Self {
+            result,
+            deposit_receipt,
+        }
+    }
+}
+
+impl From<ExecutionResult<OpHaltReason>> for OpExecutionResult {
+    fn from(result: ExecutionResult<OpHaltReason>) -> Self {
+        Self::new(result, None)
+    }
+}
+
+/// Deposit specific fields of the receipt of a deposit transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepositReceipt {
+    /// Nonce of the depositor before execution. Set from Regolith.
+    pub deposit_nonce: Option<u64>,
+    /// Version of the deposit receipt. Set to `1` from Canyon.
+    pub deposit_receipt_version: Option<u64>,
+}
+
+impl DepositReceipt {
+    /// Returns the receipt fields of a deposit executed on `spec` by a depositor with the given
+    /// nonce before execution.
+    pub const fn new(spec: OpSpecId, nonce: u64) -> Self {
+        Self {
+            deposit_nonce: if spec.is_enabled_in(OpSpecId::REGOLITH) {
+                Some(nonce)
+            } else {
+                None
+            },
+            deposit_receipt_version: if spec.is_enabled_in(OpSpecId::CANYON) {
+                Some(1)
+            } else {
+                None
+            },
+        }
+    }
+}
+
 impl From<HaltReason> for OpHaltReason {
     fn from(value: HaltReason) -> Self {
         Self::Base(value)
@@ -1,4 +1,5 @@
 //! Contains the `[OpTransactionError]` type.
+use crate::interop::InteropError;
 use core::fmt::Display;
 use revm::context_interface::{
     result::{EVMError, InvalidTransaction},
@@ -46,6 +47,21 @@ pub enum OpTransactionError {
     /// Non-deposit transactions on Optimism must have `enveloped_tx` field set
     /// to properly calculate L1 costs.
     MissingEnvelopedTx,
+    /// Executing message declared in the access list of the
+    /// [`CrossL2Inbox`](crate::interop::CROSS_L2_INBOX_ADDRESS) is invalid.
+    ///
+    /// Only checked from the Interop hardfork.
+    InvalidExecutingMessage(InteropError),
+    /// Including the transaction would exceed the DA footprint limit of the block.
+    ///
+    /// Only checked from the Jovian hardfork, see
+    /// [`DaFootprintAccumulator`](crate::da_footprint::DaFootprintAccumulator).
+    BlockDaFootprintLimitExceeded {
+        /// DA footprint of the transaction.
+        tx_da_footprint: u64,
+        /// DA footprint still available in the block.
+        available: u64,
+    },
 }
 
 impl TransactionError for OpTransactionError {}
@@ -72,12 +88,30 @@ impl Display for OpTransactionError {
                     "missing enveloped transaction bytes for non-deposit transaction"
                 )
             }
+            Self::InvalidExecutingMessage(error) => {
+                write!(f, "invalid executing message: {error}")
+            }
+            Self::BlockDaFootprintLimitExceeded {
+                tx_da_footprint,
+                available,
+            } => {
+                write!(
+                    f,
+                    "transaction DA footprint {tx_da_footprint} exceeds the {available} available in the block"
+                )
+            }
         }
     }
 }
 
 impl core::error::Error for OpTransactionError {}
 
+impl From<InteropError> for OpTransactionError {
+    fn from(value: InteropError) -> Self {
+        Self::InvalidExecutingMessage(value)
+    }
+}
+
 impl From<InvalidTransaction> for OpTransactionError {
     fn from(value: InvalidTransaction) -> Self {
         Self::Base(value)
@@ -114,6 +148,18 @@ mod test {
             OpTransactionError::MissingEnvelopedTx.to_string(),
             "missing enveloped transaction bytes for non-deposit transaction"
         );
+        assert_eq!(
+            OpTransactionError::InvalidExecutingMessage(InteropError::ChecksumMismatch).to_string(),
+            "invalid executing message: message checksum mismatch"
+        );
+        assert_eq!(
+            OpTransactionError::BlockDaFootprintLimitExceeded {
+                tx_da_footprint: 40_000,
+                available: 10_000,
+            }
+            .to_string(),
+            "transaction DA footprint 40000 exceeds the 10000 available in the block"
+        );
     }
 
     #[cfg(feature = "serde")]
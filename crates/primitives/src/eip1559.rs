@@ -0,0 +1,12 @@
+//! EIP-1559: Fee market change for ETH 1.0 chain
+//!
+//! Constants of the base fee update rule introduced in the London hard fork.
+
+/// Bounds the amount the base fee can change between blocks.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Bounds the maximum gas limit of a block relative to its gas target.
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+
+/// Base fee of the first London block.
+pub const INITIAL_BASE_FEE: u64 = 1_000_000_000;
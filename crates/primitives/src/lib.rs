@@ -16,6 +16,7 @@
 extern crate alloc as std;
 
 pub mod constants;
+pub mod eip1559;
 pub mod eip170;
 pub mod eip3860;
 pub mod eip4844;